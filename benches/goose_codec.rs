@@ -1,8 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use iec_61850_lib::decode_basics::decode_ethernet_header;
-use iec_61850_lib::decode_goose::{decode_goose_pdu, is_goose_frame};
-use iec_61850_lib::encode_goose::{encode_ethernet_header, encode_goose};
-use iec_61850_lib::types::{EthernetHeader, IECData, IECGoosePdu, TimeQuality, Timestamp};
+use iec_61850::decode_basics::decode_ethernet_header;
+use iec_61850::decode_goose::{decode_goose_header, decode_goose_pdu, is_goose_frame};
+use iec_61850::encode_goose::{encode_ethernet_header, encode_goose};
+use iec_61850::types::{EthernetHeader, IECData, IECGoosePdu, TimeQuality, Timestamp};
 
 /// Create sample GOOSE PDU for encoding with realistic data size
 /// Typical GOOSE frames contain 50-200 data points
@@ -114,6 +114,22 @@ fn benchmark_goose_pdu_decode(c: &mut Criterion) {
     });
 }
 
+fn benchmark_goose_header_decode(c: &mut Criterion) {
+    let packet = create_large_goose_packet();
+    let mut header = EthernetHeader::default();
+    let pos = decode_ethernet_header(&mut header, &packet);
+    let mut group = c.benchmark_group("goose_header_vs_pdu_decode");
+
+    group.bench_function("header", |b| {
+        b.iter(|| decode_goose_header(black_box(&packet), black_box(pos)));
+    });
+    group.bench_function("pdu", |b| {
+        b.iter(|| decode_goose_pdu(black_box(&packet), black_box(pos)));
+    });
+
+    group.finish();
+}
+
 fn benchmark_full_goose_decode(c: &mut Criterion) {
     let packet = create_large_goose_packet();
 
@@ -223,6 +239,7 @@ criterion_group!(
     benches,
     benchmark_goose_frame_detection,
     benchmark_goose_pdu_decode,
+    benchmark_goose_header_decode,
     benchmark_full_goose_decode,
     benchmark_ethernet_header_encode,
     benchmark_goose_pdu_encode,
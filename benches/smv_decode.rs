@@ -1,7 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use iec_61850_lib::decode_smv::decode_smv;
-use iec_61850_lib::encode_smv::encode_smv;
-use iec_61850_lib::types::{EthernetHeader, Sample, SavAsdu, SavPdu};
+use iec_61850::decode_smv::{
+    decode_smv, decode_smv_nth_asdu, decode_smv_nth_asdu_view, smv_samples,
+};
+use iec_61850::encode_smv::encode_smv;
+use iec_61850::types::{EthernetHeader, Sample, SampleValue, SavAsdu, SavPdu};
 
 /// Diagnostic function to validate packet structure
 fn validate_packet(packet: &[u8], name: &str) {
@@ -684,6 +686,76 @@ fn benchmark_smv_roundtrip(c: &mut Criterion) {
     group.finish();
 }
 
+/// Sums the integer samples of all ASDUs, once from the decoded PDU with its `Vec`s
+/// and once through `SampleIter`, which allocates nothing
+fn benchmark_sample_iter(c: &mut Criterion) {
+    let packet = create_max_stress_smv_packet();
+    let int_value = |value: &SampleValue| match value {
+        SampleValue::Int(value) => *value as i64,
+        SampleValue::Float(_) => 0,
+    };
+
+    let mut group = c.benchmark_group("sample_iter_vs_vec");
+
+    group.bench_function("decode_smv_8x32", |b| {
+        b.iter(|| {
+            let pdu = decode_smv(black_box(&packet), black_box(22)).unwrap();
+            pdu.sav_asdu
+                .iter()
+                .flat_map(|asdu| asdu.all_data.iter())
+                .map(|sample| int_value(&sample.value))
+                .sum::<i64>()
+        });
+    });
+
+    group.bench_function("sample_iter_8x32", |b| {
+        b.iter(|| {
+            (0..8)
+                .flat_map(|n| smv_samples(black_box(&packet), black_box(22), n).unwrap())
+                .map(|sample| int_value(&sample.unwrap().value))
+                .sum::<i64>()
+        });
+    });
+
+    group.finish();
+}
+
+/// Checks the svID of every ASDU, once with the owned `SavAsdu` that allocates the
+/// svID, data set reference and samples, and once with the borrowing `SavAsduView`
+fn benchmark_asdu_view(c: &mut Criterion) {
+    let packet = create_max_stress_smv_packet();
+
+    let mut group = c.benchmark_group("asdu_view_vs_owned");
+
+    group.bench_function("nth_asdu_8x32", |b| {
+        b.iter(|| {
+            (0..8)
+                .filter(|&n| {
+                    decode_smv_nth_asdu(black_box(&packet), black_box(22), n)
+                        .unwrap()
+                        .msv_id
+                        == "IED1/LLN0$MSVCB01"
+                })
+                .count()
+        });
+    });
+
+    group.bench_function("nth_asdu_view_8x32", |b| {
+        b.iter(|| {
+            (0..8)
+                .filter(|&n| {
+                    decode_smv_nth_asdu_view(black_box(&packet), black_box(22), n)
+                        .unwrap()
+                        .msv_id
+                        == "IED1/LLN0$MSVCB01"
+                })
+                .count()
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_full_smv_decode,
@@ -692,6 +764,8 @@ criterion_group!(
     benchmark_decode_comparison,
     benchmark_smv_encode,
     benchmark_smv_encode_comparison,
-    benchmark_smv_roundtrip
+    benchmark_smv_roundtrip,
+    benchmark_sample_iter,
+    benchmark_asdu_view
 );
 criterion_main!(benches);
@@ -0,0 +1,660 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::MIN_FRAME_LENGTH;
+use crate::decode_goose::{decode_goose_pdu, is_goose_frame};
+use crate::decode_smv::{decode_smv, is_smv_frame};
+use crate::types::{
+    DecodeError, EthernetHeader, IECData, IECGoosePdu, RetransmissionSchedule, SavPdu,
+};
+
+/// A captured Ethernet frame decoded into the protocol it carries
+#[derive(Debug, PartialEq)]
+pub enum DecodedFrame {
+    Goose(EthernetHeader, IECGoosePdu),
+    Smv(EthernetHeader, SavPdu),
+    /// Any frame that is neither GOOSE nor Sampled Values
+    Other,
+}
+
+impl DecodedFrame {
+    /// Classifies a raw Ethernet frame by its EtherType and decodes GOOSE and
+    /// Sampled Values frames.
+    ///
+    /// # Parameters
+    /// - `buffer`: The complete Ethernet frame.
+    ///
+    /// # Returns
+    /// The decoded frame, or a DecodeError if a GOOSE or SMV frame is malformed.
+    pub fn decode(buffer: &[u8]) -> Result<DecodedFrame, DecodeError> {
+        if is_goose_frame(buffer) {
+            let (header, pos) = EthernetHeader::from_wire(buffer)?;
+            Ok(DecodedFrame::Goose(header, decode_goose_pdu(buffer, pos)?))
+        } else if is_smv_frame(buffer) {
+            let (header, pos) = EthernetHeader::from_wire(buffer)?;
+            Ok(DecodedFrame::Smv(header, decode_smv(buffer, pos)?))
+        } else {
+            Ok(DecodedFrame::Other)
+        }
+    }
+}
+
+/// Classifies a raw Ethernet frame by its EtherType and decodes it, see
+/// `DecodedFrame::decode`.
+///
+/// # Parameters
+/// - `buffer`: The complete Ethernet frame.
+///
+/// # Returns
+/// The decoded GOOSE or SMV frame, `DecodedFrame::Other` for any other protocol, or a
+/// DecodeError if a GOOSE or SMV frame is malformed.
+pub fn decode_frame(buffer: &[u8]) -> Result<DecodedFrame, DecodeError> {
+    DecodedFrame::decode(buffer)
+}
+
+/// A discontinuity in the sample counter of a Sampled Values stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmpCntGap {
+    /** svID of the stream the gap was detected in */
+    pub sv_id: String,
+    /** Sample count that should have followed the previous ASDU */
+    pub expected: u16,
+    /** Sample count that was actually received */
+    pub received: u16,
+}
+
+/// High-level report over a decoded capture
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureSummary {
+    /** Number of GOOSE frames */
+    pub goose_frames: usize,
+    /** Number of Sampled Values frames */
+    pub smv_frames: usize,
+    /** Number of frames of any other protocol */
+    pub other_frames: usize,
+    /** All APPIDs seen in GOOSE and Sampled Values frames */
+    pub appids: BTreeSet<u16>,
+    /** All svIDs seen in Sampled Values ASDUs */
+    pub sv_ids: BTreeSet<String>,
+    /** All goIDs seen in GOOSE frames */
+    pub go_ids: BTreeSet<String>,
+    /** Sample count discontinuities per svID, in capture order */
+    pub smp_cnt_gaps: Vec<SmpCntGap>,
+    /** Number of frames with the simulation flag set */
+    pub simulated_frames: usize,
+}
+
+/// Summarizes a decoded capture: frame counts per protocol, the identifiers seen,
+/// sample count continuity and use of the simulation flag.
+///
+/// Sample counts are tracked per svID. A sample count that does not follow the
+/// previous one of the same stream is reported as gap. A restart at 0 is only the
+/// regular roll-over of the counter after 65535, or after a multiple of `smp_rate`
+/// if the stream sends it.
+///
+/// # Parameters
+/// - `frames`: The decoded frames in capture order.
+///
+/// # Returns
+/// The summary of the capture.
+pub fn summarize_capture(frames: &[DecodedFrame]) -> CaptureSummary {
+    let mut summary = CaptureSummary::default();
+    let mut last_smp_cnt: BTreeMap<&str, u16> = BTreeMap::new();
+
+    for frame in frames {
+        match frame {
+            DecodedFrame::Goose(header, pdu) => {
+                summary.goose_frames += 1;
+                summary.appids.insert(u16::from_be_bytes(header.appid));
+                summary.go_ids.insert(pdu.go_id.clone());
+                if pdu.simulation {
+                    summary.simulated_frames += 1;
+                }
+            }
+            DecodedFrame::Smv(header, pdu) => {
+                summary.smv_frames += 1;
+                summary.appids.insert(u16::from_be_bytes(header.appid));
+                if pdu.sim {
+                    summary.simulated_frames += 1;
+                }
+
+                for asdu in &pdu.sav_asdu {
+                    summary.sv_ids.insert(asdu.msv_id.clone());
+
+                    if let Some(last) = last_smp_cnt.insert(&asdu.msv_id, asdu.smp_cnt) {
+                        let next = last as u32 + 1;
+                        let rate = asdu.smp_rate.filter(|rate| *rate > 0).map(u32::from);
+                        // With smpRate samples per nominal period the counter restarts
+                        // after a multiple of smpRate that depends on the frequency
+                        let wraps = rate.is_some_and(|rate| next.is_multiple_of(rate));
+                        let expected = match rate {
+                            Some(rate) if next == rate => 0,
+                            _ => last.wrapping_add(1),
+                        };
+                        if asdu.smp_cnt != last.wrapping_add(1) && !(wraps && asdu.smp_cnt == 0) {
+                            summary.smp_cnt_gaps.push(SmpCntGap {
+                                sv_id: asdu.msv_id.clone(),
+                                expected,
+                                received: asdu.smp_cnt,
+                            });
+                        }
+                    }
+                }
+            }
+            DecodedFrame::Other => summary.other_frames += 1,
+        }
+    }
+
+    summary
+}
+
+/// A difference between two GOOSE PDUs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GooseDiff {
+    /// A header field differs, the values are given as text
+    Field {
+        name: String,
+        left: String,
+        right: String,
+    },
+    /// The data sets have a different number of entries
+    DataLength { left: usize, right: usize },
+    /// The data set entry at `index` differs
+    Data { index: usize, diff: DataDiff },
+}
+
+/// A difference between two data values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataDiff {
+    /// The values differ in type or value
+    Value { left: IECData, right: IECData },
+    /// The structures or arrays have a different number of elements
+    Length { left: usize, right: usize },
+    /// The element at `index` of a structure or array differs
+    Element { index: usize, diff: Box<DataDiff> },
+}
+
+/// Compares two GOOSE PDUs field by field and data set entry by data set entry.
+///
+/// Structures and arrays are compared element-wise, so a difference deep inside
+/// a data set entry is reported with the path of indices leading to it.
+///
+/// # Parameters
+/// - `a`: The left PDU, e.g. the expected one.
+/// - `b`: The right PDU, e.g. the received one.
+///
+/// # Returns
+/// All differences, header fields first and data set entries in index order.
+pub fn diff_goose_pdu(a: &IECGoosePdu, b: &IECGoosePdu) -> Vec<GooseDiff> {
+    let mut diffs = vec![];
+    let mut field = |name: &str, left: String, right: String| {
+        if left != right {
+            diffs.push(GooseDiff::Field {
+                name: name.to_string(),
+                left,
+                right,
+            });
+        }
+    };
+
+    field("goCbRef", a.go_cb_ref.clone(), b.go_cb_ref.clone());
+    field(
+        "timeAllowedtoLive",
+        a.time_allowed_to_live.to_string(),
+        b.time_allowed_to_live.to_string(),
+    );
+    field("datSet", a.dat_set.clone(), b.dat_set.clone());
+    field("goID", a.go_id.clone(), b.go_id.clone());
+    field("t", format!("{:?}", a.t), format!("{:?}", b.t));
+    field("stNum", a.st_num.to_string(), b.st_num.to_string());
+    field("sqNum", a.sq_num.to_string(), b.sq_num.to_string());
+    field(
+        "simulation",
+        a.simulation.to_string(),
+        b.simulation.to_string(),
+    );
+    field("confRev", a.conf_rev.to_string(), b.conf_rev.to_string());
+    field("ndsCom", a.nds_com.to_string(), b.nds_com.to_string());
+    field(
+        "numDatSetEntries",
+        a.num_dat_set_entries.to_string(),
+        b.num_dat_set_entries.to_string(),
+    );
+
+    if a.all_data.len() != b.all_data.len() {
+        diffs.push(GooseDiff::DataLength {
+            left: a.all_data.len(),
+            right: b.all_data.len(),
+        });
+    }
+    for (index, (left, right)) in a.all_data.iter().zip(&b.all_data).enumerate() {
+        diffs.extend(
+            diff_data(left, right)
+                .into_iter()
+                .map(|diff| GooseDiff::Data { index, diff }),
+        );
+    }
+
+    diffs
+}
+
+fn diff_data(left: &IECData, right: &IECData) -> Vec<DataDiff> {
+    match (left, right) {
+        (IECData::Structure(l), IECData::Structure(r)) | (IECData::Array(l), IECData::Array(r)) => {
+            let mut diffs = vec![];
+            if l.len() != r.len() {
+                diffs.push(DataDiff::Length {
+                    left: l.len(),
+                    right: r.len(),
+                });
+            }
+            for (index, (l, r)) in l.iter().zip(r).enumerate() {
+                diffs.extend(diff_data(l, r).into_iter().map(|diff| DataDiff::Element {
+                    index,
+                    diff: Box::new(diff),
+                }));
+            }
+            diffs
+        }
+        _ if left == right => vec![],
+        _ => vec![DataDiff::Value {
+            left: left.clone(),
+            right: right.clone(),
+        }],
+    }
+}
+
+/// Bytes a frame occupies on the wire besides its content: preamble and start frame
+/// delimiter (8), frame check sequence (4) and inter-frame gap (12)
+const ETHERNET_OVERHEAD: usize = 24;
+
+fn wire_bits(frame_size: usize) -> u64 {
+    (frame_size.max(MIN_FRAME_LENGTH) + ETHERNET_OVERHEAD) as u64 * 8
+}
+
+/// Computes the bandwidth a Sampled Values stream occupies on the link.
+///
+/// # Parameters
+/// - `frame_size`: The size of an encoded frame, e.g. from `smv_size`.
+/// - `smp_rate`: The number of samples per second.
+/// - `no_asdu`: The number of ASDUs (samples) per frame.
+///
+/// # Returns
+/// The bandwidth in bits per second, including the Ethernet framing overhead.
+pub fn sv_bandwidth_bps(frame_size: usize, smp_rate: u16, no_asdu: u16) -> u64 {
+    wire_bits(frame_size) * smp_rate as u64 / no_asdu.max(1) as u64
+}
+
+/// Computes the bandwidth a GOOSE publisher occupies on the link in the stable state,
+/// where frames are repeated with the heartbeat interval of `schedule`.
+///
+/// # Parameters
+/// - `frame_size`: The size of an encoded frame, e.g. the length of `encode_goose` output.
+/// - `schedule`: The retransmission timing of the publisher.
+///
+/// # Returns
+/// The bandwidth in bits per second, including the Ethernet framing overhead.
+pub fn goose_bandwidth_bps(frame_size: usize, schedule: &RetransmissionSchedule) -> u64 {
+    wire_bits(frame_size) * 1000 / schedule.max_interval_ms.max(1) as u64
+}
+
+/// Column names of the rows written by `write_csv`
+const CSV_HEADER: &str = "asdu,sv_id,smp_cnt,channel,value,quality";
+
+/// Writes the samples of a Sampled Values PDU as CSV, one row per sample.
+///
+/// The columns are the ASDU index within the PDU, svID, smpCnt, the sample index within
+/// the ASDU, the value and the quality as rendered by `Quality`'s `Display`
+/// (e.g. `good|test`). svIDs containing commas or quotes are quoted.
+///
+/// # Parameters
+/// - `writer`: The destination of the CSV text, including the header row.
+/// - `pdu`: The decoded PDU, e.g. from `decode_smv`.
+pub fn write_csv<W: Write>(mut writer: W, pdu: &SavPdu) -> io::Result<()> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+    for (asdu_index, asdu) in pdu.sav_asdu.iter().enumerate() {
+        let sv_id = csv_field(&asdu.msv_id);
+        for (channel, sample) in asdu.all_data.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                asdu_index, sv_id, asdu.smp_cnt, channel, sample.value, sample.quality
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the samples of a Sampled Values PDU as CSV like `write_csv`.
+pub fn samples_to_csv(pdu: &SavPdu) -> String {
+    let mut csv = Vec::new();
+    // Writing into a Vec cannot fail
+    let _ = write_csv(&mut csv, pdu);
+    String::from_utf8(csv).unwrap_or_default()
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_goose::encode_goose;
+    use crate::encode_smv::encode_smv;
+    use crate::types::{Quality, Sample, SavAsdu, TimeQuality, Timestamp};
+
+    fn header(ether_type: [u8; 2], appid: [u8; 2]) -> EthernetHeader {
+        EthernetHeader {
+            dst_addr: [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            src_addr: [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            tpid: None,
+            tci: None,
+            ether_type,
+            appid,
+            length: [0x00, 0x00],
+        }
+    }
+
+    fn goose_frame(go_id: &str, simulation: bool) -> DecodedFrame {
+        DecodedFrame::Goose(
+            header([0x88, 0xb8], [0x00, 0x01]),
+            IECGoosePdu {
+                go_cb_ref: "IED1/LLN0$GO$gcb1".to_string(),
+                time_allowed_to_live: 2000,
+                dat_set: "IED1/LLN0$DataSet1".to_string(),
+                go_id: go_id.to_string(),
+                t: Timestamp {
+                    seconds: 0,
+                    fraction: 0,
+                    quality: TimeQuality::default(),
+                },
+                st_num: 1,
+                sq_num: 0,
+                simulation,
+                conf_rev: 1,
+                nds_com: false,
+                num_dat_set_entries: 0,
+                all_data: vec![],
+            },
+        )
+    }
+
+    fn smv_frame(sv_id: &str, smp_cnt: u16, sim: bool) -> DecodedFrame {
+        DecodedFrame::Smv(
+            header([0x88, 0xba], [0x40, 0x00]),
+            SavPdu {
+                sim,
+                no_asdu: 1,
+                security: None,
+                sav_asdu: vec![SavAsdu {
+                    msv_id: sv_id.to_string(),
+                    smp_cnt,
+                    smp_rate: Some(4000),
+                    ..Default::default()
+                }],
+            },
+        )
+    }
+
+    #[test]
+    fn test_decode_frame() {
+        let DecodedFrame::Smv(smv_header, smv_pdu) = smv_frame("MU01", 7, false) else {
+            unreachable!()
+        };
+        let frame = encode_smv(&smv_header, &smv_pdu).unwrap();
+        match decode_frame(&frame).unwrap() {
+            DecodedFrame::Smv(header, pdu) => {
+                assert_eq!(header.appid, [0x40, 0x00]);
+                assert_eq!(pdu, smv_pdu);
+            }
+            other => panic!("Expected an SMV frame, got {:?}", other),
+        }
+
+        // IPv4 frame
+        let mut frame = vec![0xff; 12];
+        frame.extend([0x08, 0x00, 0x45, 0x00, 0x00, 0x14]);
+        frame.resize(60, 0);
+        assert_eq!(decode_frame(&frame).unwrap(), DecodedFrame::Other);
+
+        let DecodedFrame::Goose(goose_header, goose_pdu) = goose_frame("GOOSE1", false) else {
+            unreachable!()
+        };
+        let frame = encode_goose(&goose_header, &goose_pdu).unwrap();
+        match decode_frame(&frame).unwrap() {
+            DecodedFrame::Goose(header, pdu) => {
+                assert_eq!(header.ether_type, [0x88, 0xb8]);
+                assert_eq!(pdu, goose_pdu);
+            }
+            other => panic!("Expected a GOOSE frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_summarize_mixed_capture() {
+        let frames = vec![
+            smv_frame("MU01", 3998, false),
+            goose_frame("GOOSE1", false),
+            smv_frame("MU01", 3999, false),
+            smv_frame("MU02", 10, true),
+            smv_frame("MU01", 0, false), // roll-over, not a gap
+            DecodedFrame::Other,
+            smv_frame("MU01", 2, false), // sample 1 missing
+            smv_frame("MU02", 11, true),
+            goose_frame("GOOSE2", true),
+        ];
+
+        let summary = summarize_capture(&frames);
+
+        assert_eq!(summary.goose_frames, 2);
+        assert_eq!(summary.smv_frames, 6);
+        assert_eq!(summary.other_frames, 1);
+        assert_eq!(summary.appids, BTreeSet::from([0x0001, 0x4000]));
+        assert_eq!(
+            summary.sv_ids,
+            BTreeSet::from(["MU01".to_string(), "MU02".to_string()])
+        );
+        assert_eq!(
+            summary.go_ids,
+            BTreeSet::from(["GOOSE1".to_string(), "GOOSE2".to_string()])
+        );
+        assert_eq!(
+            summary.smp_cnt_gaps,
+            vec![SmpCntGap {
+                sv_id: "MU01".to_string(),
+                expected: 1,
+                received: 2,
+            }]
+        );
+        assert_eq!(summary.simulated_frames, 3);
+    }
+
+    #[test]
+    fn test_summarize_restart_at_0() {
+        let with_rate = |smp_cnt, smp_rate| {
+            let mut frame = smv_frame("MU01", smp_cnt, false);
+            if let DecodedFrame::Smv(_, pdu) = &mut frame {
+                pdu.sav_asdu[0].smp_rate = smp_rate;
+            }
+            frame
+        };
+        let gap = |expected, received| SmpCntGap {
+            sv_id: "MU01".to_string(),
+            expected,
+            received,
+        };
+
+        // Samples 6 to 3999 missing
+        let frames = [with_rate(5, Some(4000)), with_rate(0, Some(4000))];
+        assert_eq!(summarize_capture(&frames).smp_cnt_gaps, vec![gap(6, 0)]);
+
+        // 80 samples per period: the counter may continue after 79 or restart later
+        let frames = [
+            with_rate(79, Some(80)),
+            with_rate(80, Some(80)),
+            with_rate(3999, Some(80)),
+            with_rate(0, Some(80)),
+        ];
+        assert_eq!(summarize_capture(&frames).smp_cnt_gaps, vec![gap(81, 3999)]);
+
+        // Without smpRate only the roll-over after 65535 is regular
+        let frames = [
+            with_rate(u16::MAX, None),
+            with_rate(0, None),
+            with_rate(5, None),
+            with_rate(0, None),
+        ];
+        assert_eq!(
+            summarize_capture(&frames).smp_cnt_gaps,
+            vec![gap(1, 5), gap(6, 0)]
+        );
+    }
+
+    #[test]
+    fn test_summarize_empty_capture() {
+        assert_eq!(summarize_capture(&[]), CaptureSummary::default());
+    }
+
+    fn goose_pdu(sq_num: u32, all_data: Vec<IECData>) -> IECGoosePdu {
+        let DecodedFrame::Goose(_, mut pdu) = goose_frame("GOOSE1", false) else {
+            unreachable!()
+        };
+        pdu.sq_num = sq_num;
+        pdu.num_dat_set_entries = all_data.len() as u32;
+        pdu.all_data = all_data;
+        pdu
+    }
+
+    #[test]
+    fn test_diff_goose_pdu() {
+        let a = goose_pdu(
+            1,
+            vec![
+                IECData::Boolean(true),
+                IECData::Structure(vec![IECData::Int(10), IECData::Float(1.5)]),
+            ],
+        );
+        let b = goose_pdu(
+            2,
+            vec![
+                IECData::Boolean(true),
+                IECData::Structure(vec![IECData::Int(10), IECData::Float(2.5)]),
+            ],
+        );
+
+        assert_eq!(
+            diff_goose_pdu(&a, &b),
+            vec![
+                GooseDiff::Field {
+                    name: "sqNum".to_string(),
+                    left: "1".to_string(),
+                    right: "2".to_string(),
+                },
+                GooseDiff::Data {
+                    index: 1,
+                    diff: DataDiff::Element {
+                        index: 1,
+                        diff: Box::new(DataDiff::Value {
+                            left: IECData::Float(1.5),
+                            right: IECData::Float(2.5),
+                        }),
+                    },
+                },
+            ]
+        );
+        assert!(diff_goose_pdu(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn test_diff_goose_pdu_data_set_size() {
+        let a = goose_pdu(0, vec![IECData::Boolean(true)]);
+        let b = goose_pdu(0, vec![IECData::Int(1), IECData::Boolean(false)]);
+
+        assert_eq!(
+            diff_goose_pdu(&a, &b),
+            vec![
+                GooseDiff::Field {
+                    name: "numDatSetEntries".to_string(),
+                    left: "1".to_string(),
+                    right: "2".to_string(),
+                },
+                GooseDiff::DataLength { left: 1, right: 2 },
+                GooseDiff::Data {
+                    index: 0,
+                    diff: DataDiff::Value {
+                        left: IECData::Boolean(true),
+                        right: IECData::Int(1),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sv_bandwidth() {
+        // 4 kHz, one ASDU per frame: 4000 frames of (126 + 24) bytes
+        assert_eq!(sv_bandwidth_bps(126, 4000, 1), 4_800_000);
+        // Eight ASDUs per frame reduce the frame rate to 500 Hz
+        assert_eq!(sv_bandwidth_bps(600, 4000, 8), 2_496_000);
+        // Short frames are padded to the Ethernet minimum
+        assert_eq!(sv_bandwidth_bps(40, 4000, 1), sv_bandwidth_bps(60, 4000, 1));
+    }
+
+    #[test]
+    fn test_goose_bandwidth() {
+        let schedule = RetransmissionSchedule {
+            min_interval_ms: 2,
+            max_interval_ms: 100,
+        };
+        // 10 frames per second of (150 + 24) bytes
+        assert_eq!(goose_bandwidth_bps(150, &schedule), 13_920);
+    }
+
+    #[test]
+    fn test_samples_to_csv() {
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 2,
+            security: None,
+            sav_asdu: vec![
+                SavAsdu {
+                    msv_id: "MU01".to_string(),
+                    smp_cnt: 7,
+                    all_data: vec![
+                        Sample::new(1000, 0),
+                        Sample::from_parts(
+                            -1000,
+                            Quality::builder().overflow(true).test(true).build(),
+                        ),
+                    ],
+                    ..Default::default()
+                },
+                SavAsdu {
+                    msv_id: "MU,02".to_string(),
+                    smp_cnt: 8,
+                    all_data: vec![Sample::from_parts(1.5f32, Quality::invalid())],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let csv = samples_to_csv(&pdu);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            rows,
+            vec![
+                "asdu,sv_id,smp_cnt,channel,value,quality",
+                "0,MU01,7,0,1000,good",
+                "0,MU01,7,1,-1000,good|overflow|test",
+                "1,\"MU,02\",8,0,1.5,invalid",
+            ]
+        );
+    }
+}
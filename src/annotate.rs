@@ -0,0 +1,340 @@
+use std::fmt::Write;
+
+use crate::ber::decode_tag_length;
+use crate::types::{EthernetHeader, Quality, Timestamp};
+
+/// Where in the frame a TLV appears, which decides the meaning of its tag
+#[derive(Debug, Clone, Copy)]
+enum Context {
+    GooseFrame,
+    GoosePdu,
+    GooseData,
+    SmvFrame,
+    SmvPdu,
+    SmvSeqAsdu,
+    SmvAsdu,
+    SmvSeqData,
+}
+
+/// How the content of a TLV is rendered
+#[derive(Debug, Clone, Copy)]
+enum Content {
+    Nested(Context),
+    Text,
+    Unsigned,
+    Signed,
+    Boolean,
+    Float,
+    Time,
+    Quality,
+    Hex,
+}
+
+/// Renders a GOOSE frame as a tree of its fields with byte offsets and decoded values.
+///
+/// Malformed parts are marked with `<parse error at N>`, N being the byte offset at
+/// which the structure could not be followed; the rest of the frame is still shown
+/// where possible.
+///
+/// # Parameters
+/// - `buffer`: The complete Ethernet frame.
+///
+/// # Returns
+/// One line per field, nested fields indented below their parent.
+pub fn annotate_goose(buffer: &[u8]) -> String {
+    annotate(buffer, Context::GooseFrame)
+}
+
+/// Renders a Sampled Values frame like `annotate_goose`.
+pub fn annotate_smv(buffer: &[u8]) -> String {
+    annotate(buffer, Context::SmvFrame)
+}
+
+fn annotate(buffer: &[u8], context: Context) -> String {
+    let mut out = String::new();
+    let (header, pos) = match EthernetHeader::from_wire(buffer) {
+        Ok(decoded) => decoded,
+        Err(_) => {
+            let _ = writeln!(out, "<parse error at {}>", buffer.len());
+            return out;
+        }
+    };
+
+    let _ = writeln!(out, "{:>4}  destination: {}", 0, header.dst_mac());
+    let _ = writeln!(out, "{:>4}  source: {}", 6, header.src_mac());
+    let mut offset = 12;
+    if let Some(tci) = header.tci {
+        let _ = writeln!(
+            out,
+            "{:>4}  vlan: priority {}, id {}",
+            offset,
+            tci[0] >> 5,
+            header.vlan_id().unwrap_or_default()
+        );
+        offset += 4;
+    }
+    let length = u16::from_be_bytes(header.length) as usize;
+    for (name, bytes) in [
+        ("etherType", header.ether_type),
+        ("appID", header.appid),
+        ("length", header.length),
+        ("reserved1", [buffer[pos - 4], buffer[pos - 3]]),
+        ("reserved2", [buffer[pos - 2], buffer[pos - 1]]),
+    ] {
+        if name == "length" {
+            let _ = writeln!(out, "{:>4}  {}: {}", offset, name, length);
+        } else {
+            let _ = writeln!(
+                out,
+                "{:>4}  {}: 0x{:04x}",
+                offset,
+                name,
+                u16::from_be_bytes(bytes)
+            );
+        }
+        offset += 2;
+    }
+
+    // The length counts from the APPID to the end of the PDU, padding follows after
+    let end = (pos - 8 + length).min(buffer.len());
+    walk(buffer, pos, end, 0, context, &mut out);
+    out
+}
+
+/// Writes the TLVs between `start` and `end` and recurses into nested ones
+fn walk(buffer: &[u8], start: usize, end: usize, depth: usize, context: Context, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let mut pos = start;
+    while pos < end {
+        let mut tag = 0u8;
+        let mut length = 0usize;
+        let content_pos = match decode_tag_length(&mut tag, &mut length, &buffer[..end], pos) {
+            Ok(content_pos) if content_pos + length <= end => content_pos,
+            _ => {
+                let _ = writeln!(out, "{:>4}  {}<parse error at {}>", pos, indent, pos);
+                return;
+            }
+        };
+
+        let (name, content) = describe(context, tag);
+        let _ = write!(
+            out,
+            "{:>4}  {}{} (0x{:02x}, len {})",
+            pos, indent, name, tag, length
+        );
+        let value = &buffer[content_pos..content_pos + length];
+        match content {
+            Content::Nested(child) => {
+                let _ = writeln!(out);
+                walk(
+                    buffer,
+                    content_pos,
+                    content_pos + length,
+                    depth + 1,
+                    child,
+                    out,
+                );
+            }
+            content => {
+                let _ = writeln!(out, ": {}", render(content, value));
+            }
+        }
+        pos = content_pos + length;
+    }
+}
+
+/// Name and content type of `tag` in `context`
+fn describe(context: Context, tag: u8) -> (&'static str, Content) {
+    let described = match (context, tag) {
+        (Context::GooseFrame, 0x61) => ("goosePdu", Content::Nested(Context::GoosePdu)),
+        (Context::GoosePdu, 0x80) => ("gocbRef", Content::Text),
+        (Context::GoosePdu, 0x81) => ("timeAllowedtoLive", Content::Unsigned),
+        (Context::GoosePdu, 0x82) => ("datSet", Content::Text),
+        (Context::GoosePdu, 0x83) => ("goID", Content::Text),
+        (Context::GoosePdu, 0x84) => ("t", Content::Time),
+        (Context::GoosePdu, 0x85) => ("stNum", Content::Unsigned),
+        (Context::GoosePdu, 0x86) => ("sqNum", Content::Unsigned),
+        (Context::GoosePdu, 0x87) => ("simulation", Content::Boolean),
+        (Context::GoosePdu, 0x88) => ("confRev", Content::Unsigned),
+        (Context::GoosePdu, 0x89) => ("ndsCom", Content::Boolean),
+        (Context::GoosePdu, 0x8a) => ("numDatSetEntries", Content::Unsigned),
+        (Context::GoosePdu, 0xab) => ("allData", Content::Nested(Context::GooseData)),
+        (Context::GooseData, 0xa1) => ("array", Content::Nested(Context::GooseData)),
+        (Context::GooseData, 0xa2) => ("structure", Content::Nested(Context::GooseData)),
+        (Context::GooseData, 0x83) => ("boolean", Content::Boolean),
+        (Context::GooseData, 0x84) => ("bit-string", Content::Hex),
+        (Context::GooseData, 0x85) => ("integer", Content::Signed),
+        (Context::GooseData, 0x86) => ("unsigned", Content::Unsigned),
+        (Context::GooseData, 0x87) => ("floating-point", Content::Float),
+        (Context::GooseData, 0x89) => ("octet-string", Content::Hex),
+        (Context::GooseData, 0x8a) => ("visible-string", Content::Text),
+        (Context::GooseData, 0x90) => ("mms-string", Content::Text),
+        (Context::GooseData, 0x91) => ("utc-time", Content::Time),
+        (Context::SmvFrame, 0x60) => ("savPdu", Content::Nested(Context::SmvPdu)),
+        (Context::SmvPdu, 0x80) => ("noASDU", Content::Unsigned),
+        (Context::SmvPdu, 0x81) => ("security", Content::Hex),
+        (Context::SmvPdu, 0xa2) => ("seqASDU", Content::Nested(Context::SmvSeqAsdu)),
+        (Context::SmvSeqAsdu, 0x30) => ("ASDU", Content::Nested(Context::SmvAsdu)),
+        (Context::SmvAsdu, 0x80) => ("svID", Content::Text),
+        (Context::SmvAsdu, 0x81) => ("datSet", Content::Text),
+        (Context::SmvAsdu, 0x82) => ("smpCnt", Content::Unsigned),
+        (Context::SmvAsdu, 0x83) => ("confRev", Content::Unsigned),
+        (Context::SmvAsdu, 0x84) => ("refrTm", Content::Time),
+        (Context::SmvAsdu, 0x85) => ("smpSynch", Content::Unsigned),
+        (Context::SmvAsdu, 0x86) => ("smpRate", Content::Unsigned),
+        (Context::SmvAsdu, 0x87) => ("seqData", Content::Nested(Context::SmvSeqData)),
+        (Context::SmvAsdu, 0x88) => ("smpMod", Content::Unsigned),
+        (Context::SmvAsdu, 0x89) => ("gmIdentity", Content::Hex),
+        (Context::SmvSeqData, 0x83 | 0x85) => ("value", Content::Signed),
+        (Context::SmvSeqData, 0x87) => ("value", Content::Float),
+        (Context::SmvSeqData, 0x84) => ("quality", Content::Quality),
+        _ => ("unknown", Content::Hex),
+    };
+    // Keep following constructed TLVs with unknown tags
+    match described {
+        ("unknown", _) if tag & 0x20 != 0 => ("unknown", Content::Nested(context)),
+        described => described,
+    }
+}
+
+/// Renders the content of a primitive TLV, falling back to hex if it does not fit
+fn render(content: Content, value: &[u8]) -> String {
+    let hex = || {
+        value
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    match content {
+        Content::Text => format!("{:?}", String::from_utf8_lossy(value)),
+        Content::Unsigned if !value.is_empty() && value.len() <= 8 => value
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64)
+            .to_string(),
+        Content::Signed if !value.is_empty() && value.len() <= 8 => {
+            let fill = if value[0] & 0x80 != 0 { -1i64 } else { 0 };
+            value
+                .iter()
+                .fold(fill, |acc, b| (acc << 8) | *b as i64)
+                .to_string()
+        }
+        Content::Boolean if value.len() == 1 => (value[0] != 0).to_string(),
+        Content::Float => match value.len() {
+            4 | 5 => f32::from_be_bytes(value[value.len() - 4..].try_into().unwrap()).to_string(),
+            8 | 9 => f64::from_be_bytes(value[value.len() - 8..].try_into().unwrap()).to_string(),
+            _ => hex(),
+        },
+        Content::Time if value.len() == 8 => {
+            Timestamp::from_bytes(value.try_into().unwrap()).to_utc_string()
+        }
+        Content::Quality if value.len() == 3 => {
+            Quality::from_u16(u16::from_be_bytes([value[1], value[2]])).to_string()
+        }
+        _ => hex(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcap::read_pcap;
+
+    fn fixture_frames() -> Vec<Vec<u8>> {
+        read_pcap(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/goose_smv.pcap"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_annotate_goose() {
+        let expected = r#"   0  destination: 01:0c:cd:01:00:01
+   6  source: 00:1a:b6:03:2f:1c
+  12  vlan: priority 0, id 1
+  16  etherType: 0x88b8
+  18  appID: 0x1001
+  20  length: 140
+  22  reserved1: 0x0000
+  24  reserved2: 0x0000
+  26  goosePdu (0x61, len 129)
+  29    gocbRef (0x80, len 17): "IED1/LLN0$GO$gcb1"
+  48    timeAllowedtoLive (0x81, len 2): 2000
+  52    datSet (0x82, len 18): "IED1/LLN0$DATASET1"
+  72    goID (0x83, len 6): "GOOSE1"
+  80    t (0x84, len 8): 1987-01-30T19:59:14.039794Z
+  90    stNum (0x85, len 1): 1
+  93    sqNum (0x86, len 1): 42
+  96    simulation (0x87, len 1): false
+  99    confRev (0x88, len 2): 128
+ 103    ndsCom (0x89, len 1): false
+ 106    numDatSetEntries (0x8a, len 1): 11
+ 109    allData (0xab, len 47)
+ 111      unsigned (0x86, len 1): 1
+ 114      unsigned (0x86, len 2): 128
+ 118      unsigned (0x86, len 2): 255
+ 122      unsigned (0x86, len 1): 127
+ 125      unsigned (0x86, len 1): 1
+ 128      unsigned (0x86, len 2): 128
+ 132      unsigned (0x86, len 2): 255
+ 136      boolean (0x83, len 1): true
+ 139      integer (0x85, len 4): 2147483647
+ 145      integer (0x85, len 5): 2147483648
+ 152      visible-string (0x8a, len 4): "test"
+"#;
+        assert_eq!(annotate_goose(&fixture_frames()[0]), expected);
+    }
+
+    #[test]
+    fn test_annotate_smv() {
+        let expected = r#"   0  destination: 01:0c:cd:04:00:01
+   6  source: 00:11:22:33:44:55
+  12  etherType: 0x88ba
+  14  appID: 0x4000
+  16  length: 52
+  18  reserved1: 0x0000
+  20  reserved2: 0x0000
+  22  savPdu (0x60, len 42)
+  24    noASDU (0x80, len 1): 1
+  27    seqASDU (0xa2, len 37)
+  29      ASDU (0x30, len 35)
+  31        svID (0x80, len 4): "MU01"
+  37        smpCnt (0x82, len 1): 7
+  40        confRev (0x83, len 1): 1
+  43        smpSynch (0x85, len 1): 2
+  46        seqData (0x87, len 18)
+  48          value (0x83, len 2): 1000
+  52          quality (0x84, len 3): good
+  57          value (0x83, len 2): -1000
+  61          quality (0x84, len 3): good
+"#;
+        assert_eq!(annotate_smv(&fixture_frames()[1]), expected);
+    }
+
+    #[test]
+    fn test_annotate_malformed() {
+        let frames = fixture_frames();
+
+        // Too short for the Ethernet header
+        assert_eq!(annotate_goose(&frames[0][..10]), "<parse error at 10>\n");
+
+        // Truncated inside the PDU
+        let annotated = annotate_goose(&frames[0][..60]);
+        assert!(
+            annotated.ends_with("  26  <parse error at 26>\n"),
+            "{}",
+            annotated
+        );
+
+        // Broken length of the svID inside the ASDU
+        let mut frame = frames[1].clone();
+        frame[32] = 0x7f;
+        let annotated = annotate_smv(&frame);
+        assert!(
+            annotated.ends_with("  29      ASDU (0x30, len 35)\n  31        <parse error at 31>\n"),
+            "{}",
+            annotated
+        );
+    }
+}
@@ -0,0 +1,802 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::error::{DecodeError, EncodeError};
+
+/// Calculates the encoded length of an unsigned integer value
+/// Takes into account the extra 0x00 byte needed when MSB is set
+pub(crate) fn unsigned_integer_length(value: &[u8]) -> usize {
+    if value.is_empty() {
+        return 0;
+    }
+    // For unsigned integers, strip leading zeros (but not 0xFF like signed integers)
+    let mut start = 0;
+    while start < value.len() - 1 && value[start] == 0x00 {
+        start += 1;
+    }
+    let minimal = &value[start..];
+
+    // If MSB is set, we need to prepend a zero byte
+    if !minimal.is_empty() && (minimal[0] & 0x80) != 0 {
+        minimal.len() + 1
+    } else {
+        minimal.len()
+    }
+}
+
+/// Returns the number of bytes required to encode the length field in ASN.1 BER format.
+///
+/// This function determines how many bytes are needed to represent the given length value
+/// according to BER rules:
+/// - 1 byte for values < 128 (short form)
+/// - 2 bytes for values < 256 (0x81 + 1 byte)
+/// - 3 bytes for values < 65536 (0x82 + 2 bytes)
+/// - 4 bytes for values < 2^24 (0x83 + 3 bytes)
+/// - 5 bytes for larger values (0x84 + 4 bytes)
+pub(crate) fn size_length(value: usize) -> usize {
+    if value < 128 {
+        1
+    } else if value < 256 {
+        2
+    } else if value < 65536 {
+        3
+    } else if value < 1 << 24 {
+        4
+    } else {
+        5
+    }
+}
+
+/// Encodes an ASN.1 BER element (tag, length, value) into the buffer at the given position.
+///
+/// # Parameters
+/// - `tag`: The ASN.1 tag to write.
+/// - `value`: The value bytes to encode.
+/// - `buffer`: The output buffer.
+/// - `buffer_index`: The position in the buffer to start writing.
+///
+/// # Returns
+/// Result with the new position in the buffer after writing, or EncodeError.
+pub fn encode_ber(
+    tag: u8,
+    value: &[u8],
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    // Calculate required space: tag + length + value
+    let length_field_size = size_length(value.len());
+    let total_size = 1 + length_field_size + value.len();
+
+    if buffer.len() < buffer_index + total_size {
+        return Err(EncodeError::new(
+            "Buffer does not have enough capacity to encode the BER element.",
+            buffer_index,
+        ));
+    }
+
+    // Write tag and length
+    let mut pos = encode_tag_length(tag, value.len(), buffer, buffer_index)?;
+    // Write value
+    buffer[pos..pos + value.len()].copy_from_slice(value);
+    pos += value.len();
+
+    Ok(pos)
+}
+
+/// Checks that the content octets of a BER INTEGER are not empty and have no redundant
+/// leading 0x00 or 0xFF octet, as X.690 8.3.2 requires.
+pub(crate) fn is_minimal_integer(content: &[u8]) -> bool {
+    !content.is_empty() && minimal_twos_complement_bytes(content).len() == content.len()
+}
+
+/// Returns the minimal two's complement representation of a signed integer as a byte slice.
+/// This is used for ASN.1 BER INTEGER encoding.
+fn minimal_twos_complement_bytes(value: &[u8]) -> &[u8] {
+    if value.is_empty() {
+        return value;
+    }
+    let mut significant_start = 0;
+    while significant_start < value.len() - 1 {
+        let curr = value[significant_start];
+        let next = value[significant_start + 1];
+        if (curr == 0x00 && (next & 0x80) == 0) || (curr == 0xFF && (next & 0x80) == 0x80) {
+            significant_start += 1;
+        } else {
+            break;
+        }
+    }
+    &value[significant_start..]
+}
+
+/// Encodes an unsigned integer in ASN.1 BER format with a leading zero byte to ensure positive interpretation.
+///
+/// # Parameters
+/// - `tag`: A `u8` representing the ASN.1 tag for the unsigned integer type.
+/// - `value`: A slice of `u8` containing the unsigned integer in big-endian format.
+/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
+/// - `buffer_index`: The starting position in the buffer to write the encoded data.
+///
+/// # Returns
+/// Result with the new position in the buffer after writing the encoded unsigned integer, or EncodeError.
+pub(crate) fn encode_unsigned_integer(
+    tag: u8,
+    value: &[u8],
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    if value.is_empty() {
+        return encode_ber(tag, value, buffer, buffer_index);
+    }
+    // For unsigned integers, strip leading zeros but NOT leading 0xFF bytes
+    // (unlike signed integers where 0xFF is sign extension)
+    let mut start = 0;
+    while start < value.len() - 1 && value[start] == 0x00 {
+        start += 1;
+    }
+    let minimal = &value[start..];
+
+    // If MSB is set, prepend a zero byte to ensure positive interpretation
+    if !minimal.is_empty() && (minimal[0] & 0x80) != 0 {
+        let mut prepend = [0u8; 1 + 8];
+        prepend[0] = 0x00;
+        prepend[1..1 + minimal.len()].copy_from_slice(minimal);
+        encode_ber(tag, &prepend[..1 + minimal.len()], buffer, buffer_index)
+    } else {
+        encode_ber(tag, minimal, buffer, buffer_index)
+    }
+}
+
+/// Encodes an integer in minimal two's complement form according to ASN.1 BER.
+///
+/// # Parameters
+/// - `tag`: The ASN.1 tag for the integer type.
+/// - `value`: The integer as a big-endian byte slice (with possible leading sign extension bytes).
+/// - `buffer`: The output buffer.
+/// - `buffer_index`: Where to start writing in the buffer.
+///
+/// # Returns
+/// The new position in the buffer after writing the encoded integer.
+pub(crate) fn encode_integer(
+    tag: u8,
+    value: &[u8],
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    encode_ber(
+        tag,
+        minimal_twos_complement_bytes(value),
+        buffer,
+        buffer_index,
+    )
+}
+
+/// Encodes an ASN.1 octet string using BER rules.
+///
+/// # Parameters
+/// - `tag`: The ASN.1 tag to write.
+/// - `value`: A slice of `u8` containing the octet string to encode.
+/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
+/// - `buffer_index`: The starting position in the buffer to write the encoded data.
+///
+/// # Returns
+/// Result with the new position in the buffer after writing the encoded octet string, or EncodeError.
+pub(crate) fn encode_octet_string(
+    tag: u8,
+    value: &[u8],
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    encode_ber(tag, value, buffer, buffer_index)
+}
+
+/// # Parameters
+/// - `tag`: A `u8` representing the tag to be written to the buffer.
+/// - `value`: A `String` reference containing the string to encode.
+/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
+/// - `buffer_index`: The starting position in the buffer to write the encoded data.
+///
+/// # Returns
+/// Result with the new position in the buffer after writing the encoded string, or EncodeError.
+pub(crate) fn encode_string(
+    tag: u8,
+    value: &str,
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    let bytes = value.as_bytes();
+    encode_ber(tag, bytes, buffer, buffer_index)
+}
+
+/// Encodes an ASN.1 tag and its length field using BER rules.
+///
+/// This function writes the tag and the length field into the provided buffer at `buffer_index`.
+/// The length is encoded in short or long form depending on its value:
+/// - For values < 128, the length is encoded in a single byte.
+/// - For values < 256, the length is encoded as 0x81 followed by one byte.
+/// - For values < 65536, the length is encoded as 0x82 followed by two bytes (big-endian).
+/// - For larger values, the length is encoded as 0x80 plus the number of length bytes,
+///   followed by the minimal number of bytes (big-endian), at most four.
+///
+/// # Parameters
+/// - `tag`: The ASN.1 tag to write.
+/// - `value`: The length value to encode.
+/// - `buffer`: The output buffer.
+/// - `buffer_index`: The position in the buffer to start writing.
+///
+/// # Returns
+/// Result with the new position in the buffer after writing the tag and length, or EncodeError.
+pub fn encode_tag_length(
+    tag: u8,
+    value: usize,
+    buffer: &mut [u8],
+    buffer_index: usize,
+) -> Result<usize, EncodeError> {
+    if value as u64 >= 1 << 32 {
+        return Err(EncodeError::new(
+            "Value exceeds the maximum range for four-byte encoding (2^32 - 1).",
+            buffer_index,
+        ));
+    }
+
+    let required = 1 + size_length(value); // 1 for tag, rest for length field
+
+    if buffer.len() < buffer_index + required {
+        return Err(EncodeError::new(
+            "Buffer too small to write tag and length.",
+            buffer_index,
+        ));
+    }
+
+    let mut new_pos = buffer_index;
+    buffer[new_pos] = tag;
+    new_pos += 1;
+
+    // Now encode the length field as before
+    if value < 128 {
+        buffer[new_pos] = value as u8;
+        new_pos += 1;
+    } else {
+        let length_octets = size_length(value) - 1;
+        buffer[new_pos] = 0x80 | length_octets as u8;
+        new_pos += 1;
+        for octet in (0..length_octets).rev() {
+            buffer[new_pos] = (value >> (8 * octet)) as u8;
+            new_pos += 1;
+        }
+    }
+
+    Ok(new_pos)
+}
+
+/// Decodes an octet string (raw bytes) from the buffer at the specified position and length.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the decoded bytes will be stored.
+/// - `buffer`: The input byte slice containing the encoded data.
+/// - `buffer_index`: The starting position in the buffer to read the octet string from.
+/// - `length`: The number of bytes to read for the octet string.
+///
+/// # Returns
+/// The next position in the buffer after reading the octet string.
+///
+/// # Panics
+/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
+pub(crate) fn decode_octet_string(
+    val: &mut [u8],
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    if buffer_index + length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Attempt to read {} bytes exceeds buffer length {}",
+                length,
+                buffer.len()
+            ),
+            buffer_index,
+        ));
+    }
+    val[0..length].copy_from_slice(&buffer[buffer_index..buffer_index + length]);
+    Ok(buffer_index + length)
+}
+
+/// Decodes an ASN.1 BER encoded 8-bit unsigned integer from the buffer at the specified position and length.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the decoded u16 will be stored.
+/// - `buffer`: The input byte slice containing the encoded integer.
+/// - `buffer_index`: The starting position in the buffer to read the integer from.
+/// - `length`: The number of bytes used for the encoded integer in the buffer.
+///
+/// # Returns
+/// The next position in the buffer after reading the integer.
+///
+/// # Panics
+/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
+pub(crate) fn decode_unsigned_8(
+    val: &mut u8,
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    let mut value_bytes = [0u8; 1];
+    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
+    *val = u8::from_be_bytes(value_bytes);
+    Ok(buffer_index + length)
+}
+
+/// Decompresses an ASN.1 BER encoded integer from the buffer into the provided value slice,
+/// restoring it to its full width (e.g., i32, i64) with correct sign extension.
+///
+/// # Parameters
+/// - `value`: The output buffer (e.g., 4 or 8 bytes) to store the decompressed integer (big-endian).
+/// - `buffer`: The input byte slice containing the encoded integer.
+/// - `buffer_index`: The starting position in the buffer to read the integer from.
+/// - `length`: The number of bytes used for the encoded integer in the buffer.
+///
+/// # Returns
+/// A DecodeError if `length` is 0 (an INTEGER has at least one content octet), if the
+/// requested range exceeds the buffer or if `length` is greater than `value.len()`.
+pub(crate) fn decompress_integer(
+    value: &mut [u8],
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<(), DecodeError> {
+    if length == 0 {
+        return Err(DecodeError::new(
+            "Integer with zero length content",
+            buffer_index,
+        ));
+    }
+    if buffer_index + length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Attempt to read {} bytes exceeds buffer length {}",
+                length,
+                buffer.len()
+            ),
+            buffer_index,
+        ));
+    }
+
+    // Handle unsigned integers with leading 0x00 byte (for MSB set prevention)
+    // If the encoded value has a leading 0x00 and the next byte has MSB set,
+    // and the length is exactly one more than our target size, skip the leading 0x00
+    let (actual_start, actual_length) = if length == value.len() + 1
+        && length >= 2
+        && buffer[buffer_index] == 0x00
+        && (buffer[buffer_index + 1] & 0x80) != 0
+    {
+        // Skip the leading 0x00 byte for unsigned integers
+        (buffer_index + 1, length - 1)
+    } else if length > value.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Mismatch value length {} vs buffer length {}",
+                value.len(),
+                length
+            ),
+            buffer_index,
+        ));
+    } else {
+        (buffer_index, length)
+    };
+
+    // Determine fill byte for sign extension (0xFF for negative, 0x00 for positive)
+    let fill = if buffer[actual_start] & 0x80 == 0x80 {
+        0xFF
+    } else {
+        0x00
+    };
+
+    // Fill the leading bytes with the sign extension
+    let fill_length = value.len() - actual_length;
+    for item in value.iter_mut().take(fill_length) {
+        *item = fill;
+    }
+
+    // Copy the encoded integer bytes into the lower part of the output buffer
+    value[fill_length..].copy_from_slice(&buffer[actual_start..actual_start + actual_length]);
+    Ok(())
+}
+
+/// Decodes an ASN.1 BER encoded 32-bit unsigned integer from the buffer at the specified position and length.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the decoded u32 will be stored.
+/// - `buffer`: The input byte slice containing the encoded integer.
+/// - `buffer_index`: The starting position in the buffer to read the integer from.
+/// - `length`: The number of bytes used for the encoded integer in the buffer.
+///
+/// # Returns
+/// The next position in the buffer after reading the integer.
+///
+/// # Panics
+/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
+pub(crate) fn decode_unsigned_32(
+    val: &mut u32,
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    let mut value_bytes = [0u8; 4];
+    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
+    *val = u32::from_be_bytes(value_bytes);
+    Ok(buffer_index + length)
+}
+
+/// Decodes an ASN.1 BER encoded 16-bit unsigned integer from the buffer at the specified position and length.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the decoded u16 will be stored.
+/// - `buffer`: The input byte slice containing the encoded integer.
+/// - `buffer_index`: The starting position in the buffer to read the integer from.
+/// - `length`: The number of bytes used for the encoded integer in the buffer.
+///
+/// # Returns
+/// The next position in the buffer after reading the integer.
+///
+/// # Panics
+/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
+pub(crate) fn decode_unsigned_16(
+    val: &mut u16,
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    let mut value_bytes = [0u8; 2];
+    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
+    *val = u16::from_be_bytes(value_bytes);
+    Ok(buffer_index + length)
+}
+
+/// Decodes a UTF-8 string from the buffer at the specified position and length.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the decoded string will be stored.
+/// - `buffer`: The input byte slice containing the encoded data.
+/// - `buffer_index`: The starting position in the buffer to read the string from.
+/// - `length`: The number of bytes to read for the string.
+///
+/// # Returns
+/// The next position in the buffer after reading the string.
+///
+/// # Panics
+/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
+pub(crate) fn decode_string(
+    val: &mut String,
+    buffer: &[u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    if buffer_index + length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Attempt to read {} bytes exceeds buffer length {}",
+                length,
+                buffer.len()
+            ),
+            buffer_index,
+        ));
+    }
+    *val = String::from_utf8_lossy(&buffer[buffer_index..buffer_index + length]).to_string();
+    Ok(buffer_index + length)
+}
+
+/// Borrows a UTF-8 string from the buffer at the specified position and length,
+/// without copying it.
+///
+/// # Parameters
+/// - `val`: A mutable reference where the borrowed string will be stored.
+/// - `buffer`: The input byte slice containing the encoded data.
+/// - `buffer_index`: The starting position in the buffer to read the string from.
+/// - `length`: The number of bytes to read for the string.
+///
+/// # Returns
+/// The next position in the buffer after the string, or a DecodeError if the string
+/// exceeds the buffer or is not valid UTF-8.
+pub(crate) fn decode_str<'a>(
+    val: &mut &'a str,
+    buffer: &'a [u8],
+    buffer_index: usize,
+    length: usize,
+) -> Result<usize, DecodeError> {
+    let Some(bytes) = buffer.get(buffer_index..buffer_index + length) else {
+        return Err(DecodeError::new(
+            &format!(
+                "Attempt to read {} bytes exceeds buffer length {}",
+                length,
+                buffer.len()
+            ),
+            buffer_index,
+        ));
+    };
+    *val = core::str::from_utf8(bytes)
+        .map_err(|_| DecodeError::new("String is not valid UTF-8", buffer_index))?;
+    Ok(buffer_index + length)
+}
+
+/// Maximum number of subsequent identifier octets of a high tag number, enough for a
+/// 28 bit tag number
+const MAX_TAG_NUMBER_OCTETS: usize = 4;
+
+/// Maximum number of long form length octets accepted by the decoder
+const MAX_LENGTH_OCTETS: usize = 8;
+
+/// Decodes the identifier octets of an ASN.1 BER tag.
+///
+/// Tag numbers up to 30 are held in the low five bits of the first octet. For larger
+/// numbers these bits are all set and the number follows in base-128 octets, each but
+/// the last with the high bit set.
+///
+/// # Parameters
+/// - `buffer`: The input byte slice containing the encoded tag.
+/// - `buffer_index`: The position of the first identifier octet.
+///
+/// # Returns
+/// The first identifier octet (class, constructed bit and low tag bits), the tag number
+/// and the position after the identifier octets.
+pub fn decode_tag(buffer: &[u8], buffer_index: usize) -> Result<(u8, u32, usize), DecodeError> {
+    let Some(&identifier) = buffer.get(buffer_index) else {
+        return Err(DecodeError::new(
+            &format!("Out of bounds for buffer length {}", buffer.len()),
+            buffer_index,
+        ));
+    };
+    let mut pos = buffer_index + 1;
+
+    if identifier & 0x1F != 0x1F {
+        return Ok((identifier, (identifier & 0x1F) as u32, pos));
+    }
+
+    let mut number = 0u32;
+    for _ in 0..MAX_TAG_NUMBER_OCTETS {
+        let Some(&octet) = buffer.get(pos) else {
+            return Err(DecodeError::new(
+                "Decode tag: missing subsequent tag number octet",
+                buffer_index,
+            ));
+        };
+        pos += 1;
+        number = (number << 7) | (octet & 0x7F) as u32;
+        if octet & 0x80 == 0 {
+            return Ok((identifier, number, pos));
+        }
+    }
+
+    Err(DecodeError::new(
+        &format!(
+            "Decode tag: tag number exceeds {} octets",
+            MAX_TAG_NUMBER_OCTETS
+        ),
+        buffer_index,
+    ))
+}
+
+/// Decodes an ASN.1 BER tag and length field from the buffer at the specified position,
+/// writing the results into the provided mutable references.
+///
+/// This function supports definite-length encoding with up to 8 length bytes.
+/// High tag numbers are skipped, `tag` receives the first identifier octet; use
+/// `decode_tag` for the full tag number.
+///
+/// # Parameters
+/// - `tag`: Mutable reference to store the first identifier octet of the tag (`u8`).
+/// - `length`: Mutable reference to store the decoded length (`usize`).
+/// - `buffer`: The input byte slice containing the encoded tag and length.
+/// - `buffer_index`: The starting position in the buffer to read the tag and length from.
+///
+/// # Returns
+/// The next position in the buffer after reading the tag and length, or a DecodeError if
+/// the buffer ends within them or the length is not supported.
+pub fn decode_tag_length(
+    tag: &mut u8,
+    length: &mut usize,
+    buffer: &[u8],
+    buffer_index: usize,
+) -> Result<usize, DecodeError> {
+    let (identifier, _, mut pos) = decode_tag(buffer, buffer_index)?;
+    *tag = identifier;
+
+    if pos >= buffer.len() {
+        return Err(DecodeError::new(
+            "Decode tag length: missing length byte ",
+            buffer_index,
+        ));
+    }
+
+    let first_len_byte = buffer[pos];
+    pos += 1;
+
+    *length = if first_len_byte & 0x80 == 0 {
+        // Short form: single byte length (0..127)
+        first_len_byte as usize
+    } else {
+        // Long form: lower 7 bits indicate number of length bytes
+        let num_len_bytes = (first_len_byte & 0x7F) as usize;
+        if num_len_bytes == 0 || num_len_bytes > MAX_LENGTH_OCTETS {
+            return Err(DecodeError::new(
+                &format!(
+                    "Decode tag length: unsupported or invalid number of length bytes: {}",
+                    num_len_bytes
+                ),
+                buffer_index,
+            ));
+        }
+        if pos + num_len_bytes > buffer.len() {
+            return Err(DecodeError::new(
+                &format!(
+                    "Decode tag length: not enough bytes for {}-byte length at position {}",
+                    num_len_bytes, pos
+                ),
+                buffer_index,
+            ));
+        }
+        let mut len = 0u64;
+        for _ in 0..num_len_bytes {
+            len = (len << 8) | buffer[pos] as u64;
+            pos += 1;
+        }
+        usize::try_from(len).map_err(|_| {
+            DecodeError::new(
+                &format!(
+                    "Decode tag length: length {} exceeds the address space",
+                    len
+                ),
+                buffer_index,
+            )
+        })?
+    };
+
+    Ok(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_length_with_four_length_octets() {
+        for (length, expected) in [
+            (0x01000000, vec![0x04, 0x84, 0x01, 0x00, 0x00, 0x00]),
+            (0x12345678, vec![0x04, 0x84, 0x12, 0x34, 0x56, 0x78]),
+        ] {
+            assert_eq!(size_length(length), 5);
+
+            let mut buffer = [0u8; 6];
+            let end = encode_tag_length(0x04, length, &mut buffer, 0).unwrap();
+            assert_eq!(&buffer[..end], expected.as_slice());
+
+            let mut tag = 0u8;
+            let mut decoded = 0usize;
+            let pos = decode_tag_length(&mut tag, &mut decoded, &buffer, 0).unwrap();
+            assert_eq!((tag, decoded, pos), (0x04, length, end));
+        }
+
+        // Minimal encoding below 2^24
+        let mut buffer = [0u8; 6];
+        let end = encode_tag_length(0x04, 0x00FFFFFF, &mut buffer, 0).unwrap();
+        assert_eq!(&buffer[..end], &[0x04, 0x83, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_tag_length_octet_limits() {
+        let mut buffer = [0u8; 8];
+        assert!(encode_tag_length(0x04, 1 << 32, &mut buffer, 0).is_err());
+
+        // Non-minimal lengths up to 8 octets are accepted when decoding
+        let eight_octets = [0x04, 0x88, 0, 0, 0, 0, 0, 0, 0x01, 0x00];
+        let mut tag = 0u8;
+        let mut length = 0usize;
+        let pos = decode_tag_length(&mut tag, &mut length, &eight_octets, 0).unwrap();
+        assert_eq!((length, pos), (256, 10));
+
+        let nine_octets = [0x04, 0x89, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x00];
+        assert!(decode_tag_length(&mut tag, &mut length, &nine_octets, 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_high_tag_numbers() {
+        // Context specific primitive tag 31 in two octets, length 1
+        let two_octets = [0x9F, 0x1F, 0x01, 0xAA];
+        assert_eq!(decode_tag(&two_octets, 0).unwrap(), (0x9F, 31, 2));
+
+        let mut tag = 0u8;
+        let mut length = 0usize;
+        let pos = decode_tag_length(&mut tag, &mut length, &two_octets, 0).unwrap();
+        assert_eq!((tag, length, pos), (0x9F, 1, 3));
+
+        // Context specific constructed tag 200 (0x01 0x48 in base 128) in three octets
+        let three_octets = [0xBF, 0x81, 0x48, 0x81, 0x80, 0x00];
+        assert_eq!(decode_tag(&three_octets, 0).unwrap(), (0xBF, 200, 3));
+        let pos = decode_tag_length(&mut tag, &mut length, &three_octets, 0).unwrap();
+        assert_eq!((tag, length, pos), (0xBF, 128, 5));
+
+        // Low tag numbers are unchanged
+        assert_eq!(decode_tag(&[0x87, 0x00], 0).unwrap(), (0x87, 7, 1));
+    }
+
+    #[test]
+    fn test_decode_high_tag_number_errors() {
+        // Continuation octet missing
+        assert!(decode_tag(&[0x9F, 0x81], 0).is_err());
+        // More subsequent octets than a u32 tag number holds
+        assert!(decode_tag(&[0x9F, 0x81, 0x81, 0x81, 0x81, 0x01], 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_str_borrows() {
+        let buffer = b"\x80\x04MU01";
+        let mut val = "";
+        assert_eq!(decode_str(&mut val, buffer, 2, 4).unwrap(), 6);
+        assert_eq!(val, "MU01");
+        assert!(std::ptr::eq(val.as_ptr(), buffer[2..].as_ptr()));
+
+        assert!(decode_str(&mut val, buffer, 2, 5).is_err());
+        assert!(decode_str(&mut val, &[0xFF, 0xFE], 0, 2).is_err());
+    }
+
+    #[test]
+    fn test_is_minimal_integer() {
+        for minimal in [
+            &[0x00][..],
+            &[0x7F],
+            &[0x00, 0x80],
+            &[0xFF],
+            &[0xFF, 0x7F],
+            &[0x12, 0x34],
+        ] {
+            assert!(is_minimal_integer(minimal), "{:02x?}", minimal);
+        }
+        for redundant in [&[][..], &[0x00, 0x01], &[0x00, 0x00, 0x80], &[0xFF, 0x80]] {
+            assert!(!is_minimal_integer(redundant), "{:02x?}", redundant);
+        }
+    }
+
+    #[test]
+    fn test_decompress_zero_length_integer() {
+        let mut value = [0u8; 4];
+        assert!(decompress_integer(&mut value, &[0x61, 0x02, 0x85, 0x00], 4, 0).is_err());
+        let mut val = 0u32;
+        assert!(decode_unsigned_32(&mut val, &[0x85, 0x00, 0x01], 2, 0).is_err());
+    }
+
+    #[test]
+    fn test_empty_integer_input() {
+        assert_eq!(unsigned_integer_length(&[]), 0);
+        assert_eq!(minimal_twos_complement_bytes(&[]), &[] as &[u8]);
+
+        // Empty content as unsigned_integer_length accounts for it
+        let mut buffer = [0xAAu8; 4];
+        assert_eq!(
+            encode_unsigned_integer(0x82, &[], &mut buffer, 1).unwrap(),
+            3
+        );
+        assert_eq!(buffer, [0xAA, 0x82, 0x00, 0xAA]);
+
+        assert_eq!(unsigned_integer_length(&[0x00, 0x80]), 2);
+        assert_eq!(minimal_twos_complement_bytes(&[0xFF, 0xFF, 0x80]), &[0x80]);
+    }
+
+    #[test]
+    fn test_tag_length_at_two_octet_boundary() {
+        for (length, expected) in [
+            (65534, vec![0x30, 0x82, 0xFF, 0xFE]),
+            (65535, vec![0x30, 0x82, 0xFF, 0xFF]),
+            (65536, vec![0x30, 0x83, 0x01, 0x00, 0x00]),
+        ] {
+            assert_eq!(size_length(length), expected.len() - 1);
+
+            let mut buffer = [0u8; 5];
+            let end = encode_tag_length(0x30, length, &mut buffer, 0).unwrap();
+            assert_eq!(&buffer[..end], expected.as_slice(), "length {}", length);
+
+            let mut tag = 0u8;
+            let mut decoded = 0usize;
+            let pos = decode_tag_length(&mut tag, &mut decoded, &buffer, 0).unwrap();
+            assert_eq!((tag, decoded, pos), (0x30, length, end));
+        }
+    }
+}
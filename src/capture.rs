@@ -0,0 +1,126 @@
+use std::io;
+
+use pnet::datalink::{self, Channel};
+
+use crate::decode_goose::is_goose_frame;
+use crate::decode_smv::is_smv_frame;
+
+/// Receives the GOOSE and SMV frames of a network interface.
+///
+/// Opens a pnet datalink channel on `interface` and calls `on_frame` with every GOOSE
+/// or SMV frame, tagged or not. Other traffic is dropped. Capturing usually requires
+/// root or the CAP_NET_RAW capability.
+///
+/// # Parameters
+/// - `interface`: The name of the network interface, e.g. "eth0".
+/// - `on_frame`: Called with the raw Ethernet frame.
+///
+/// # Returns
+/// Only returns with an error, if the interface does not exist or the channel cannot
+/// be opened or read.
+pub fn subscribe(interface: &str, mut on_frame: impl FnMut(&[u8])) -> io::Result<()> {
+    subscribe_while(interface, |frame| {
+        on_frame(frame);
+        true
+    })
+}
+
+/// Receives the GOOSE and SMV frames of a network interface like `subscribe`, until
+/// `on_frame` returns false.
+pub fn subscribe_while(interface: &str, mut on_frame: impl FnMut(&[u8]) -> bool) -> io::Result<()> {
+    let interface = datalink::interfaces()
+        .into_iter()
+        .find(|candidate| candidate.name == interface)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Network interface {} not found", interface),
+            )
+        })?;
+
+    let mut rx = match datalink::channel(&interface, Default::default())? {
+        Channel::Ethernet(_, rx) => rx,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("No Ethernet channel on {}", interface.name),
+            ))
+        }
+    };
+
+    loop {
+        let frame = rx.next()?;
+        if let Some(false) = dispatch(frame, &mut on_frame) {
+            return Ok(());
+        }
+    }
+}
+
+/// Passes `frame` to `on_frame` if it is a GOOSE or SMV frame and returns the result
+fn dispatch(frame: &[u8], on_frame: &mut impl FnMut(&[u8]) -> bool) -> Option<bool> {
+    if is_goose_frame(frame) || is_smv_frame(frame) {
+        Some(on_frame(frame))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(ether_type: [u8; 2], vlan: bool) -> Vec<u8> {
+        let mut frame = vec![0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01];
+        frame.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        if vlan {
+            frame.extend_from_slice(&[0x81, 0x00, 0x80, 0x01]);
+        }
+        frame.extend_from_slice(&ether_type);
+        frame.resize(60, 0);
+        frame
+    }
+
+    #[test]
+    fn test_dispatch_filters_iec_61850_frames() {
+        let frames = [
+            frame([0x88, 0xb8], false),
+            frame([0x88, 0xb9], true),
+            frame([0x88, 0xba], false),
+            frame([0x88, 0xba], true),
+            frame([0x08, 0x00], false),
+            frame([0x86, 0xdd], true),
+            vec![0x01, 0x02],
+        ];
+
+        let mut received = vec![];
+        let results: Vec<Option<bool>> = frames
+            .iter()
+            .map(|frame| {
+                dispatch(frame, &mut |frame: &[u8]| {
+                    received.push(frame.to_vec());
+                    true
+                })
+            })
+            .collect();
+
+        assert_eq!(
+            results,
+            vec![
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(true),
+                None,
+                None,
+                None
+            ]
+        );
+        assert_eq!(received, frames[..4].to_vec());
+    }
+
+    #[test]
+    fn test_subscribe_unknown_interface() {
+        let err = subscribe("no-such-interface0", |_| {}).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
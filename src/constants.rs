@@ -0,0 +1,52 @@
+/// EtherType of GOOSE (IEC 61850-8-1)
+pub const ETHERTYPE_GOOSE: [u8; 2] = [0x88, 0xb8];
+/// EtherType of GSE management, also accepted for GOOSE by older publishers
+pub const ETHERTYPE_GSE: [u8; 2] = [0x88, 0xb9];
+/// EtherType of Sampled Values (IEC 61850-9-2)
+pub const ETHERTYPE_SMV: [u8; 2] = [0x88, 0xba];
+
+/// Minimum length of an Ethernet frame without frame check sequence
+pub const MIN_FRAME_LENGTH: usize = 60;
+/// Length of the Ethernet frame check sequence
+pub const FCS_LENGTH: usize = 4;
+
+/// First destination address of the recommended GOOSE multicast range
+/// `01:0C:CD:01:00:00` to `01:0C:CD:01:01:FF`
+pub const GOOSE_MCAST_BASE: [u8; 6] = [0x01, 0x0c, 0xcd, 0x01, 0x00, 0x00];
+/// First destination address of the recommended Sampled Values multicast range
+/// `01:0C:CD:04:00:00` to `01:0C:CD:04:01:FF`
+pub const SMV_MCAST_BASE: [u8; 6] = [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x00];
+
+/// Checks whether `mac` lies in one of the multicast ranges IEC 61850-8-1 and 9-2
+/// recommend for GOOSE, GSSE (`01:0C:CD:02:..`) and Sampled Values.
+pub fn is_iec61850_multicast(mac: [u8; 6]) -> bool {
+    matches!(mac, [0x01, 0x0c, 0xcd, 0x01 | 0x02 | 0x04, 0x00 | 0x01, _])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(u16::from_be_bytes(ETHERTYPE_GOOSE), 0x88b8);
+        assert_eq!(u16::from_be_bytes(ETHERTYPE_GSE), 0x88b9);
+        assert_eq!(u16::from_be_bytes(ETHERTYPE_SMV), 0x88ba);
+        assert_eq!(GOOSE_MCAST_BASE[..4], [0x01, 0x0c, 0xcd, 0x01]);
+        assert_eq!(SMV_MCAST_BASE[..4], [0x01, 0x0c, 0xcd, 0x04]);
+    }
+
+    #[test]
+    fn test_is_iec61850_multicast() {
+        assert!(is_iec61850_multicast(GOOSE_MCAST_BASE));
+        assert!(is_iec61850_multicast(SMV_MCAST_BASE));
+        assert!(is_iec61850_multicast([0x01, 0x0c, 0xcd, 0x01, 0x01, 0xff]));
+        assert!(is_iec61850_multicast([0x01, 0x0c, 0xcd, 0x02, 0x00, 0x10]));
+        assert!(is_iec61850_multicast([0x01, 0x0c, 0xcd, 0x04, 0x01, 0x00]));
+
+        assert!(!is_iec61850_multicast([0x01, 0x0c, 0xcd, 0x01, 0x02, 0x00]));
+        assert!(!is_iec61850_multicast([0x01, 0x0c, 0xcd, 0x03, 0x00, 0x00]));
+        assert!(!is_iec61850_multicast([0x01, 0x00, 0x5e, 0x00, 0x00, 0x01]));
+        assert!(!is_iec61850_multicast([0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c]));
+    }
+}
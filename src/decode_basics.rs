@@ -1,4 +1,6 @@
-use crate::types::EthernetHeader;
+use alloc::format;
+
+use crate::types::{DecodeError, EthernetHeader};
 
 /// Decodes an Ethernet header from the buffer at the specified position,
 /// writing the result into the provided mutable reference.
@@ -11,7 +13,8 @@ use crate::types::EthernetHeader;
 /// The next position in the buffer after reading the Ethernet header.
 ///
 /// # Panics
-/// Panics if the buffer does not contain enough bytes to decode the header.
+/// Panics if the buffer does not contain enough bytes to decode the header, see
+/// `try_decode_ethernet_header` for frames of unknown length.
 pub fn decode_ethernet_header(header: &mut EthernetHeader, buffer: &[u8]) -> usize {
     let mut new_pos = 0;
 
@@ -57,6 +60,112 @@ pub fn decode_ethernet_header(header: &mut EthernetHeader, buffer: &[u8]) -> usi
     new_pos
 }
 
+/// Decodes an Ethernet header like `decode_ethernet_header`, returning an error
+/// instead of panicking if the buffer is too short.
+///
+/// # Parameters
+/// - `header`: A mutable reference where the decoded EthernetHeader will be stored.
+///   It is left unchanged on error.
+/// - `buffer`: The input byte slice containing the encoded Ethernet header.
+///
+/// # Returns
+/// The next position in the buffer after the Ethernet header, or a DecodeError at the
+/// field the buffer ends in.
+pub fn try_decode_ethernet_header(
+    header: &mut EthernetHeader,
+    buffer: &[u8],
+) -> Result<usize, DecodeError> {
+    let mut decoded = EthernetHeader {
+        dst_addr: header_field(buffer, 0, "the destination address")?,
+        src_addr: header_field(buffer, 6, "the source address")?,
+        ..Default::default()
+    };
+    let mut pos = 12;
+    let tpid = header_field(buffer, pos, "the EtherType")?;
+    if tpid == [0x81, 0x00] {
+        decoded.tpid = Some(tpid);
+        decoded.tci = Some(header_field(buffer, pos + 2, "the VLAN tag")?);
+        pos += 4;
+    }
+    decoded.ether_type = header_field(buffer, pos, "the EtherType")?;
+    decoded.appid = header_field(buffer, pos + 2, "the APPID")?;
+    decoded.length = header_field(buffer, pos + 4, "the length")?;
+    header_field::<2>(buffer, pos + 6, "reserved 1")?;
+    header_field::<2>(buffer, pos + 8, "reserved 2")?;
+
+    *header = decoded;
+    Ok(pos + 10)
+}
+
+/// Reads the `N` bytes of a header field at `pos`
+fn header_field<const N: usize>(
+    buffer: &[u8],
+    pos: usize,
+    name: &str,
+) -> Result<[u8; N], DecodeError> {
+    buffer
+        .get(pos..pos + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            DecodeError::new(
+                &format!(
+                    "Ethernet header ends in {}, buffer has {} bytes",
+                    name,
+                    buffer.len()
+                ),
+                pos,
+            )
+        })
+}
+
+/// Extracts the SIM bit from the "reserved 1" field in the GOOSE or SV/SMV header.
+/// The SIM bit is the most significant bit (bit 7) of the first byte of reserved 1.
+/// Returns Some(true) if the SIM bit is set, Some(false) if not, or None if the buffer is too short.
+///
+/// # Arguments
+/// * `buffer` - The Ethernet frame buffer (must be long enough to contain reserved 1).
+pub fn decode_sim_bit(buffer: &[u8]) -> Option<bool> {
+    // Ethernet: 6 (dst) + 6 (src)
+    let mut offset = 12;
+
+    // Check for VLAN tag (0x81, 0x00)
+    if buffer.len() >= offset + 2 && buffer[offset..offset + 2] == [0x81, 0x00] {
+        offset += 4; // VLAN tag is 4 bytes
+    }
+
+    // EtherType (2) + appid (2) + length (2)
+    offset += 2 + 2 + 2;
+
+    // Now offset points to the first byte of reserved 1
+    if buffer.len() <= offset {
+        return None;
+    }
+
+    let reserved1_byte = buffer[offset];
+    Some((reserved1_byte & 0x80) != 0)
+}
+
+/// Extracts the APPID, which follows the EtherType, from a GOOSE or SV/SMV header.
+/// Returns the APPID, or None if the buffer is too short.
+///
+/// # Arguments
+/// * `buffer` - The Ethernet frame buffer, with or without VLAN tag.
+pub fn decode_appid(buffer: &[u8]) -> Option<u16> {
+    // Ethernet: 6 (dst) + 6 (src)
+    let mut offset = 12;
+
+    // Check for VLAN tag (0x81, 0x00)
+    if buffer.get(offset..offset + 2) == Some(&[0x81, 0x00]) {
+        offset += 4; // VLAN tag is 4 bytes
+    }
+
+    // EtherType (2)
+    offset += 2;
+
+    let appid = buffer.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([appid[0], appid[1]]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +291,84 @@ mod tests {
         let mut header = EthernetHeader::default();
         decode_ethernet_header(&mut header, &buffer);
     }
+
+    #[test]
+    fn test_try_decode_ethernet_header() {
+        let mut header = EthernetHeader::default();
+        let err = try_decode_ethernet_header(&mut header, &[0xff; 10]).unwrap_err();
+        assert_eq!(err.buffer_index, 6);
+        assert!(err.message.contains("source address"), "{}", err.message);
+        assert_eq!(header, EthernetHeader::default());
+
+        let mut frame = vec![
+            0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01, 0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c,
+        ];
+        frame.extend([
+            0x81, 0x00, 0x80, 0x01, 0x88, 0xb8, 0x10, 0x01, 0x00, 0x8c, 0x00, 0x00,
+        ]);
+        // Ends before reserved 2
+        let err = try_decode_ethernet_header(&mut header, &frame).unwrap_err();
+        assert_eq!(err.buffer_index, 24);
+        frame.extend([0x00, 0x00]);
+        assert_eq!(try_decode_ethernet_header(&mut header, &frame).unwrap(), 26);
+        let mut expected = EthernetHeader::default();
+        assert_eq!(decode_ethernet_header(&mut expected, &frame), 26);
+        assert_eq!(header, expected);
+        assert_eq!(header.tci, Some([0x80, 0x01]));
+    }
+
+    #[test]
+    fn test_decode_sim_bit() {
+        // Without VLAN, SIM bit not set
+        let mut frame = vec![
+            0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, // dst
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src
+            0x88, 0xba, // EtherType
+            0x40, 0x00, // APPID
+            0x00, 0x64, // Length
+            0x00, 0x00, // Reserved 1 (SIM bit = 0)
+        ];
+
+        assert_eq!(decode_sim_bit(&frame), Some(false));
+
+        // Set SIM bit (MSB of reserved 1)
+        frame[18] = 0x80;
+        assert_eq!(decode_sim_bit(&frame), Some(true));
+    }
+
+    #[test]
+    fn test_decode_sim_bit_goose_with_vlan() {
+        let mut frame = vec![
+            0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01, // dst
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src
+            0x81, 0x00, 0x80, 0x01, // VLAN tag
+            0x88, 0xb8, // EtherType
+            0x10, 0x01, // APPID
+            0x00, 0x64, // Length
+            0x00, 0x00, // Reserved 1 (SIM bit = 0)
+        ];
+
+        assert_eq!(decode_sim_bit(&frame), Some(false));
+        frame[22] = 0x80;
+        assert_eq!(decode_sim_bit(&frame), Some(true));
+        assert_eq!(decode_sim_bit(&frame[..22]), None);
+    }
+
+    #[test]
+    fn test_decode_appid() {
+        let frame = [
+            0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01, // dst
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src
+            0x81, 0x00, 0x80, 0x01, // VLAN tag
+            0x88, 0xb8, // EtherType
+            0x10, 0x01, // APPID
+        ];
+        assert_eq!(decode_appid(&frame), Some(0x1001));
+        assert_eq!(decode_appid(&frame[..19]), None);
+
+        // Without VLAN tag
+        let frame = [&frame[..12], &frame[16..]].concat();
+        assert_eq!(decode_appid(&frame), Some(0x1001));
+        assert_eq!(decode_appid(&frame[..13]), None);
+    }
 }
@@ -1,5 +1,11 @@
-use crate::types::{DecodeError, IECGoosePdu, IECGoosePduRasn};
-use rasn::ber::decode;
+use crate::ber::{decode_string, decode_tag_length, decode_unsigned_32};
+use crate::constants::{ETHERTYPE_GOOSE, ETHERTYPE_GSE, FCS_LENGTH, MIN_FRAME_LENGTH};
+use crate::decode_basics::decode_appid;
+use crate::types::{
+    DataTemplate, DataType, DecodeError, DecodeOptions, DecodeWarning, GooseHeaderView, IECData,
+    IECDataRasn, IECGoosePdu, IECGoosePduRasn,
+};
+use rasn::ber::decode_with_remainder;
 
 // Re-export decode_ethernet_header for backward compatibility
 pub use crate::decode_basics::decode_ethernet_header;
@@ -7,16 +13,398 @@ pub use crate::decode_basics::decode_ethernet_header;
 /// Decodes a GOOSE PDU from the buffer using rasn.
 /// Returns the decoded PDU.
 ///
+/// Bytes following the PDU (padding, frame check sequence) are ignored.
+///
 /// # Parameters
 /// - `buffer`: The input byte slice containing the encoded GOOSE PDU (just the PDU, not Ethernet headers)
 ///
 /// # Returns
 /// The decoded IECGoosePdu with all_data still in raw form
 pub fn decode_goose_pdu(buffer: &[u8], pos: usize) -> Result<IECGoosePdu, DecodeError> {
-    let pdu: IECGoosePduRasn = decode(&buffer[pos..])
-        .map_err(|e| DecodeError::new(&format!("Failed to decode GOOSE PDU: {:?}", e), 0))?;
+    decode_goose_pdu_with_warnings(buffer, pos).map(|(pdu, _, _)| pdu)
+}
+
+/// Decodes a GOOSE PDU from the buffer and reports unexpected trailing bytes.
+///
+/// Decoding stops at the end of the PDU. Bytes after it are expected when they pad the
+/// frame to the Ethernet minimum length or hold the frame check sequence, any other
+/// trailing bytes are reported as warning. Inconsistencies found by
+/// `IECGoosePdu::validate` are reported as warning as well.
+///
+/// # Parameters
+/// - `buffer`: The complete frame containing the encoded GOOSE PDU
+/// - `pos`: The position of the GOOSE PDU in the buffer
+///
+/// # Returns
+/// The decoded PDU, the number of bytes the PDU occupies and the warnings
+pub fn decode_goose_pdu_with_warnings(
+    buffer: &[u8],
+    pos: usize,
+) -> Result<(IECGoosePdu, usize, Vec<DecodeWarning>), DecodeError> {
+    decode_goose_pdu_with_options(buffer, pos, &DecodeOptions::default())
+}
+
+/// Decodes a GOOSE PDU like `decode_goose_pdu_with_warnings`.
+///
+/// In `DecodeMode::Strict`, inconsistencies found by `IECGoosePdu::validate` are
+/// returned as error instead of warning.
+pub fn decode_goose_pdu_with_options(
+    buffer: &[u8],
+    pos: usize,
+    options: &DecodeOptions,
+) -> Result<(IECGoosePdu, usize, Vec<DecodeWarning>), DecodeError> {
+    let input = buffer
+        .get(pos..)
+        .ok_or_else(|| DecodeError::new("GOOSE PDU position is out of bounds", pos))?;
+    let (pdu, remainder): (IECGoosePduRasn, _) = decode_with_remainder(input)
+        .map_err(|e| DecodeError::new(&format!("Failed to decode GOOSE PDU: {:?}", e), pos))?;
+    let pdu = IECGoosePdu::from(&pdu);
+
+    let consumed = input.len() - remainder.len();
+    let pdu_end = pos + consumed;
+    let mut warnings = vec![];
+    if let Err(message) = pdu.validate() {
+        if options.is_strict() {
+            return Err(DecodeError::new(&message, pos));
+        }
+        warnings.push(DecodeWarning::new(&message, pos));
+    }
+    if !is_expected_trailer(buffer.len(), remainder.len()) {
+        warnings.push(DecodeWarning::new(
+            &format!("{} unexpected bytes after the GOOSE PDU", remainder.len()),
+            pdu_end,
+        ));
+    }
 
-    Ok(IECGoosePdu::from(&pdu))
+    Ok((pdu, consumed, warnings))
+}
+
+/// Decodes a GOOSE PDU and interprets the data set according to `template`.
+///
+/// Integers are read with the signedness the template declares, whatever their tag,
+/// e.g. an INT32U sent as `0x85 04 ff ff ff ff` decodes as `UInt(4294967295)` instead
+/// of `Int(-1)`. Structures and arrays are matched member by member.
+///
+/// # Parameters
+/// - `buffer`: The complete frame containing the encoded GOOSE PDU
+/// - `pos`: The position of the GOOSE PDU in the buffer
+/// - `template`: The declared types of the data set entries
+///
+/// # Returns
+/// The decoded PDU, or an error if the number of template entries differs from
+/// `num_dat_set_entries` or an entry does not match its declared type
+pub fn decode_goose_pdu_typed(
+    buffer: &[u8],
+    pos: usize,
+    template: &DataTemplate,
+) -> Result<IECGoosePdu, DecodeError> {
+    let mut pdu = decode_goose_pdu(buffer, pos)?;
+    if template.entries.len() != pdu.num_dat_set_entries as usize {
+        return Err(DecodeError::new(
+            &format!(
+                "Template has {} entries, numDatSetEntries is {}",
+                template.entries.len(),
+                pdu.num_dat_set_entries
+            ),
+            pos,
+        ));
+    }
+
+    // Find allData, the PDU itself has been checked by rasn already
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let mut index = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
+    let pdu_end = index + length;
+    while index < pdu_end {
+        index = decode_tag_length(&mut tag, &mut length, buffer, index)?;
+        if tag == 0xab {
+            break;
+        }
+        index += length;
+    }
+    if tag != 0xab {
+        return Err(DecodeError::new("GOOSE PDU misses allData", pos));
+    }
+
+    let all_data_end = index + length;
+    let mut all_data = Vec::with_capacity(template.entries.len());
+    for data_type in &template.entries {
+        let (next, data) = decode_typed_data(buffer, index, all_data_end, data_type)?;
+        all_data.push(data);
+        index = next;
+    }
+    if index != all_data_end {
+        return Err(DecodeError::new(
+            "allData holds more entries than the template",
+            index,
+        ));
+    }
+    pdu.all_data = all_data;
+    Ok(pdu)
+}
+
+/// Decodes one data element ending before `end` as `data_type`
+fn decode_typed_data(
+    buffer: &[u8],
+    pos: usize,
+    end: usize,
+    data_type: &DataType,
+) -> Result<(usize, IECData), DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let index = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
+    let element_end = index + length;
+    if element_end > end {
+        return Err(DecodeError::new(
+            &format!("Element 0x{:02x} exceeds its container", tag),
+            pos,
+        ));
+    }
+
+    let data = match (data_type, tag) {
+        (DataType::Structure(members), 0xa2) => {
+            let mut index = index;
+            let mut values = Vec::with_capacity(members.len());
+            for member in members {
+                let (next, value) =
+                    decode_typed_data(buffer, index, element_end, &member.data_type)?;
+                values.push(value);
+                index = next;
+            }
+            if index != element_end {
+                return Err(DecodeError::new(
+                    "Structure holds more members than the template",
+                    index,
+                ));
+            }
+            IECData::Structure(values)
+        }
+        (
+            DataType::Array {
+                count,
+                element_type,
+            },
+            0xa1,
+        ) => {
+            let mut index = index;
+            let mut values = vec![];
+            while index < element_end {
+                let (next, value) = decode_typed_data(buffer, index, element_end, element_type)?;
+                values.push(value);
+                index = next;
+            }
+            if values.len() != *count as usize {
+                return Err(DecodeError::new(
+                    &format!(
+                        "Array has {} elements, the template declares {}",
+                        values.len(),
+                        count
+                    ),
+                    pos,
+                ));
+            }
+            IECData::Array(values)
+        }
+        (DataType::Int, 0x85 | 0x86) => {
+            decode_typed_integer(&buffer[index..element_end], true, index)?
+        }
+        (DataType::UInt, 0x85 | 0x86) => {
+            decode_typed_integer(&buffer[index..element_end], false, index)?
+        }
+        (DataType::Structure(_) | DataType::Array { .. } | DataType::Int | DataType::UInt, _) => {
+            return Err(DecodeError::new(
+                &format!("Expected {:?}, found tag 0x{:02x}", data_type, tag),
+                pos,
+            ));
+        }
+        _ => {
+            let value: IECDataRasn = rasn::ber::decode(&buffer[pos..element_end])
+                .map_err(|e| DecodeError::new(&format!("Failed to decode data: {:?}", e), pos))?;
+            let value = IECData::from(&value);
+            if !matches!(
+                (data_type, &value),
+                (DataType::Boolean, IECData::Boolean(_))
+                    | (DataType::BitString, IECData::BitString(_))
+                    | (DataType::Float, IECData::Float(_) | IECData::Float32(_))
+                    | (DataType::OctetString, IECData::OctetString(_))
+                    | (DataType::VisibleString, IECData::VisibleString(_))
+                    | (DataType::MmsString, IECData::MmsString(_))
+                    | (DataType::Timestamp, IECData::Timestamp(_))
+            ) {
+                return Err(DecodeError::new(
+                    &format!("Expected {:?}, found {}", data_type, value.type_name()),
+                    pos,
+                ));
+            }
+            value
+        }
+    };
+    Ok((element_end, data))
+}
+
+/// Reads the content octets of an integer as signed or unsigned value
+fn decode_typed_integer(content: &[u8], signed: bool, pos: usize) -> Result<IECData, DecodeError> {
+    if content.is_empty() {
+        return Err(DecodeError::new("Integer without content", pos));
+    }
+    if signed {
+        if content.len() > 8 {
+            return Err(DecodeError::new("Integer exceeds 64 bit", pos));
+        }
+        let sign = if content[0] & 0x80 != 0 { -1 } else { 0 };
+        let value = content
+            .iter()
+            .fold(sign, |value: i64, &byte| (value << 8) | byte as i64);
+        Ok(IECData::Int(value))
+    } else {
+        let start = content
+            .iter()
+            .position(|&byte| byte != 0)
+            .unwrap_or(content.len());
+        if content.len() - start > 8 {
+            return Err(DecodeError::new("Unsigned integer exceeds 64 bit", pos));
+        }
+        let value = content[start..]
+            .iter()
+            .fold(0u64, |value, &byte| (value << 8) | byte as u64);
+        Ok(IECData::UInt(value))
+    }
+}
+
+/// Decodes the control block references and counters of a GOOSE PDU without rasn.
+///
+/// Only the context tags 0 to 3 and 5 to 7 are read; the timestamp, the remaining
+/// header fields and the data set are skipped. This is enough to detect state changes
+/// and retransmissions at a fraction of the cost of `decode_goose_pdu`.
+///
+/// # Parameters
+/// - `buffer`: The complete frame containing the encoded GOOSE PDU
+/// - `pos`: The position of the GOOSE PDU in the buffer
+///
+/// # Returns
+/// The decoded header fields, or an error if the PDU is malformed or misses gocbRef,
+/// timeAllowedToLive, datSet, stNum or sqNum
+pub fn decode_goose_header(buffer: &[u8], pos: usize) -> Result<GooseHeaderView, DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let mut index = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
+    if tag != 0x61 {
+        return Err(DecodeError::new(
+            &format!("Expected GOOSE PDU tag 0x61, found 0x{:02x}", tag),
+            pos,
+        ));
+    }
+    let pdu_end = index + length;
+    if pdu_end > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "GOOSE PDU length {} exceeds buffer length {}",
+                length,
+                buffer.len()
+            ),
+            pos,
+        ));
+    }
+
+    let mut header = GooseHeaderView::default();
+    // Bit n is set once context tag n has been read
+    let mut found = 0u8;
+    while index < pdu_end && tag != 0x87 {
+        let element = index;
+        index = decode_tag_length(&mut tag, &mut length, buffer, index)?;
+        if index + length > pdu_end {
+            return Err(DecodeError::new(
+                &format!("Element 0x{:02x} exceeds the GOOSE PDU", tag),
+                element,
+            ));
+        }
+        let next = match tag {
+            0x80 => decode_string(&mut header.go_cb_ref, buffer, index, length)?,
+            0x81 => decode_unsigned_32(&mut header.time_allowed_to_live, buffer, index, length)?,
+            0x82 => decode_string(&mut header.dat_set, buffer, index, length)?,
+            0x83 => decode_string(&mut header.go_id, buffer, index, length)?,
+            0x85 => decode_unsigned_32(&mut header.st_num, buffer, index, length)?,
+            0x86 => decode_unsigned_32(&mut header.sq_num, buffer, index, length)?,
+            0x87 => {
+                header.simulation = length > 0 && buffer[index] != 0;
+                index + length
+            }
+            _ => index + length,
+        };
+        if (0x80..=0x87).contains(&tag) {
+            found |= 1 << (tag - 0x80);
+        }
+        index = next;
+    }
+
+    for (bit, name) in [
+        (0, "gocbRef"),
+        (1, "timeAllowedtoLive"),
+        (2, "datSet"),
+        (5, "stNum"),
+        (6, "sqNum"),
+    ] {
+        if found & (1 << bit) == 0 {
+            return Err(DecodeError::new(&format!("GOOSE PDU misses {}", name), pos));
+        }
+    }
+    Ok(header)
+}
+
+/// Reads only stNum and sqNum of a GOOSE PDU, e.g. to tell retransmissions from new
+/// events before decoding anything else.
+///
+/// # Parameters
+/// - `buffer`: The complete frame containing the encoded GOOSE PDU
+/// - `pos`: The position of the GOOSE PDU in the buffer
+///
+/// # Returns
+/// `(st_num, sq_num)`, or an error if the PDU is malformed or misses one of them
+pub fn peek_goose_st_sq(buffer: &[u8], pos: usize) -> Result<(u32, u32), DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let mut index = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
+    if tag != 0x61 {
+        return Err(DecodeError::new(
+            &format!("Expected GOOSE PDU tag 0x61, found 0x{:02x}", tag),
+            pos,
+        ));
+    }
+    let pdu_end = (index + length).min(buffer.len());
+
+    let mut st_num = None;
+    let mut sq_num = None;
+    while index < pdu_end {
+        index = decode_tag_length(&mut tag, &mut length, buffer, index)?;
+        match tag {
+            0x85 => {
+                let mut value = 0;
+                decode_unsigned_32(&mut value, buffer, index, length)?;
+                st_num = Some(value);
+            }
+            0x86 => {
+                let mut value = 0;
+                decode_unsigned_32(&mut value, buffer, index, length)?;
+                sq_num = Some(value);
+            }
+            _ => {}
+        }
+        if let (Some(st_num), Some(sq_num)) = (st_num, sq_num) {
+            return Ok((st_num, sq_num));
+        }
+        index += length;
+    }
+
+    Err(DecodeError::new("GOOSE PDU misses stNum or sqNum", pos))
+}
+
+/// Checks whether `trailing` bytes at the end of a frame of `frame_length` bytes are
+/// Ethernet padding and/or the frame check sequence.
+fn is_expected_trailer(frame_length: usize, trailing: usize) -> bool {
+    let content_length = frame_length - trailing;
+    trailing == 0
+        || trailing == FCS_LENGTH
+        || (content_length < MIN_FRAME_LENGTH && frame_length <= MIN_FRAME_LENGTH + FCS_LENGTH)
 }
 
 /// Checks if the given buffer contains a GOOSE frame by inspecting the EtherType field,
@@ -39,30 +427,56 @@ pub fn is_goose_frame(buffer: &[u8]) -> bool {
         return false;
     }
     let ether_type = &buffer[ether_type_offset..ether_type_offset + 2];
-    ether_type == [0x88, 0xb8] || ether_type == [0x88, 0xb9]
+    ether_type == ETHERTYPE_GOOSE || ether_type == ETHERTYPE_GSE
+}
+
+/// Reads the APPID of a GOOSE frame, which follows the EtherType, so subscribers can
+/// skip foreign streams before decoding.
+///
+/// Returns `None` if the buffer is no GOOSE frame according to `is_goose_frame` or ends
+/// before the APPID.
+pub fn goose_appid(buffer: &[u8]) -> Option<u16> {
+    if !is_goose_frame(buffer) {
+        return None;
+    }
+    decode_appid(buffer)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{EthernetHeader, IECData};
+    use crate::types::{DecodeMode, EthernetHeader};
+
+    /// VLAN tagged frame with eleven data set entries, the last a VisibleString
+    const ALL_FIELDS_FRAME: &[u8] = &[
+        1, 12, 205, 1, 0, 1, 0, 26, 182, 3, 47, 28, 129, 0, 0, 1, 136, 184, 16, 1, 0, 140, 0, 0, 0,
+        0, 97, 129, 129, 128, 17, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 71, 79, 36, 103, 99, 98,
+        49, 129, 2, 7, 208, 130, 18, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 68, 65, 84, 65, 83,
+        69, 84, 49, 131, 6, 71, 79, 79, 83, 69, 49, 132, 8, 32, 33, 6, 18, 10, 48, 0, 0, 133, 1, 1,
+        134, 1, 42, 135, 1, 0, 136, 2, 0, 128, 137, 1, 0, 138, 1, 11, 171, 47, 134, 1, 1, 134, 2,
+        0, 128, 134, 2, 0, 255, 134, 1, 127, 134, 1, 1, 134, 2, 0, 128, 134, 2, 0, 255, 131, 1,
+        255, 133, 4, 127, 255, 255, 255, 133, 5, 0, 128, 0, 0, 0, 138, 4, 116, 101, 115, 116,
+    ];
+
+    /// ALL_FIELDS_FRAME with a FLOAT32 230.5 and a 13-bit quality appended to allData
+    const FLOAT32_FRAME: &[u8] = &[
+        1, 12, 205, 1, 0, 1, 0, 26, 182, 3, 47, 28, 129, 0, 0, 1, 136, 184, 16, 1, 0, 152, 0, 0, 0,
+        0, 97, 129, 141, 128, 17, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 71, 79, 36, 103, 99, 98,
+        49, 129, 2, 7, 208, 130, 18, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 68, 65, 84, 65, 83,
+        69, 84, 49, 131, 6, 71, 79, 79, 83, 69, 49, 132, 8, 32, 33, 6, 18, 10, 48, 0, 0, 133, 1, 1,
+        134, 1, 42, 135, 1, 0, 136, 2, 0, 128, 137, 1, 0, 138, 1, 13, 171, 59, 134, 1, 1, 134, 2,
+        0, 128, 134, 2, 0, 255, 134, 1, 127, 134, 1, 1, 134, 2, 0, 128, 134, 2, 0, 255, 131, 1,
+        255, 133, 4, 127, 255, 255, 255, 133, 5, 0, 128, 0, 0, 0, 138, 4, 116, 101, 115, 116, 135,
+        5, 8, 67, 102, 128, 0, 132, 3, 3, 192, 16,
+    ];
 
     #[test]
     fn test_decode_goose_pdu_all_fields() {
-        let buf: &[u8] = &[
-            1, 12, 205, 1, 0, 1, 0, 26, 182, 3, 47, 28, 129, 0, 0, 1, 136, 184, 16, 1, 0, 140, 0,
-            0, 0, 0, 97, 129, 129, 128, 17, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 71, 79, 36,
-            103, 99, 98, 49, 129, 2, 7, 208, 130, 18, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 68,
-            65, 84, 65, 83, 69, 84, 49, 131, 6, 71, 79, 79, 83, 69, 49, 132, 8, 32, 33, 6, 18, 10,
-            48, 0, 0, 133, 1, 1, 134, 1, 42, 135, 1, 0, 136, 2, 0, 128, 137, 1, 0, 138, 1, 11, 171,
-            47, 134, 1, 1, 134, 2, 0, 128, 134, 2, 0, 255, 134, 1, 127, 134, 1, 1, 134, 2, 0, 128,
-            134, 2, 0, 255, 131, 1, 255, 133, 4, 127, 255, 255, 255, 133, 5, 0, 128, 0, 0, 0, 138,
-            4, 116, 101, 115, 116,
-        ];
+        let buf = ALL_FIELDS_FRAME;
 
         let mut header = EthernetHeader::default();
-        let pos = decode_ethernet_header(&mut header, &buf);
-        let goose_pdu = decode_goose_pdu(&buf, pos).unwrap();
+        let pos = decode_ethernet_header(&mut header, buf);
+        let goose_pdu = decode_goose_pdu(buf, pos).unwrap();
 
         println!("Decoded GOOSE PDU: {:?}", goose_pdu.all_data[10]);
 
@@ -98,6 +512,259 @@ mod tests {
         assert_eq!(data[10], IECData::VisibleString("test".to_string()));
     }
 
+    #[test]
+    fn test_decode_goose_pdu_reencodes_identically() {
+        use crate::encode_goose::encode_goose;
+
+        // Minimally encoded integers, FLOAT32 values and bit strings that are not a
+        // multiple of 8 bits long come back in the same octets
+        for frame in [ALL_FIELDS_FRAME, FLOAT32_FRAME] {
+            let mut header = EthernetHeader::default();
+            let pos = decode_ethernet_header(&mut header, frame);
+            let goose_pdu = decode_goose_pdu(frame, pos).unwrap();
+
+            let encoded = encode_goose(&header, &goose_pdu).unwrap();
+            assert_eq!(encoded, frame);
+        }
+    }
+
+    #[test]
+    fn test_decode_goose_pdu_keeps_float32_and_bit_string_width() {
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, FLOAT32_FRAME);
+        let goose_pdu = decode_goose_pdu(FLOAT32_FRAME, pos).unwrap();
+
+        assert_eq!(goose_pdu.num_dat_set_entries, 13);
+        assert_eq!(goose_pdu.all_data[11], IECData::Float32(230.5));
+        assert_eq!(
+            goose_pdu.all_data[12],
+            IECData::BitString("1100000000010".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_goose_header() {
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, ALL_FIELDS_FRAME);
+
+        let view = decode_goose_header(ALL_FIELDS_FRAME, pos).unwrap();
+        assert_eq!(
+            view,
+            GooseHeaderView {
+                go_cb_ref: "IED1/LLN0$GO$gcb1".to_string(),
+                time_allowed_to_live: 2000,
+                dat_set: "IED1/LLN0$DATASET1".to_string(),
+                go_id: "GOOSE1".to_string(),
+                st_num: 1,
+                sq_num: 42,
+                simulation: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_goose_header_malformed() {
+        let pos = 26;
+
+        // Cut inside the PDU
+        assert!(decode_goose_header(&ALL_FIELDS_FRAME[..100], pos).is_err());
+        // Not a GOOSE PDU
+        assert!(decode_goose_header(ALL_FIELDS_FRAME, pos + 3).is_err());
+        // gocbRef tag replaced by an unknown context tag
+        let mut frame = ALL_FIELDS_FRAME.to_vec();
+        frame[29] = 0x8F;
+        let err = decode_goose_header(&frame, pos).unwrap_err();
+        assert!(err.to_string().contains("gocbRef"), "{}", err);
+    }
+
+    #[test]
+    fn test_peek_goose_st_sq() {
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, ALL_FIELDS_FRAME);
+        assert_eq!(peek_goose_st_sq(ALL_FIELDS_FRAME, pos).unwrap(), (1, 42));
+
+        // Truncated before sqNum
+        assert!(peek_goose_st_sq(&ALL_FIELDS_FRAME[..95], pos).is_err());
+        assert!(peek_goose_st_sq(ALL_FIELDS_FRAME, pos + 1).is_err());
+    }
+
+    #[test]
+    fn test_zero_length_integer_at_buffer_end() {
+        // stNum with empty content as last bytes of the buffer
+        let buf = [0x61, 0x02, 0x85, 0x00];
+        assert!(decode_goose_header(&buf, 0).is_err());
+        assert!(peek_goose_st_sq(&buf, 0).is_err());
+    }
+
+    #[test]
+    fn test_num_dat_set_entries_mismatch() {
+        // Drop the last data set entry (VisibleString "test") but keep numDatSetEntries 11
+        let mut buf = ALL_FIELDS_FRAME[..ALL_FIELDS_FRAME.len() - 6].to_vec();
+        buf[21] -= 6; // Ethernet length
+        buf[28] -= 6; // GOOSE PDU length
+        let all_data_pos = buf.windows(2).position(|w| w == [171, 47]).unwrap();
+        buf[all_data_pos + 1] -= 6;
+
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, &buf);
+        let (goose_pdu, _, warnings) = decode_goose_pdu_with_warnings(&buf, pos).unwrap();
+
+        assert_eq!(goose_pdu.num_dat_set_entries, 11);
+        assert_eq!(goose_pdu.all_data.len(), 10);
+        assert!(goose_pdu.validate().is_err());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("numDatSetEntries is 11"));
+
+        let strict = DecodeOptions {
+            mode: DecodeMode::Strict,
+            ..Default::default()
+        };
+        let err = decode_goose_pdu_with_options(&buf, pos, &strict).unwrap_err();
+        assert!(
+            err.message.contains("numDatSetEntries is 11"),
+            "{}",
+            err.message
+        );
+        assert!(decode_goose_pdu_with_options(ALL_FIELDS_FRAME, pos, &strict).is_ok());
+    }
+
+    #[test]
+    fn test_decode_goose_pdu_typed() {
+        // Entry 8 becomes 0x85 04 ff ff ff ff, -1 by its tag and 4294967295 as INT32U
+        let mut buf = ALL_FIELDS_FRAME.to_vec();
+        let entry = buf.windows(3).position(|w| w == [133, 4, 127]).unwrap();
+        buf[entry + 2] = 255;
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, &buf);
+        assert_eq!(
+            decode_goose_pdu(&buf, pos).unwrap().all_data[8],
+            IECData::Int(-1)
+        );
+
+        let mut entries = vec![DataType::UInt; 7];
+        entries.extend([
+            DataType::Boolean,
+            DataType::UInt,
+            DataType::Int,
+            DataType::VisibleString,
+        ]);
+        let template = DataTemplate { entries };
+        let pdu = decode_goose_pdu_typed(&buf, pos, &template).unwrap();
+        assert_eq!(pdu.all_data[1], IECData::UInt(0x80));
+        assert_eq!(pdu.all_data[7], IECData::Boolean(true));
+        assert_eq!(pdu.all_data[8], IECData::UInt(4294967295));
+        assert_eq!(pdu.all_data[9], IECData::Int(2147483648));
+        assert_eq!(pdu.all_data[10], IECData::VisibleString("test".to_string()));
+
+        // A tag 0x86 entry declared as signed
+        let mut signed = template.clone();
+        signed.entries[2] = DataType::Int;
+        let pdu = decode_goose_pdu_typed(&buf, pos, &signed).unwrap();
+        assert_eq!(pdu.all_data[2], IECData::Int(255));
+
+        // Arity and type mismatches
+        let mut short = template.clone();
+        short.entries.pop();
+        let err = decode_goose_pdu_typed(&buf, pos, &short).unwrap_err();
+        assert!(
+            err.message.contains("numDatSetEntries is 11"),
+            "{}",
+            err.message
+        );
+        let mut wrong = template.clone();
+        wrong.entries[10] = DataType::Boolean;
+        let err = decode_goose_pdu_typed(&buf, pos, &wrong).unwrap_err();
+        assert!(
+            err.message.contains("found VisibleString"),
+            "{}",
+            err.message
+        );
+        let mut wrong = template;
+        wrong.entries[7] = DataType::Int;
+        assert!(decode_goose_pdu_typed(&buf, pos, &wrong).is_err());
+    }
+
+    #[test]
+    fn test_decode_goose_pdu_with_padding() {
+        let mut buf = vec![
+            1, 12, 205, 1, 0, 1, 0, 26, 182, 3, 47, 28, 136, 184, 16, 1, 0, 90, 0, 0, 0, 0, 97, 80,
+            128, 17, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 71, 79, 36, 103, 99, 98, 49, 129, 2,
+            7, 208, 130, 13, 73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 68, 83, 49, 131, 6, 71, 79,
+            79, 83, 69, 49, 132, 8, 32, 33, 6, 18, 10, 48, 0, 0, 133, 1, 1, 134, 1, 42, 135, 1, 0,
+            136, 2, 0, 128, 137, 1, 0, 138, 1, 1, 171, 3, 131, 1, 255,
+        ];
+        let pdu_length = buf.len() - 22;
+        buf.extend_from_slice(&[0; 6]);
+
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, &buf);
+        let (goose_pdu, consumed, warnings) = decode_goose_pdu_with_warnings(&buf, pos).unwrap();
+
+        assert_eq!(consumed, pdu_length);
+        assert_eq!(goose_pdu.go_id, "GOOSE1");
+        assert_eq!(goose_pdu.all_data, vec![IECData::Boolean(true)]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].buffer_index, pos + pdu_length);
+        assert_eq!(decode_goose_pdu(&buf, pos).unwrap(), goose_pdu);
+    }
+
+    #[test]
+    fn test_decode_goose_pdu_st_num_with_leading_zeros() {
+        let buf: &[u8] = &[
+            1, 12, 205, 1, 0, 1, 0, 26, 182, 3, 47, 28, 136, 184, // Ethernet header
+            16, 1, 0, 96, 0, 0, 0, 0, // APPID, length, reserved
+            97, 86, // goosePdu
+            128, 17, // gocbRef
+            73, 69, 68, 49, 47, 76, 76, 78, 48, // IED1/LLN0
+            36, 71, 79, 36, 103, 99, 98, 49, // $GO$gcb1
+            129, 2, 7, 208, // timeAllowedtoLive
+            130, 13, // datSet
+            73, 69, 68, 49, 47, 76, 76, 78, 48, 36, 68, 83, 49, // IED1/LLN0$DS1
+            131, 6, 71, 79, 79, 83, 69, 49, // goID
+            132, 8, 32, 33, 6, 18, 10, 48, 0, 0, // t
+            133, 3, 0, 0, 5, // stNum 5 with two leading 0x00
+            134, 5, 0, 255, 255, 255, 255, // sqNum 0xFFFFFFFF
+            135, 1, 0, // simulation
+            136, 2, 0, 128, // confRev
+            137, 1, 0, // ndsCom
+            138, 1, 1, // numDatSetEntries
+            171, 3, 131, 1, 255, // allData
+        ];
+
+        let mut header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut header, buf);
+        let goose_pdu = decode_goose_pdu(buf, pos).unwrap();
+
+        assert_eq!(goose_pdu.st_num, 5);
+        assert_eq!(goose_pdu.sq_num, u32::MAX);
+        assert_eq!(goose_pdu.conf_rev, 128);
+    }
+
+    #[test]
+    fn test_expected_trailer() {
+        assert!(is_expected_trailer(100, 0));
+        assert!(is_expected_trailer(100, FCS_LENGTH));
+        // Padded to the minimum frame length, with and without FCS
+        assert!(is_expected_trailer(60, 10));
+        assert!(is_expected_trailer(64, 14));
+        assert!(!is_expected_trailer(106, 6));
+        assert!(!is_expected_trailer(70, 10));
+    }
+
+    #[test]
+    fn test_goose_appid() {
+        // VLAN tagged, APPID 0x1001 at 18-19
+        assert_eq!(goose_appid(ALL_FIELDS_FRAME), Some(0x1001));
+
+        let mut buf = [0u8; 60];
+        buf[12..16].copy_from_slice(&[0x88, 0xb8, 0x30, 0x02]);
+        assert_eq!(goose_appid(&buf), Some(0x3002));
+        assert_eq!(goose_appid(&buf[..15]), None);
+
+        buf[12..14].copy_from_slice(&[0x88, 0xba]);
+        assert_eq!(goose_appid(&buf), None);
+    }
+
     #[test]
     fn test_is_goose_frame() {
         // GOOSE EtherType without VLAN tag (0x88b8 at bytes 12-13)
@@ -1,305 +1,313 @@
-use crate::types::{DecodeError, SavAsdu, SavPdu};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ber::{
+    decode_octet_string, decode_str, decode_string, decode_tag, decode_tag_length,
+    decode_unsigned_16, decode_unsigned_32, decode_unsigned_8, decompress_integer,
+    is_minimal_integer,
+};
+use crate::constants::{ETHERTYPE_SMV, MIN_FRAME_LENGTH};
+use crate::decode_basics::{decode_appid, decode_sim_bit};
+use crate::encode_smv::{FLOAT32_EXPONENT_WIDTH, FLOAT32_LENGTH};
+use crate::types::{
+    DecodeError, DecodeLimits, DecodeOptions, DecodeStats, DecodeWarning, EthernetHeader, Quality,
+    Sample, SampleValue, SavAsdu, SavPdu, SecurityInfo,
+};
+
+/// Options and the warnings collected while decoding a single frame
+struct DecodeContext<'a> {
+    options: &'a DecodeOptions,
+    warnings: Vec<DecodeWarning>,
+    stats: Option<&'a mut DecodeStats>,
+}
+
+impl<'a> DecodeContext<'a> {
+    fn new(options: &'a DecodeOptions) -> Self {
+        DecodeContext {
+            options,
+            warnings: Vec::new(),
+            stats: None,
+        }
+    }
+
+    /// Decodes a tag and length like `decode_tag_length`, counting them if statistics
+    /// are collected
+    fn tag_length(
+        &mut self,
+        tag: &mut u8,
+        length: &mut usize,
+        buffer: &[u8],
+        buffer_index: usize,
+    ) -> Result<usize, DecodeError> {
+        let next_pos = decode_tag_length(tag, length, buffer, buffer_index)?;
+        if let Some(stats) = self.stats.as_deref_mut() {
+            // The initial length octet is followed by the long form length octets
+            let (_, _, length_pos) = decode_tag(buffer, buffer_index)?;
+            stats.record(*tag, next_pos - length_pos - 1);
+        }
+        Ok(next_pos)
+    }
+
+    /// In strict mode, rejects an INTEGER whose content octets are not minimal
+    fn check_integer(
+        &self,
+        name: &str,
+        buffer: &[u8],
+        buffer_index: usize,
+        length: usize,
+    ) -> Result<(), DecodeError> {
+        if !self.options.is_strict() {
+            return Ok(());
+        }
+        match buffer.get(buffer_index..buffer_index + length) {
+            Some(content) if !is_minimal_integer(content) => Err(DecodeError::new(
+                &format!("{} is not minimally encoded: {:02x?}", name, content),
+                buffer_index,
+            )),
+            _ => Ok(()),
+        }
+    }
 
-/// Decodes an octet string (raw bytes) from the buffer at the specified position and length.
+    /// Reports a length field that does not match its content: an error in strict mode,
+    /// a warning otherwise
+    fn length_mismatch(&mut self, message: &str, buffer_index: usize) -> Result<(), DecodeError> {
+        if self.options.is_strict() {
+            return Err(DecodeError::new(message, buffer_index));
+        }
+        self.warnings
+            .push(DecodeWarning::new(message, buffer_index));
+        Ok(())
+    }
+}
+
+/// Length of the link header after the EtherType: APPID, length, reserved 1 and 2
+const LINK_HEADER_LENGTH: usize = 8;
+/// Number of bits of the quality BIT STRING of a sample
+const QUALITY_BITS: usize = 13;
+/// Number of bytes holding the quality bits, without the unused bits byte
+const QUALITY_LENGTH: usize = 2;
+/// Maximum number of non-standard bytes between the link header and the savPdu
+const MAX_EXTRA_HEADER_BYTES: usize = 8;
+
+/// Locates the savPdu tag (0x60) in a frame whose link header may be followed by
+/// additional vendor specific reserved bytes.
+///
+/// The tag is searched within a small window starting right after the regular
+/// 8-byte link header, so frames with the standard layout yield the same position
+/// as `decode_ethernet_header`.
 ///
 /// # Parameters
-/// - `val`: A mutable reference where the decoded bytes will be stored.
-/// - `buffer`: The input byte slice containing the encoded data.
-/// - `buffer_index`: The starting position in the buffer to read the octet string from.
-/// - `length`: The number of bytes to read for the octet string.
+/// - `buffer`: The complete Ethernet frame.
 ///
 /// # Returns
-/// The next position in the buffer after reading the octet string.
-///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
-fn decode_octet_string(
-    val: &mut [u8],
-    buffer: &[u8],
-    buffer_index: usize,
-    length: usize,
-) -> Result<usize, DecodeError> {
-    if buffer_index + length > buffer.len() {
-        return Err(DecodeError::new(
-            &format!(
-                "Attempt to read {} bytes exceeds buffer length {}",
-                length,
-                buffer.len()
-            ),
-            buffer_index,
-        ));
+/// The position of the savPdu tag, to be passed to `decode_smv`.
+pub fn find_sav_pdu(buffer: &[u8]) -> Result<usize, DecodeError> {
+    let mut pos = 12;
+    if buffer.len() >= pos + 2 && buffer[pos..pos + 2] == [0x81, 0x00] {
+        pos += 4;
     }
-    val[0..length].copy_from_slice(&buffer[buffer_index..buffer_index + length]);
-    Ok(buffer_index + length)
+    // EtherType and link header
+    pos += 2 + LINK_HEADER_LENGTH;
+
+    let window_end = (pos + MAX_EXTRA_HEADER_BYTES + 1).min(buffer.len());
+    buffer
+        .get(pos..window_end)
+        .and_then(|window| window.iter().position(|&byte| byte == 0x60))
+        .map(|offset| pos + offset)
+        .ok_or_else(|| {
+            DecodeError::new(
+                &format!(
+                    "No savPdu tag 0x60 within {} bytes after the link header",
+                    MAX_EXTRA_HEADER_BYTES
+                ),
+                pos,
+            )
+        })
 }
 
-/// Decodes an ASN.1 BER encoded 8-bit unsigned integer from the buffer at the specified position and length.
+/// Maximum number of constructed elements wrapping the savPdu that are skipped
+const MAX_WRAPPER_DEPTH: usize = 4;
+
+/// Skips the savPdu tag (0x60) and its length, descending into outer constructed
+/// elements first if the savPdu is wrapped, as in tunneled or 90-5 encodings.
 ///
 /// # Parameters
-/// - `val`: A mutable reference where the decoded u16 will be stored.
-/// - `buffer`: The input byte slice containing the encoded integer.
-/// - `buffer_index`: The starting position in the buffer to read the integer from.
-/// - `length`: The number of bytes used for the encoded integer in the buffer.
+/// - `buffer`: The input byte slice containing the encoded SMV PDU.
+/// - `buffer_index`: The position of the first tag after the Ethernet header.
 ///
 /// # Returns
-/// The next position in the buffer after reading the integer.
-///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
-fn decode_unsigned_8(
-    val: &mut u8,
+/// The position of the first element inside the savPdu.
+fn skip_to_sav_pdu(
     buffer: &[u8],
     buffer_index: usize,
-    length: usize,
+    ctx: &mut DecodeContext,
 ) -> Result<usize, DecodeError> {
-    let mut value_bytes = [0u8; 1];
-    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
-    *val = u8::from_be_bytes(value_bytes);
-    Ok(buffer_index + length)
-}
-
-/// Decompresses an ASN.1 BER encoded integer from the buffer into the provided value slice,
-/// restoring it to its full width (e.g., i32, i64) with correct sign extension.
-///
-/// # Parameters
-/// - `value`: The output buffer (e.g., 4 or 8 bytes) to store the decompressed integer (big-endian).
-/// - `buffer`: The input byte slice containing the encoded integer.
-/// - `buffer_index`: The starting position in the buffer to read the integer from.
-/// - `length`: The number of bytes used for the encoded integer in the buffer.
-///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length,
-/// or if `length` is greater than `value.len()`.
-fn decompress_integer(
-    value: &mut [u8],
-    buffer: &[u8],
-    buffer_index: usize,
-    length: usize,
-) -> Result<(), DecodeError> {
-    if buffer_index + length > buffer.len() {
-        return Err(DecodeError::new(
-            &format!(
-                "Attempt to read {} bytes exceeds buffer length {}",
-                length,
-                buffer.len()
-            ),
-            buffer_index,
-        ));
-    }
-
-    // Handle unsigned integers with leading 0x00 byte (for MSB set prevention)
-    // If the encoded value has a leading 0x00 and the next byte has MSB set,
-    // and the length is exactly one more than our target size, skip the leading 0x00
-    let (actual_start, actual_length) = if length == value.len() + 1
-        && length >= 2
-        && buffer[buffer_index] == 0x00
-        && (buffer[buffer_index + 1] & 0x80) != 0
-    {
-        // Skip the leading 0x00 byte for unsigned integers
-        (buffer_index + 1, length - 1)
-    } else if length > value.len() {
-        return Err(DecodeError::new(
-            &format!(
-                "Mismatch value length {} vs buffer length {}",
-                value.len(),
-                length
-            ),
-            buffer_index,
-        ));
-    } else {
-        (buffer_index, length)
-    };
-
-    // Determine fill byte for sign extension (0xFF for negative, 0x00 for positive)
-    let fill = if buffer[actual_start] & 0x80 == 0x80 {
-        0xFF
-    } else {
-        0x00
-    };
+    let mut pos = buffer_index;
+    let mut tag = 0u8;
+    let mut length = 0usize;
 
-    // Fill the leading bytes with the sign extension
-    let fill_length = value.len() - actual_length;
-    for item in value.iter_mut().take(fill_length) {
-        *item = fill;
+    for _ in 0..=MAX_WRAPPER_DEPTH {
+        let next_pos = ctx.tag_length(&mut tag, &mut length, buffer, pos)?;
+        if tag == 0x60 {
+            return Ok(next_pos);
+        }
+        // Only constructed elements can contain the savPdu
+        if tag & 0x20 == 0 {
+            return Err(DecodeError::new(
+                &format!("Expected savPdu tag 0x60, got 0x{:02x}", tag),
+                pos,
+            ));
+        }
+        pos = next_pos;
     }
 
-    // Copy the encoded integer bytes into the lower part of the output buffer
-    value[fill_length..].copy_from_slice(&buffer[actual_start..actual_start + actual_length]);
-    Ok(())
+    Err(DecodeError::new(
+        &format!(
+            "No savPdu tag 0x60 within {} wrapping elements",
+            MAX_WRAPPER_DEPTH
+        ),
+        buffer_index,
+    ))
 }
 
-/// Decodes an ASN.1 BER encoded 32-bit unsigned integer from the buffer at the specified position and length.
+/// Decodes a SMV PDU from the buffer at the specified position.
+///
+/// The simulation flag is read from the reserved 1 field of the Ethernet header, so
+/// `buffer` must hold the complete frame.
 ///
 /// # Parameters
-/// - `val`: A mutable reference where the decoded u32 will be stored.
-/// - `buffer`: The input byte slice containing the encoded integer.
-/// - `buffer_index`: The starting position in the buffer to read the integer from.
-/// - `length`: The number of bytes used for the encoded integer in the buffer.
+/// - `buffer`: The input byte slice containing the complete SMV frame.
+/// - `pos`: The position of the savPdu in the buffer.
 ///
 /// # Returns
-/// The next position in the buffer after reading the integer.
-///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
-fn decode_unsigned_32(
-    val: &mut u32,
-    buffer: &[u8],
-    buffer_index: usize,
-    length: usize,
-) -> Result<usize, DecodeError> {
-    let mut value_bytes = [0u8; 4];
-    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
-    *val = u32::from_be_bytes(value_bytes);
-    Ok(buffer_index + length)
+/// The decoded SavPdu with the simulation flag, number of ASDUs, security field and
+/// all ASDUs, or a DecodeError if the frame is malformed.
+pub fn decode_smv(buffer: &[u8], pos: usize) -> Result<SavPdu, DecodeError> {
+    decode_smv_with_limits(buffer, pos, &DecodeLimits::default())
 }
 
-/// Decodes an ASN.1 BER encoded 16-bit unsigned integer from the buffer at the specified position and length.
+/// Decodes a SMV PDU like `decode_smv`, bounding the work spent on the frame by `limits`.
 ///
 /// # Parameters
-/// - `val`: A mutable reference where the decoded u16 will be stored.
-/// - `buffer`: The input byte slice containing the encoded integer.
-/// - `buffer_index`: The starting position in the buffer to read the integer from.
-/// - `length`: The number of bytes used for the encoded integer in the buffer.
+/// - `buffer`: The input byte slice containing the encoded SMV PDU.
+/// - `pos`: The starting position in the buffer to read from.
+/// - `limits`: The limits the decoded frame must stay within.
 ///
 /// # Returns
-/// The next position in the buffer after reading the integer.
-///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
-fn decode_unsigned_16(
-    val: &mut u16,
+/// The decoded SavPdu, or a DecodeError if the frame is malformed or exceeds a limit.
+pub fn decode_smv_with_limits(
     buffer: &[u8],
-    buffer_index: usize,
-    length: usize,
-) -> Result<usize, DecodeError> {
-    let mut value_bytes = [0u8; 2];
-    decompress_integer(&mut value_bytes, buffer, buffer_index, length)?;
-    *val = u16::from_be_bytes(value_bytes);
-    Ok(buffer_index + length)
+    pos: usize,
+    limits: &DecodeLimits,
+) -> Result<SavPdu, DecodeError> {
+    let options = DecodeOptions {
+        limits: *limits,
+        ..Default::default()
+    };
+    let (pdu, _) = decode_smv_with_options(buffer, pos, &options)?;
+    Ok(pdu)
 }
 
-/// Decodes a UTF-8 string from the buffer at the specified position and length.
+/// Decodes a SMV PDU like `decode_smv` with the given decode options.
+///
+/// A sample block or ASDU whose length field disagrees with its content, and bytes after
+/// the last ASDU in the sequence of ASDU, are reported as warning. In
+/// `DecodeMode::Lenient` a sample block is additionally re-synchronized on the next
+/// valid tag, and ASDUs with a tag other than 0x30 are accepted with a warning.
+///
+/// In `DecodeMode::Strict` these length mismatches and encodings that `decode_smv`
+/// tolerates are rejected: INTEGERs that are not minimally encoded and a quality BIT
+/// STRING whose unused bits do not leave exactly 13 bits.
 ///
 /// # Parameters
-/// - `val`: A mutable reference where the decoded string will be stored.
-/// - `buffer`: The input byte slice containing the encoded data.
-/// - `buffer_index`: The starting position in the buffer to read the string from.
-/// - `length`: The number of bytes to read for the string.
+/// - `buffer`: The input byte slice containing the encoded SMV PDU.
+/// - `pos`: The starting position in the buffer to read from.
+/// - `options`: The limits and tolerance to apply.
 ///
 /// # Returns
-/// The next position in the buffer after reading the string.
+/// The decoded SavPdu together with the warnings raised while decoding it.
+pub fn decode_smv_with_options(
+    buffer: &[u8],
+    pos: usize,
+    options: &DecodeOptions,
+) -> Result<(SavPdu, Vec<DecodeWarning>), DecodeError> {
+    let mut ctx = DecodeContext::new(options);
+    let pdu = decode_sav_pdu(buffer, pos, &mut ctx)?;
+    Ok((pdu, ctx.warnings))
+}
+
+/// Decodes a SMV PDU like `decode_smv` and interprets its security field.
 ///
-/// # Panics
-/// Panics if the requested range (buffer_index..buffer_index+length) exceeds the buffer length.
-fn decode_string(
-    val: &mut String,
+/// # Returns
+/// The decoded SavPdu, with the security field as raw bytes, and the signature if the
+/// field follows the layout of `SecurityInfo::parse`. Frames without security field or
+/// with an unknown format yield `None`.
+pub fn decode_smv_with_security(
     buffer: &[u8],
-    buffer_index: usize,
-    length: usize,
-) -> Result<usize, DecodeError> {
-    if buffer_index + length > buffer.len() {
-        return Err(DecodeError::new(
-            &format!(
-                "Attempt to read {} bytes exceeds buffer length {}",
-                length,
-                buffer.len()
-            ),
-            buffer_index,
-        ));
-    }
-    *val = String::from_utf8_lossy(&buffer[buffer_index..buffer_index + length]).to_string();
-    Ok(buffer_index + length)
+    pos: usize,
+) -> Result<(SavPdu, Option<SecurityInfo>), DecodeError> {
+    let pdu = decode_smv(buffer, pos)?;
+    let security = pdu.security_info();
+    Ok((pdu, security))
 }
 
-/// Decodes an ASN.1 BER tag and length field from the buffer at the specified position,
-/// writing the results into the provided mutable references.
+/// Decodes a SMV PDU like `decode_smv`, counting every decoded tag and length form
+/// in `stats`.
 ///
-/// This function supports definite-length encoding with up to 3 length bytes (sufficient for most practical uses).
+/// Statistics accumulate over calls, so a single `DecodeStats` can profile a whole
+/// capture.
 ///
 /// # Parameters
-/// - `tag`: Mutable reference to store the decoded tag (`u8`).
-/// - `length`: Mutable reference to store the decoded length (`usize`).
-/// - `buffer`: The input byte slice containing the encoded tag and length.
-/// - `buffer_index`: The starting position in the buffer to read the tag and length from.
+/// - `buffer`: The input byte slice containing the encoded SMV PDU.
+/// - `pos`: The starting position in the buffer to read from.
+/// - `stats`: The collector to add the observed tags and length forms to.
 ///
 /// # Returns
-/// The next position in the buffer after reading the tag and length.
-///
-/// # Panics
-/// Panics if the buffer does not contain enough bytes to decode the tag and length.
-fn decode_tag_length(
-    tag: &mut u8,
-    length: &mut usize,
+/// The decoded SavPdu, or a DecodeError if the frame is malformed.
+pub fn decode_smv_with_stats(
     buffer: &[u8],
-    buffer_index: usize,
-) -> Result<usize, DecodeError> {
-    if buffer_index >= buffer.len() {
-        return Err(DecodeError::new(
-            &format!("Out of bounds for buffer length {}", buffer.len()),
-            buffer_index,
-        ));
-    }
-
-    *tag = buffer[buffer_index];
-    let mut pos = buffer_index + 1;
-
-    if pos >= buffer.len() {
-        return Err(DecodeError::new(
-            "Decode tag length: missing length byte ",
-            buffer_index,
-        ));
-    }
+    pos: usize,
+    stats: &mut DecodeStats,
+) -> Result<SavPdu, DecodeError> {
+    let options = DecodeOptions::default();
+    let mut ctx = DecodeContext::new(&options);
+    ctx.stats = Some(stats);
+    decode_sav_pdu(buffer, pos, &mut ctx)
+}
 
-    let first_len_byte = buffer[pos];
-    pos += 1;
+fn decode_sav_pdu(
+    buffer: &[u8],
+    pos: usize,
+    ctx: &mut DecodeContext,
+) -> Result<SavPdu, DecodeError> {
+    let mut pdu = SavPdu::default();
+    let (new_pos, sequence_end) = decode_sav_pdu_header(&mut pdu, buffer, pos, ctx)?;
 
-    *length = if first_len_byte & 0x80 == 0 {
-        // Short form: single byte length (0..127)
-        first_len_byte as usize
-    } else {
-        // Long form: lower 7 bits indicate number of length bytes
-        let num_len_bytes = (first_len_byte & 0x7F) as usize;
-        if num_len_bytes == 0 || num_len_bytes > 3 {
-            return Err(DecodeError::new(
-                &format!(
-                    "Decode tag length: unsupported or invalid number of length bytes: {}",
-                    num_len_bytes
-                ),
-                buffer_index,
-            ));
-        }
-        if pos + num_len_bytes > buffer.len() {
-            return Err(DecodeError::new(
-                &format!(
-                    "Decode tag length: not enough bytes for {}-byte length at position {}",
-                    num_len_bytes, pos
-                ),
-                buffer_index,
-            ));
-        }
-        let mut len = 0usize;
-        for _ in 0..num_len_bytes {
-            len = (len << 8) | buffer[pos] as usize;
-            pos += 1;
-        }
-        len
-    };
+    pdu.sav_asdu.clear();
+    decode_smv_asdus(
+        &mut pdu.sav_asdu,
+        buffer,
+        new_pos,
+        sequence_end,
+        pdu.no_asdu,
+        ctx,
+    )?;
 
-    Ok(pos)
+    Ok(pdu)
 }
 
-/// Decodes a GOOSE PDU from the buffer at the specified position,
-/// writing the result into the provided mutable reference.
-///
-/// # Parameters
-/// - `pdu`: A mutable reference where the decoded IECGoosePdu will be stored.
-/// - `buffer`: The input byte slice containing the encoded GOOSE PDU.
-/// - `pos`: The starting position in the buffer to read from.
+/// Decodes the fields of the savPdu preceding the ASDUs into `pdu`.
 ///
 /// # Returns
-/// The new buffer position after decoding the PDU.
-pub fn decode_smv(buffer: &[u8], pos: usize) -> Result<SavPdu, DecodeError> {
-    let mut pdu = SavPdu::default();
+/// The position of the first ASDU and the end of the sequence of ASDU.
+fn decode_sav_pdu_header(
+    pdu: &mut SavPdu,
+    buffer: &[u8],
+    pos: usize,
+    ctx: &mut DecodeContext,
+) -> Result<(usize, usize), DecodeError> {
     let mut new_pos = pos;
 
     // decode simulation bit that is encoded into the first bit of reserved 1 field (see decode ethernet)
@@ -308,17 +316,17 @@ pub fn decode_smv(buffer: &[u8], pos: usize) -> Result<SavPdu, DecodeError> {
     // Jump over the length tag of the SAV PDU
     let mut _tag = 0u8;
     let mut _length = 0usize;
-    new_pos = decode_tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
+    new_pos = skip_to_sav_pdu(buffer, new_pos, ctx)?;
 
     // Number of ASDUs in the packet
-    new_pos = decode_tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
+    ctx.check_integer("noASDU", buffer, new_pos, _length)?;
     new_pos = decode_unsigned_16(&mut pdu.no_asdu, buffer, new_pos, _length)?;
 
     // Optional field security (ANY OPTIONAL - reserved for future use)
-    let tag = buffer[new_pos];
-    if tag == 0x81 {
+    if peek_tag(buffer, new_pos)? == 0x81 {
         let mut _length = 0usize;
-        new_pos = decode_tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
+        new_pos = ctx.tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
         let mut sec_buf = vec![0u8; _length];
         new_pos = decode_octet_string(&mut sec_buf, buffer, new_pos, _length)?;
         pdu.security = Some(sec_buf);
@@ -328,12 +336,329 @@ pub fn decode_smv(buffer: &[u8], pos: usize) -> Result<SavPdu, DecodeError> {
 
     // sequence of ASDU
     let mut length = 0usize;
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
 
-    pdu.sav_asdu.clear();
-    decode_smv_asdus(&mut pdu.sav_asdu, buffer, new_pos, pdu.no_asdu)?;
+    Ok((new_pos, new_pos + length))
+}
 
-    Ok(pdu)
+/// Decodes only the ASDU with index `n` of a SMV frame, skipping the ASDUs before it
+/// without decoding their content.
+///
+/// # Parameters
+/// - `buffer`: The input byte slice containing the complete SMV frame.
+/// - `pos`: The position of the savPdu in the buffer.
+/// - `n`: The zero based index of the ASDU to decode.
+///
+/// # Returns
+/// The decoded ASDU, or a DecodeError if the frame is malformed or has no ASDU `n`.
+pub fn decode_smv_nth_asdu(buffer: &[u8], pos: usize, n: usize) -> Result<SavAsdu, DecodeError> {
+    let options = DecodeOptions::default();
+    let mut ctx = DecodeContext::new(&options);
+    let mut pdu = SavPdu::default();
+    let (mut new_pos, _) = decode_sav_pdu_header(&mut pdu, buffer, pos, &mut ctx)?;
+
+    if n >= pdu.no_asdu as usize {
+        return Err(DecodeError::new(
+            &format!("ASDU {} requested, frame has {} ASDUs", n, pdu.no_asdu),
+            new_pos,
+        ));
+    }
+
+    for _ in 0..n {
+        new_pos = skip_element(buffer, new_pos)?;
+    }
+
+    let mut tag = 0u8;
+    let mut _length = 0usize;
+    let asdu_pos = new_pos;
+    new_pos = ctx.tag_length(&mut tag, &mut _length, buffer, new_pos)?;
+    check_asdu_tag(tag, asdu_pos, &mut ctx)?;
+    let (_, asdu) = decode_smv_asdu(buffer, new_pos, &mut ctx)?;
+
+    Ok(asdu)
+}
+
+/// Lazily decodes the samples of a seqData block, borrowing the frame instead of
+/// collecting the samples into a `Vec`.
+///
+/// Each item is the next sample, or the DecodeError of a malformed sample after which
+/// the iteration ends.
+#[derive(Debug, Clone)]
+pub struct SampleIter<'a> {
+    buffer: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> SampleIter<'a> {
+    /// Creates an iterator over the samples encoded in `buffer[pos..pos + length]`, the
+    /// content of a seqData element.
+    pub fn new(buffer: &'a [u8], pos: usize, length: usize) -> Self {
+        SampleIter {
+            buffer,
+            pos,
+            end: (pos + length).min(buffer.len()),
+        }
+    }
+}
+
+impl Iterator for SampleIter<'_> {
+    type Item = Result<Sample, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        let options = DecodeOptions::default();
+        let mut ctx = DecodeContext::new(&options);
+        // Samples must not extend past the seqData element
+        match decode_sample(&self.buffer[..self.end], self.pos, &mut ctx) {
+            Ok((next_pos, sample)) => {
+                self.pos = next_pos;
+                Some(Ok(sample))
+            }
+            Err(err) => {
+                self.pos = self.end;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// An ASDU decoded without allocating: svID and data set reference borrow from the
+/// frame and the samples are decoded on demand.
+///
+/// Subscribers that filter on the svID compare `msv_id` directly and only build an
+/// owned `SavAsdu` with `into_owned` for the ASDUs they keep.
+#[derive(Debug, Clone)]
+pub struct SavAsduView<'a> {
+    /** Multicast Sampled Values ID, borrowed from the frame */
+    pub msv_id: &'a str,
+    /** Reference to the data set, borrowed from the frame */
+    pub dat_set: Option<&'a str>,
+    pub smp_cnt: u16,
+    pub conf_rev: u32,
+    pub refr_tm: Option<[u8; 8]>,
+    pub smp_synch: u8,
+    pub smp_rate: Option<u16>,
+    /** The samples, decoded while iterating */
+    pub samples: SampleIter<'a>,
+    pub smp_mod: Option<u16>,
+    pub gm_identity: Option<[u8; 8]>,
+}
+
+impl SavAsduView<'_> {
+    /// Copies the strings and decodes the samples into an owned `SavAsdu`.
+    ///
+    /// # Returns
+    /// The owned ASDU, or the DecodeError of the first malformed sample.
+    pub fn into_owned(self) -> Result<SavAsdu, DecodeError> {
+        Ok(SavAsdu {
+            msv_id: self.msv_id.to_string(),
+            dat_set: self.dat_set.map(str::to_string),
+            smp_cnt: self.smp_cnt,
+            conf_rev: self.conf_rev,
+            refr_tm: self.refr_tm,
+            smp_synch: self.smp_synch,
+            smp_rate: self.smp_rate,
+            all_data: self.samples.collect::<Result<_, _>>()?,
+            smp_mod: self.smp_mod,
+            gm_identity: self.gm_identity,
+        })
+    }
+}
+
+/// Decodes the ASDU with index `n` of a SMV frame into a `SavAsduView`, skipping the
+/// ASDUs before it. Nothing is allocated; unlike `decode_smv` a svID that is not valid
+/// UTF-8 is an error.
+///
+/// # Parameters
+/// - `buffer`: The input byte slice containing the complete SMV frame.
+/// - `pos`: The position of the savPdu in the buffer.
+/// - `n`: The zero based index of the ASDU to decode.
+///
+/// # Returns
+/// The view of the ASDU, or a DecodeError if the frame is malformed or has no ASDU `n`.
+pub fn decode_smv_nth_asdu_view(
+    buffer: &[u8],
+    pos: usize,
+    n: usize,
+) -> Result<SavAsduView<'_>, DecodeError> {
+    let options = DecodeOptions::default();
+    let mut ctx = DecodeContext::new(&options);
+    let mut pdu = SavPdu::default();
+    let (mut new_pos, _) = decode_sav_pdu_header(&mut pdu, buffer, pos, &mut ctx)?;
+
+    if n >= pdu.no_asdu as usize {
+        return Err(DecodeError::new(
+            &format!("ASDU {} requested, frame has {} ASDUs", n, pdu.no_asdu),
+            new_pos,
+        ));
+    }
+
+    for _ in 0..n {
+        new_pos = skip_element(buffer, new_pos)?;
+    }
+
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let asdu_pos = new_pos;
+    new_pos = ctx.tag_length(&mut tag, &mut length, buffer, new_pos)?;
+    check_asdu_tag(tag, asdu_pos, &mut ctx)?;
+    let asdu_end = skip_element(buffer, asdu_pos)?;
+
+    // Fields must not extend past the ASDU
+    decode_asdu_view(&buffer[..asdu_end], new_pos)
+}
+
+/// Decodes the fields of an ASDU like `decode_smv_asdu`, borrowing strings and samples
+fn decode_asdu_view(buffer: &[u8], start_pos: usize) -> Result<SavAsduView<'_>, DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+
+    // sampled value ID
+    let mut msv_id = "";
+    let mut new_pos = decode_tag_length(&mut tag, &mut length, buffer, start_pos)?;
+    new_pos = decode_str(&mut msv_id, buffer, new_pos, length)?;
+
+    // Optional data set reference
+    let mut dat_set = None;
+    if peek_tag(buffer, new_pos)? == 0x81 {
+        let mut dat_set_str = "";
+        new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        new_pos = decode_str(&mut dat_set_str, buffer, new_pos, length)?;
+        dat_set = Some(dat_set_str);
+    }
+
+    let mut smp_cnt = 0u16;
+    new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+    new_pos = decode_unsigned_16(&mut smp_cnt, buffer, new_pos, length)?;
+
+    let mut conf_rev = 0u32;
+    new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+    new_pos = decode_unsigned_32(&mut conf_rev, buffer, new_pos, length)?;
+
+    let mut refr_tm = None;
+    if peek_tag(buffer, new_pos)? == 0x84 {
+        new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        check_fixed_length("refrTm", length, 8, new_pos)?;
+        let mut refr_tm_arr = [0u8; 8];
+        new_pos = decode_octet_string(&mut refr_tm_arr, buffer, new_pos, length)?;
+        refr_tm = Some(refr_tm_arr);
+    }
+
+    let mut smp_synch = 0u8;
+    new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+    new_pos = decode_unsigned_8(&mut smp_synch, buffer, new_pos, length)?;
+
+    let mut smp_rate = None;
+    if peek_tag(buffer, new_pos)? == 0x86 {
+        let mut smp_rate_num = 0u16;
+        new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        new_pos = decode_unsigned_16(&mut smp_rate_num, buffer, new_pos, length)?;
+        smp_rate = Some(smp_rate_num);
+    }
+
+    // Data content, decoded by the iterator
+    new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+    if tag != 0x87 || new_pos + length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Expected seqData tag 0x87 within the ASDU, got 0x{:02x}",
+                tag
+            ),
+            new_pos,
+        ));
+    }
+    let samples = SampleIter::new(buffer, new_pos, length);
+    new_pos += length;
+
+    let mut smp_mod = None;
+    if buffer.get(new_pos) == Some(&0x88) {
+        let mut smp_mod_num = 0u16;
+        new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        new_pos = decode_unsigned_16(&mut smp_mod_num, buffer, new_pos, length)?;
+        smp_mod = Some(smp_mod_num);
+    }
+
+    let mut gm_identity = None;
+    if buffer.get(new_pos) == Some(&0x89) {
+        new_pos = decode_tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        check_fixed_length("gmIdentity", length, 8, new_pos)?;
+        let mut gm_identity_oct = [0u8; 8];
+        decode_octet_string(&mut gm_identity_oct, buffer, new_pos, length)?;
+        gm_identity = Some(gm_identity_oct);
+    }
+
+    Ok(SavAsduView {
+        msv_id,
+        dat_set,
+        smp_cnt,
+        conf_rev,
+        refr_tm,
+        smp_synch,
+        smp_rate,
+        samples,
+        smp_mod,
+        gm_identity,
+    })
+}
+
+/// Locates the samples of the ASDU with index `n` of a SMV frame without decoding
+/// them. The returned iterator decodes one sample per step and allocates nothing.
+///
+/// # Parameters
+/// - `buffer`: The input byte slice containing the complete SMV frame.
+/// - `pos`: The position of the savPdu in the buffer.
+/// - `n`: The zero based index of the ASDU.
+///
+/// # Returns
+/// The iterator over the samples of the ASDU, or a DecodeError if the frame is
+/// malformed or has no ASDU `n`.
+pub fn smv_samples(buffer: &[u8], pos: usize, n: usize) -> Result<SampleIter<'_>, DecodeError> {
+    Ok(decode_smv_nth_asdu_view(buffer, pos, n)?.samples)
+}
+
+/// Checks that an ASDU is encoded as SEQUENCE (0x30). Other tags are an error, in
+/// lenient mode they are reported as warning.
+fn check_asdu_tag(
+    tag: u8,
+    buffer_index: usize,
+    ctx: &mut DecodeContext,
+) -> Result<(), DecodeError> {
+    if tag == 0x30 {
+        return Ok(());
+    }
+
+    let message = format!("Expected ASDU tag 0x30, got 0x{:02x}", tag);
+    if !ctx.options.is_lenient() {
+        return Err(DecodeError::new(&message, buffer_index));
+    }
+    ctx.warnings
+        .push(DecodeWarning::new(&message, buffer_index));
+    Ok(())
+}
+
+/// Skips a complete element (tag, length and content) without decoding it.
+///
+/// # Returns
+/// The position of the element following the skipped one.
+fn skip_element(buffer: &[u8], buffer_index: usize) -> Result<usize, DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let pos = decode_tag_length(&mut tag, &mut length, buffer, buffer_index)?;
+    if pos + length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Element 0x{:02x} of {} bytes exceeds buffer length {}",
+                tag,
+                length,
+                buffer.len()
+            ),
+            buffer_index,
+        ));
+    }
+    Ok(pos + length)
 }
 
 /// Determines if the provided Ethernet frame buffer contains a Sampled Values (SMV) frame
@@ -355,7 +680,81 @@ pub fn is_smv_frame(buffer: &[u8]) -> bool {
         return false;
     }
     let ether_type = &buffer[ether_type_offset..ether_type_offset + 2];
-    ether_type == [0x88, 0xba]
+    ether_type == ETHERTYPE_SMV
+}
+
+/// Reads the APPID of a Sampled Values frame, which follows the EtherType, so
+/// subscribers can skip foreign streams before decoding.
+///
+/// Returns `None` if the buffer is no SMV frame according to `is_smv_frame` or ends
+/// before the APPID.
+pub fn smv_appid(buffer: &[u8]) -> Option<u16> {
+    if !is_smv_frame(buffer) {
+        return None;
+    }
+    decode_appid(buffer)
+}
+
+/// Decodes back-to-back SMV frames, e.g. the contents of a ring buffer.
+///
+/// The end of each frame follows from its Ethernet header and the length field, frames
+/// shorter than the Ethernet minimum of 60 bytes are expected to be padded. Decoding
+/// stops at the end of the buffer, or after the first item that is an error, as the
+/// following frame boundaries are unknown then.
+///
+/// # Parameters
+/// - `buffer`: The concatenated frames without frame check sequence.
+///
+/// # Returns
+/// For every frame its offset in `buffer` and the decoded SavPdu, or a DecodeError for
+/// a frame that is not SMV, truncated or malformed.
+pub fn decode_smv_batch(
+    buffer: &[u8],
+) -> impl Iterator<Item = Result<(usize, SavPdu), DecodeError>> + '_ {
+    let mut offset = 0;
+    core::iter::from_fn(move || {
+        if offset >= buffer.len() {
+            return None;
+        }
+        let frame_start = offset;
+        // Nothing follows an error
+        offset = buffer.len();
+
+        let decoded = decode_batch_frame(&buffer[frame_start..]);
+        Some(match decoded {
+            Ok((frame_length, pdu)) => {
+                offset = frame_start + frame_length;
+                Ok((frame_start, pdu))
+            }
+            Err(mut err) => {
+                err.buffer_index += frame_start;
+                Err(err)
+            }
+        })
+    })
+}
+
+/// Decodes the SMV frame at the start of `buffer`, returning its length including padding
+fn decode_batch_frame(buffer: &[u8]) -> Result<(usize, SavPdu), DecodeError> {
+    if !is_smv_frame(buffer) {
+        return Err(DecodeError::new("Not a SMV frame", 0));
+    }
+    let (header, pos) = EthernetHeader::from_wire(buffer)?;
+    // The length counts from the APPID to the end of the PDU
+    let content_length = pos - LINK_HEADER_LENGTH + u16::from_be_bytes(header.length) as usize;
+    if content_length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!(
+                "Frame of {} bytes exceeds the remaining {} bytes",
+                content_length,
+                buffer.len()
+            ),
+            0,
+        ));
+    }
+    let frame_length = content_length.max(MIN_FRAME_LENGTH).min(buffer.len());
+    let pdu = decode_smv(&buffer[..content_length], pos)?;
+    Ok((frame_length, pdu))
 }
 
 /// Decodes a sequence of IECData elements from the buffer, returning a vector of decoded elements.
@@ -371,20 +770,58 @@ fn decode_smv_asdus(
     val: &mut Vec<SavAsdu>,
     buffer: &[u8],
     start_pos: usize,
+    end_pos: usize,
     no_asdu: u16,
+    ctx: &mut DecodeContext,
 ) -> Result<usize, DecodeError> {
     let mut new_pos = start_pos;
 
-    let mut _tag = 0u8;
-    let mut _length = 0usize;
+    let mut tag = 0u8;
+    let mut length = 0usize;
+
+    for index in 0..no_asdu {
+        if new_pos >= end_pos {
+            return Err(DecodeError::new(
+                &format!(
+                    "noASDU is {}, but the sequence of ASDU holds only {}",
+                    no_asdu, index
+                ),
+                new_pos,
+            ));
+        }
 
-    for _ in 0..no_asdu {
         // length field of the next ASDU
-        new_pos = decode_tag_length(&mut _tag, &mut _length, buffer, new_pos)?;
+        let asdu_pos = new_pos;
+        new_pos = ctx.tag_length(&mut tag, &mut length, buffer, new_pos)?;
+        check_asdu_tag(tag, asdu_pos, ctx)?;
+        let asdu_end = new_pos + length;
+        if asdu_end > end_pos {
+            return Err(DecodeError::new(
+                &format!("ASDU {} exceeds the sequence of ASDU", index),
+                asdu_pos,
+            ));
+        }
 
-        let (next_pos, new_asdu) = decode_smv_asdu(buffer, new_pos)?;
+        let (next_pos, new_asdu) = decode_smv_asdu(buffer, new_pos, ctx)?;
         val.push(new_asdu);
-        new_pos = next_pos;
+
+        if next_pos != asdu_end {
+            let message = format!(
+                "ASDU length {} does not match its content length {}",
+                length,
+                next_pos - new_pos
+            );
+            ctx.length_mismatch(&message, asdu_pos)?;
+        }
+        new_pos = asdu_end;
+    }
+
+    if new_pos != end_pos {
+        let message = format!(
+            "{} bytes follow the last ASDU in the sequence of ASDU",
+            end_pos.saturating_sub(new_pos)
+        );
+        ctx.length_mismatch(&message, new_pos)?;
     }
 
     Ok(new_pos)
@@ -399,7 +836,11 @@ fn decode_smv_asdus(
 ///
 /// # Returns
 /// The new buffer position after decoding the PDU.
-fn decode_smv_asdu(buffer: &[u8], start_pos: usize) -> Result<(usize, SavAsdu), DecodeError> {
+fn decode_smv_asdu(
+    buffer: &[u8],
+    start_pos: usize,
+    ctx: &mut DecodeContext,
+) -> Result<(usize, SavAsdu), DecodeError> {
     let mut asdu = SavAsdu::default();
 
     let mut new_pos = start_pos;
@@ -410,13 +851,12 @@ fn decode_smv_asdu(buffer: &[u8], start_pos: usize) -> Result<(usize, SavAsdu),
 
     // sampled value ID
     let mut length = 0usize;
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
     new_pos = decode_string(&mut asdu.msv_id, buffer, new_pos, length)?;
 
     // Optional data set reference description
-    let tag = buffer[new_pos];
-    if tag == 0x81 {
-        new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    if peek_tag(buffer, new_pos)? == 0x81 {
+        new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
         let mut dat_set_str = String::new();
         new_pos = decode_string(&mut dat_set_str, buffer, new_pos, length)?;
         asdu.dat_set = Some(dat_set_str);
@@ -425,33 +865,35 @@ fn decode_smv_asdu(buffer: &[u8], start_pos: usize) -> Result<(usize, SavAsdu),
     }
 
     // sample count
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    ctx.check_integer("smpCnt", buffer, new_pos, length)?;
     new_pos = decode_unsigned_16(&mut asdu.smp_cnt, buffer, new_pos, length)?;
 
     // conf_rev
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    ctx.check_integer("confRev", buffer, new_pos, length)?;
     new_pos = decode_unsigned_32(&mut asdu.conf_rev, buffer, new_pos, length)?;
 
     // Optional refresh time (timestamp)
-    let tag = buffer[new_pos];
-    if tag == 0x84 {
-        new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    if peek_tag(buffer, new_pos)? == 0x84 {
+        new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+        check_fixed_length("refrTm", length, 8, new_pos)?;
         let mut refr_tm_arr = [0u8; 8];
-        refr_tm_arr.copy_from_slice(&buffer[new_pos..new_pos + 8]);
+        new_pos = decode_octet_string(&mut refr_tm_arr, buffer, new_pos, length)?;
         asdu.refr_tm = Some(refr_tm_arr);
-        new_pos += 8;
     } else {
         asdu.refr_tm = None;
     }
 
     // samples synched
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    ctx.check_integer("smpSynch", buffer, new_pos, length)?;
     new_pos = decode_unsigned_8(&mut asdu.smp_synch, buffer, new_pos, length)?;
 
     // Optional sample rate
-    let tag = buffer[new_pos];
-    if tag == 0x86 {
-        new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    if peek_tag(buffer, new_pos)? == 0x86 {
+        new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+        ctx.check_integer("smpRate", buffer, new_pos, length)?;
         let mut smp_rate_num = 0u16;
         new_pos = decode_unsigned_16(&mut smp_rate_num, buffer, new_pos, length)?;
         asdu.smp_rate = Some(smp_rate_num);
@@ -460,15 +902,16 @@ fn decode_smv_asdu(buffer: &[u8], start_pos: usize) -> Result<(usize, SavAsdu),
     }
 
     // Data Content
-    new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+    new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
     asdu.all_data.clear();
-    let (next_pos, result) = decode_savs(buffer, new_pos, length)?;
+    let (next_pos, result) = decode_savs(buffer, new_pos, length, ctx)?;
     new_pos = next_pos;
     asdu.all_data = result;
 
     // Optional Sampling Mod
     if new_pos < buffer.len() && buffer[new_pos] == 0x88 {
-        new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+        new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+        ctx.check_integer("smpMod", buffer, new_pos, length)?;
         let mut smp_mod_num = 0u16;
         new_pos = decode_unsigned_16(&mut smp_mod_num, buffer, new_pos, length)?;
         asdu.smp_mod = Some(smp_mod_num);
@@ -476,114 +919,223 @@ fn decode_smv_asdu(buffer: &[u8], start_pos: usize) -> Result<(usize, SavAsdu),
         asdu.smp_mod = None;
     }
 
-    // Optional grandmaster clock identity
-    if new_pos < buffer.len() && buffer[new_pos] == 0x89 {
-        new_pos = decode_tag_length(&mut _tag, &mut length, buffer, new_pos)?;
-        let mut gm_identity_oct = [0u8; 8];
-        new_pos = decode_octet_string(&mut gm_identity_oct, buffer, new_pos, length)?;
-        asdu.gm_identity = Some(gm_identity_oct);
-    } else {
-        asdu.gm_identity = None;
+    // Optional grandmaster clock identity
+    if new_pos < buffer.len() && buffer[new_pos] == 0x89 {
+        new_pos = ctx.tag_length(&mut _tag, &mut length, buffer, new_pos)?;
+        check_fixed_length("gmIdentity", length, 8, new_pos)?;
+        let mut gm_identity_oct = [0u8; 8];
+        new_pos = decode_octet_string(&mut gm_identity_oct, buffer, new_pos, length)?;
+        asdu.gm_identity = Some(gm_identity_oct);
+    } else {
+        asdu.gm_identity = None;
+    }
+
+    Ok((new_pos, asdu))
+}
+
+/// Reads the tag at `buffer_index` to check for an optional field that is followed
+/// by further mandatory fields
+fn peek_tag(buffer: &[u8], buffer_index: usize) -> Result<u8, DecodeError> {
+    buffer
+        .get(buffer_index)
+        .copied()
+        .ok_or_else(|| DecodeError::new("unexpected end of ASDU", buffer_index))
+}
+
+/// Checks the length of a fixed size field, as its content is copied into an array
+fn check_fixed_length(
+    name: &str,
+    length: usize,
+    expected: usize,
+    buffer_index: usize,
+) -> Result<(), DecodeError> {
+    if length != expected {
+        return Err(DecodeError::new(
+            &format!("{} must be {} bytes, got {}", name, expected, length),
+            buffer_index,
+        ));
+    }
+    Ok(())
+}
+
+/// Returns true for the tags a sample value may be encoded with
+fn is_sample_value_tag(tag: u8) -> bool {
+    tag == 0x83 || tag == 0x85 || tag == 0x87
+}
+
+/// Decodes a FLOAT32 sample value: the exponent width descriptor followed by 4 bytes IEEE-754
+fn decode_float32(buffer: &[u8], pos: usize, length: usize) -> Result<f32, DecodeError> {
+    if length != FLOAT32_LENGTH {
+        return Err(DecodeError::new(
+            &format!(
+                "FLOAT32 sample must be {} bytes, got {}",
+                FLOAT32_LENGTH, length
+            ),
+            pos,
+        ));
+    }
+    let Some(data) = buffer.get(pos..pos + length) else {
+        return Err(DecodeError::new("Buffer too short for FLOAT32 sample", pos));
+    };
+    if data[0] != FLOAT32_EXPONENT_WIDTH {
+        return Err(DecodeError::new(
+            &format!("Unsupported FLOAT32 exponent width 0x{:02x}", data[0]),
+            pos,
+        ));
+    }
+    Ok(f32::from_be_bytes([data[1], data[2], data[3], data[4]]))
+}
+
+/// Decodes one sample: the value (INTEGER or FLOAT32) followed by the quality BIT STRING.
+///
+/// # Returns
+/// The position after the quality and the decoded sample.
+fn decode_sample(
+    buffer: &[u8],
+    buffer_index: usize,
+    ctx: &mut DecodeContext,
+) -> Result<(usize, Sample), DecodeError> {
+    let mut tag = 0u8;
+    let mut length = 0usize;
+
+    // Decode the value (ASN.1 BER encoded integer or FLOAT32)
+    let mut pos = ctx.tag_length(&mut tag, &mut length, buffer, buffer_index)?;
+
+    // Most encoders use 0x83 for integers, some capture tools emit 0x85
+    let value = match tag {
+        0x83 | 0x85 => {
+            ctx.check_integer("Sample value", buffer, pos, length)?;
+            // Decode the integer value using BER decompression
+            let mut value_bytes = [0u8; 4];
+            decompress_integer(&mut value_bytes, buffer, pos, length)?;
+            SampleValue::Int(i32::from_be_bytes(value_bytes))
+        }
+        0x87 => SampleValue::Float(decode_float32(buffer, pos, length)?),
+        _ => {
+            return Err(DecodeError::new(
+                &format!(
+                    "Expected integer tag 0x83 or 0x85 or float tag 0x87, got 0x{:02x}",
+                    tag
+                ),
+                pos,
+            ));
+        }
+    };
+    pos += length;
+
+    // Decode the quality bitstring (ASN.1 BER encoded bitstring)
+    pos = ctx.tag_length(&mut tag, &mut length, buffer, pos)?;
+
+    if tag != 0x84 {
+        return Err(DecodeError::new(
+            &format!("Expected bitstring tag 0x84, got 0x{:02x}", tag),
+            pos,
+        ));
+    }
+
+    // First byte of bitstring is the number of unused bits
+    // For 13-bit quality, there should be 3 unused bits in the 2-byte encoding
+    if length == 0 || pos >= buffer.len() {
+        return Err(DecodeError::new(
+            "Buffer too short for bitstring unused bits",
+            pos,
+        ));
     }
+    let unused_bits = buffer[pos] as usize;
+    pos += 1;
+    let quality_length = length - 1; // Subtract the unused bits byte
 
-    Ok((new_pos, asdu))
-}
-
-/// Extracts the SIM bit from the "reserved 1" field in the SV/SMV header.
-/// The SIM bit is the most significant bit (bit 7) of the first byte of reserved 1.
-/// Returns Some(true) if the SIM bit is set, Some(false) if not, or None if the buffer is too short.
-///
-/// # Arguments
-/// * `buffer` - The Ethernet frame buffer (must be long enough to contain reserved 1).
-fn decode_sim_bit(buffer: &[u8]) -> Option<bool> {
-    // Ethernet: 6 (dst) + 6 (src)
-    let mut offset = 12;
-
-    // Check for VLAN tag (0x81, 0x00)
-    if buffer.len() >= offset + 2 && buffer[offset..offset + 2] == [0x81, 0x00] {
-        offset += 4; // VLAN tag is 4 bytes
+    // The quality is read as u16, so it must be 2 bytes holding at least the 13 bits;
+    // encoders that send all 16 bits are accepted unless in strict mode
+    if quality_length != QUALITY_LENGTH || unused_bits > QUALITY_LENGTH * 8 - QUALITY_BITS {
+        return Err(DecodeError::new(
+            &format!(
+                "Quality must be {} bytes with up to {} unused bits, got {} bytes with {} unused bits",
+                QUALITY_LENGTH,
+                QUALITY_LENGTH * 8 - QUALITY_BITS,
+                quality_length,
+                unused_bits
+            ),
+            pos - 1,
+        ));
+    }
+    if ctx.options.is_strict() && quality_length * 8 != QUALITY_BITS + unused_bits {
+        return Err(DecodeError::new(
+            &format!(
+                "Quality of {} bytes must have {} unused bits, got {}",
+                quality_length,
+                quality_length * 8 - QUALITY_BITS,
+                unused_bits
+            ),
+            pos - 1,
+        ));
     }
 
-    // EtherType (2) + appid (2) + length (2)
-    offset += 2 + 2 + 2;
+    // Read quality bytes (should be 2 bytes for 13-bit quality)
+    if pos + quality_length > buffer.len() {
+        return Err(DecodeError::new(
+            &format!("Buffer too short for quality bytes at pos {}", pos),
+            pos,
+        ));
+    }
 
-    // Now offset points to the first byte of reserved 1
-    if buffer.len() <= offset {
-        return None;
+    // Quality is encoded as big-endian, read it as u16
+    // The unused bits are at the LSB end and already accounted for in the encoding
+    let mut quality_bits = 0u16;
+    for i in 0..quality_length {
+        quality_bits = (quality_bits << 8) | buffer[pos + i] as u16;
     }
+    pos += quality_length;
 
-    let reserved1_byte = buffer[offset];
-    Some((reserved1_byte & 0x80) != 0)
+    Ok((
+        pos,
+        Sample::from_parts(value, Quality::from_u16(quality_bits)),
+    ))
 }
 
 fn decode_savs(
     buffer: &[u8],
     buffer_index: usize,
     data_length: usize,
-) -> Result<(usize, Vec<crate::types::Sample>), DecodeError> {
+    ctx: &mut DecodeContext,
+) -> Result<(usize, Vec<Sample>), DecodeError> {
     let mut pos = buffer_index;
     let end_pos = buffer_index + data_length;
     let mut result = Vec::new();
-
-    let mut tag = 0u8;
-    let mut length = 0usize;
-
-    while pos < end_pos {
-        // Decode the i32 value (ASN.1 BER encoded integer)
-        pos = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
-
-        if tag != 0x83 {
-            return Err(DecodeError::new(
-                &format!("Expected integer tag 0x83, got 0x{:02x}", tag),
-                pos,
-            ));
-        }
-
-        // Decode the integer value using BER decompression
-        let mut value_bytes = [0u8; 4];
-        decompress_integer(&mut value_bytes, buffer, pos, length)?;
-        let int_val = i32::from_be_bytes(value_bytes);
-        pos += length;
-
-        // Decode the quality bitstring (ASN.1 BER encoded bitstring)
-        pos = decode_tag_length(&mut tag, &mut length, buffer, pos)?;
-
-        if tag != 0x84 {
-            return Err(DecodeError::new(
-                &format!("Expected bitstring tag 0x84, got 0x{:02x}", tag),
-                pos,
-            ));
+    let max_samples = ctx.options.limits.max_samples;
+    let lenient = ctx.options.is_lenient();
+
+    loop {
+        // In lenient mode the content decides where the block ends: samples that
+        // continue past the declared end are still read, and the block is closed early
+        // at the first tag that cannot start a sample.
+        let next_is_sample = buffer.get(pos).is_some_and(|tag| is_sample_value_tag(*tag));
+        if pos >= end_pos && !(lenient && next_is_sample) {
+            break;
         }
-
-        // First byte of bitstring is the number of unused bits
-        // For 13-bit quality, there should be 3 unused bits in the 2-byte encoding
-        if pos >= buffer.len() {
-            return Err(DecodeError::new(
-                "Buffer too short for bitstring unused bits",
-                pos,
-            ));
+        if lenient && !next_is_sample {
+            break;
         }
-        let _unused_bits = buffer[pos];
-        pos += 1;
-        let quality_length = length - 1; // Subtract the unused bits byte
 
-        // Read quality bytes (should be 2 bytes for 13-bit quality)
-        if pos + quality_length > buffer.len() {
+        if result.len() == max_samples {
             return Err(DecodeError::new(
-                &format!("Buffer too short for quality bytes at pos {}", pos),
+                &format!("Sample count exceeds the limit of {}", max_samples),
                 pos,
             ));
         }
 
-        // Quality is encoded as big-endian, read it as u16
-        // The unused bits are at the LSB end and already accounted for in the encoding
-        let mut quality_bits = 0u16;
-        for i in 0..quality_length {
-            quality_bits = (quality_bits << 8) | buffer[pos + i] as u16;
-        }
-        pos += quality_length;
+        let (next_pos, sample) = decode_sample(buffer, pos, ctx)?;
+        pos = next_pos;
+        result.push(sample);
+    }
 
-        result.push(crate::types::Sample::new(int_val, quality_bits));
+    if pos != end_pos {
+        let message = format!(
+            "Sample block length {} does not match its content length {}",
+            data_length,
+            pos - buffer_index
+        );
+        ctx.length_mismatch(&message, buffer_index)?;
     }
 
     Ok((pos, result))
@@ -592,8 +1144,24 @@ fn decode_savs(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encode_smv::encode_smv;
+    use crate::types::DecodeMode;
+    use std::collections::BTreeMap;
     use std::time::Instant;
 
+    const MAX_SAMPLES: usize = 256;
+
+    fn decode_block(
+        buffer: &[u8],
+        max_samples: usize,
+    ) -> Result<(usize, Vec<Sample>), DecodeError> {
+        let options = DecodeOptions {
+            limits: DecodeLimits { max_samples },
+            ..Default::default()
+        };
+        decode_savs(buffer, 0, buffer.len(), &mut DecodeContext::new(&options))
+    }
+
     fn create_test_data_buffer() -> Vec<u8> {
         let mut buffer = Vec::new();
 
@@ -636,7 +1204,7 @@ mod tests {
     #[test]
     fn test_decode_92_le_data_correctness() {
         let buffer = create_test_data_buffer();
-        let result = decode_savs(&buffer, 0, buffer.len());
+        let result = decode_block(&buffer, MAX_SAMPLES);
 
         assert!(result.is_ok());
         let (pos, data) = result.unwrap();
@@ -655,6 +1223,28 @@ mod tests {
         assert!(data[7].quality.is_good());
     }
 
+    #[test]
+    fn test_decode_float_sample() {
+        let buffer = [
+            0x87, 0x05, 0x08, 0x43, 0x66, 0x40, 0x00, // FLOAT32 230.25
+            0x84, 0x03, 0x03, 0x00, 0x00, // quality
+        ];
+        let (pos, data) = decode_block(&buffer, MAX_SAMPLES).unwrap();
+
+        assert_eq!(pos, buffer.len());
+        assert_eq!(data[0].value, SampleValue::Float(230.25));
+
+        // Only the 8 bit exponent width of FLOAT32 is supported
+        let mut wrong_width = buffer;
+        wrong_width[2] = 0x0B;
+        let err = decode_block(&wrong_width, MAX_SAMPLES).unwrap_err();
+        assert!(err.message.contains("exponent width"), "{}", err.message);
+
+        let mut wrong_length = buffer;
+        wrong_length[1] = 0x04;
+        assert!(decode_block(&wrong_length, MAX_SAMPLES).is_err());
+    }
+
     #[test]
     fn test_decode_92_le_data_performance() {
         let buffer = create_test_data_buffer();
@@ -662,7 +1252,7 @@ mod tests {
 
         let start = Instant::now();
         for _ in 0..iterations {
-            let _ = decode_savs(&buffer, 0, buffer.len());
+            let _ = decode_block(&buffer, MAX_SAMPLES);
         }
         let duration = start.elapsed();
 
@@ -721,7 +1311,7 @@ mod tests {
             buffer.extend_from_slice(&quality_with_padding.to_be_bytes());
         }
 
-        let result = decode_savs(&buffer, 0, buffer.len());
+        let result = decode_block(&buffer, MAX_SAMPLES);
         assert!(result.is_ok());
         let (pos, data) = result.unwrap();
         assert_eq!(data.len(), 4);
@@ -762,7 +1352,7 @@ mod tests {
             buffer.extend_from_slice(&quality_16bit_container.to_be_bytes());
         }
 
-        let result = decode_savs(&buffer, 0, buffer.len());
+        let result = decode_block(&buffer, MAX_SAMPLES);
         assert!(result.is_ok());
         let (pos, data) = result.unwrap();
         assert_eq!(data.len(), 12);
@@ -773,6 +1363,905 @@ mod tests {
         assert_eq!(data[0].quality.validity, crate::types::Validity::Invalid);
     }
 
+    #[test]
+    fn test_decode_sample_limit() {
+        // The reference block holds 8 samples
+        let buffer = create_test_data_buffer();
+
+        let (_, data) = decode_block(&buffer, 8).unwrap();
+        assert_eq!(data.len(), 8);
+
+        let err = decode_block(&buffer, 7).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Sample count exceeds the limit of 7"));
+
+        // The limit is applied independently of the block length
+        let frame = create_frame(&create_sav_pdu_buffer());
+        let limits = DecodeLimits { max_samples: 4 };
+        assert!(decode_smv_with_limits(&frame, 22, &limits).is_err());
+        assert!(decode_smv_with_limits(&frame, 22, &DecodeLimits::default()).is_ok());
+    }
+
+    /// Position of the sample block length byte in `create_sav_pdu_buffer`
+    const SAMPLE_BLOCK_LENGTH_POS: usize = 7 + 2 + 17;
+
+    #[test]
+    fn test_lenient_sample_block_too_short() {
+        let mut sav_pdu = create_sav_pdu_buffer();
+        assert_eq!(sav_pdu[SAMPLE_BLOCK_LENGTH_POS - 1], 0x87);
+        sav_pdu[SAMPLE_BLOCK_LENGTH_POS] -= 2;
+        let frame = create_frame(&sav_pdu);
+
+        // Default and lenient decoding read the last sample past the declared end, as
+        // before the decode modes existed, and report the mismatch
+        for mode in [DecodeMode::Default, DecodeMode::Lenient] {
+            let options = DecodeOptions {
+                mode,
+                ..Default::default()
+            };
+            let (pdu, warnings) = decode_smv_with_options(&frame, 22, &options).unwrap();
+            assert_eq!(pdu.sav_asdu[0].all_data.len(), 8);
+            assert_eq!(pdu.sav_asdu[0].all_data[7].value, 17000);
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0]
+                .message
+                .starts_with("Sample block length 70 does not match its content length 72"));
+            assert_eq!(warnings[0].buffer_index, 22 + SAMPLE_BLOCK_LENGTH_POS + 1);
+        }
+        assert_eq!(
+            decode_smv(&frame, 22).unwrap().sav_asdu[0].all_data.len(),
+            8
+        );
+
+        // Strict decoding rejects the frame
+        let options = DecodeOptions {
+            mode: DecodeMode::Strict,
+            ..Default::default()
+        };
+        assert!(decode_smv_with_options(&frame, 22, &options).is_err());
+    }
+
+    #[test]
+    fn test_lenient_sample_block_resync() {
+        // Declared length ends a whole sample (9 bytes) early: the last sample is picked up again
+        let mut sav_pdu = create_sav_pdu_buffer_with(&[0x88, 0x01, 0x01]); // with smpMod
+        sav_pdu[SAMPLE_BLOCK_LENGTH_POS] -= 9;
+        let frame = create_frame(&sav_pdu);
+
+        // Default decoding follows the declared length and skips the rest of the ASDU
+        let (pdu, warnings) =
+            decode_smv_with_options(&frame, 22, &DecodeOptions::default()).unwrap();
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 7);
+        assert_eq!(pdu.sav_asdu[0].smp_mod, None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("ASDU length"));
+
+        let options = DecodeOptions {
+            mode: DecodeMode::Lenient,
+            ..Default::default()
+        };
+        let (pdu, warnings) = decode_smv_with_options(&frame, 22, &options).unwrap();
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 8);
+        assert_eq!(pdu.sav_asdu[0].smp_mod, Some(1));
+        assert_eq!(warnings.len(), 1);
+
+        // Declared length too long: the block is closed at the smpMod tag
+        sav_pdu[SAMPLE_BLOCK_LENGTH_POS] += 9 + 3;
+        let frame = create_frame(&sav_pdu);
+        assert!(decode_smv(&frame, 22).is_err());
+
+        let (pdu, warnings) = decode_smv_with_options(&frame, 22, &options).unwrap();
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 8);
+        assert_eq!(pdu.sav_asdu[0].smp_mod, Some(1));
+        assert_eq!(warnings.len(), 1);
+
+        // Consistent frames decode without warnings
+        let frame = create_frame(&create_sav_pdu_buffer());
+        let (_, warnings) = decode_smv_with_options(&frame, 22, &options).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_decode_alternative_value_tag() {
+        // Same sample block as the reference buffer, but with 0x85 value tags
+        let mut buffer = create_test_data_buffer();
+        let mut pos = 0;
+        while pos < buffer.len() {
+            assert_eq!(buffer[pos], 0x83);
+            buffer[pos] = 0x85;
+            pos += 2 + buffer[pos + 1] as usize; // value
+            pos += 2 + buffer[pos + 1] as usize; // quality
+        }
+
+        let (pos, data) = decode_block(&buffer, MAX_SAMPLES).unwrap();
+        assert_eq!(pos, buffer.len());
+        assert_eq!(data.len(), 8);
+        assert_eq!(data[0].value, 10000);
+        assert_eq!(data[7].value, 17000);
+
+        // Any other value tag is still rejected
+        buffer[0] = 0x86;
+        assert!(decode_block(&buffer, MAX_SAMPLES).is_err());
+    }
+
+    fn create_sav_pdu_buffer() -> Vec<u8> {
+        create_sav_pdu_buffer_with(&[])
+    }
+
+    /// Builds a savPdu with one ASDU, `asdu_tail` is appended after the sample block
+    fn create_sav_pdu_buffer_with(asdu_tail: &[u8]) -> Vec<u8> {
+        let samples = create_test_data_buffer();
+
+        let mut asdu = vec![
+            0x80, 0x04, b'M', b'U', b'0', b'1', // svID
+            0x82, 0x02, 0x01, 0x00, // smpCnt
+            0x83, 0x01, 0x01, // confRev
+            0x85, 0x01, 0x02, // smpSynch
+            0x87, // sample values
+        ];
+        asdu.push(samples.len() as u8);
+        asdu.extend_from_slice(&samples);
+        asdu.extend_from_slice(asdu_tail);
+
+        let mut sequence = vec![0x30, asdu.len() as u8];
+        sequence.extend_from_slice(&asdu);
+
+        let mut content = vec![0x80, 0x01, 0x01, 0xA2, sequence.len() as u8];
+        content.extend_from_slice(&sequence);
+
+        let mut sav_pdu = vec![0x60, content.len() as u8];
+        sav_pdu.extend_from_slice(&content);
+        sav_pdu
+    }
+
+    /// Prepends an untagged SMV Ethernet header, the PDU starts at offset 22
+    fn create_frame(pdu: &[u8]) -> Vec<u8> {
+        let mut frame = vec![
+            0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, // dst
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src
+            0x88, 0xba, // EtherType
+            0x40, 0x00, // APPID
+        ];
+        frame.extend_from_slice(&(pdu.len() as u16 + 8).to_be_bytes());
+        frame.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]); // Reserved 1 and 2
+        frame.extend_from_slice(pdu);
+        frame
+    }
+
+    /// Builds a frame with one ASDU holding one sample from the given content octets,
+    /// `trailing` is appended to the sequence of ASDU after the ASDU
+    fn strict_test_frame(smp_cnt: &[u8], value: &[u8], quality: &[u8], trailing: &[u8]) -> Vec<u8> {
+        let mut samples = vec![0x83, value.len() as u8];
+        samples.extend_from_slice(value);
+        samples.extend_from_slice(&[0x84, quality.len() as u8]);
+        samples.extend_from_slice(quality);
+
+        let mut asdu = vec![
+            0x80,
+            0x04,
+            b'M',
+            b'U',
+            b'0',
+            b'1',
+            0x82,
+            smp_cnt.len() as u8,
+        ];
+        asdu.extend_from_slice(smp_cnt);
+        asdu.extend_from_slice(&[
+            0x83,
+            0x01,
+            0x01,
+            0x85,
+            0x01,
+            0x02,
+            0x87,
+            samples.len() as u8,
+        ]);
+        asdu.extend_from_slice(&samples);
+
+        let mut sequence = vec![0x30, asdu.len() as u8];
+        sequence.extend_from_slice(&asdu);
+        sequence.extend_from_slice(trailing);
+
+        let mut content = vec![0x80, 0x01, 0x01, 0xA2, sequence.len() as u8];
+        content.extend_from_slice(&sequence);
+        let mut sav_pdu = vec![0x60, content.len() as u8];
+        sav_pdu.extend_from_slice(&content);
+        create_frame(&sav_pdu)
+    }
+
+    #[test]
+    fn test_strict_mode() {
+        let strict = DecodeOptions {
+            mode: DecodeMode::Strict,
+            ..Default::default()
+        };
+        let good_quality = [0x03, 0x00, 0x00];
+
+        // Well-formed frames pass, including the ones of the encoder
+        let frame = strict_test_frame(&[0x00, 0x80], &[0xFF, 0x7F], &good_quality, &[]);
+        assert!(decode_smv_with_options(&frame, 22, &strict).is_ok());
+        let frame = create_frame(&create_sav_pdu_buffer());
+        assert!(decode_smv_with_options(&frame, 22, &strict).is_ok());
+
+        let violations = [
+            // smpCnt 1 with a redundant leading zero octet
+            strict_test_frame(&[0x00, 0x01], &[0x01], &good_quality, &[]),
+            // Sample value -1 with a redundant leading 0xFF octet
+            strict_test_frame(&[0x01], &[0xFF, 0xFF], &good_quality, &[]),
+            // 13 bits of quality in 2 octets leave 3 unused bits, not 0
+            strict_test_frame(&[0x01], &[0x01], &[0x00, 0x00, 0x00], &[]),
+            // Padding after the last ASDU
+            strict_test_frame(&[0x01], &[0x01], &good_quality, &[0x00, 0x00]),
+        ];
+        for frame in &violations {
+            assert!(decode_smv(frame, 22).is_ok());
+            assert!(decode_smv_with_options(frame, 22, &strict).is_err());
+        }
+
+        // An empty sample value is no INTEGER in any mode
+        let frame = strict_test_frame(&[0x01], &[], &good_quality, &[]);
+        assert!(decode_smv(&frame, 22).is_err());
+    }
+
+    #[test]
+    fn test_length_mismatch_policy() {
+        let good_quality = [0x03, 0x00, 0x00];
+        let trailing = strict_test_frame(&[0x01], &[0x01], &good_quality, &[0x00, 0x00]);
+        // The same two bytes counted into the ASDU instead of following it
+        let mut asdu_too_long = trailing.clone();
+        assert_eq!(asdu_too_long[22 + 7], 0x30);
+        asdu_too_long[22 + 8] += 2;
+
+        for frame in [&trailing, &asdu_too_long] {
+            for mode in [DecodeMode::Lenient, DecodeMode::Default] {
+                let options = DecodeOptions {
+                    mode,
+                    ..Default::default()
+                };
+                let (pdu, warnings) = decode_smv_with_options(frame, 22, &options).unwrap();
+                assert_eq!(pdu.sav_asdu[0].all_data.len(), 1);
+                assert_eq!(warnings.len(), 1);
+            }
+            let strict = DecodeOptions {
+                mode: DecodeMode::Strict,
+                ..Default::default()
+            };
+            assert!(decode_smv_with_options(frame, 22, &strict).is_err());
+        }
+    }
+
+    #[test]
+    fn test_quality_unused_bits() {
+        let decode_quality = |quality: &[u8]| {
+            let frame = strict_test_frame(&[0x01], &[0x01], quality, &[]);
+            decode_smv(&frame, 22).map(|pdu| pdu.sav_asdu[0].all_data[0].quality)
+        };
+
+        assert_eq!(
+            decode_quality(&[0x03, 0x40, 0x08]).unwrap().to_u16(),
+            0x4008
+        );
+        // All 16 bits sent, only accepted outside of strict mode
+        assert!(decode_quality(&[0x00, 0x00, 0x00]).is_ok());
+
+        for invalid in [
+            &[][..],
+            &[0x04, 0x00, 0x00],
+            &[0x08, 0x00, 0x00],
+            &[0x03, 0x00],
+            &[0x03, 0x00, 0x00, 0x00],
+        ] {
+            let err = decode_quality(invalid).unwrap_err();
+            assert!(err.message.contains("unused bits"), "{}", err.message);
+        }
+    }
+
+    #[test]
+    fn test_decode_stats_tag_counts() {
+        let frame = create_frame(&create_sav_pdu_buffer());
+        let mut stats = DecodeStats::default();
+
+        let pdu = decode_smv_with_stats(&frame, 22, &mut stats).unwrap();
+        assert_eq!(pdu, decode_smv(&frame, 22).unwrap());
+
+        let expected_tags = BTreeMap::from([
+            (0x30, 1),
+            (0x60, 1),
+            (0x80, 2), // noASDU and svID
+            (0x82, 1),
+            (0x83, 9), // confRev and 8 sample values
+            (0x84, 8),
+            (0x85, 1),
+            (0x87, 1),
+            (0xA2, 1),
+        ]);
+        assert_eq!(stats.tags, expected_tags);
+        assert_eq!(stats.length_forms, BTreeMap::from([(0, 25)]));
+
+        // Statistics accumulate over frames
+        decode_smv_with_stats(&frame, 22, &mut stats).unwrap();
+        assert_eq!(stats.tags[&0x84], 16);
+    }
+
+    #[test]
+    fn test_decode_stats_long_length_forms() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "M".repeat(300),
+                all_data: vec![Sample::new(1, 0)],
+                ..Default::default()
+            }],
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+        let mut stats = DecodeStats::default();
+
+        decode_smv_with_stats(&frame, 22, &mut stats).unwrap();
+
+        // savPdu, sequence of ASDU, ASDU and svID exceed 255 bytes
+        assert_eq!(stats.length_forms[&2], 4);
+        assert!(!stats.length_forms.contains_key(&1));
+    }
+
+    #[test]
+    fn test_decode_optional_field_combinations() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+
+        let mut asdus = vec![];
+
+        // Bit i selects the presence of datSet, refrTm, smpRate, smpMod, gmIdentity
+        for fields in 0..32u8 {
+            let present = |bit: u8| fields & (1 << bit) != 0;
+            let asdu = SavAsdu {
+                msv_id: "MU01".to_string(),
+                dat_set: present(0).then(|| "IED1/LLN0$PhsMeas1".to_string()),
+                smp_cnt: 1234,
+                conf_rev: 1,
+                refr_tm: present(1).then_some([0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x0a]),
+                smp_synch: 2,
+                smp_rate: present(2).then_some(4000),
+                all_data: vec![Sample::new(10000, 0x0000), Sample::new(-20000, 0x4000)],
+                smp_mod: present(3).then_some(1),
+                gm_identity: present(4).then_some([0x00, 0x1b, 0x19, 0xff, 0xfe, 0x00, 0x00, 0x01]),
+            };
+            let pdu = SavPdu {
+                sim: false,
+                no_asdu: 1,
+                security: None,
+                sav_asdu: vec![asdu],
+            };
+            let frame = encode_smv(&header, &pdu).unwrap();
+
+            assert_eq!(
+                decode_smv(&frame, 22).unwrap(),
+                pdu,
+                "optional fields 0b{:05b}",
+                fields
+            );
+            asdus.extend(pdu.sav_asdu);
+        }
+
+        // All combinations as consecutive ASDUs of one frame
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: asdus.len() as u16,
+            security: None,
+            sav_asdu: asdus,
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+        assert_eq!(decode_smv(&frame, 22).unwrap(), pdu);
+    }
+
+    /// Encodes a TLV with a long-form length of `length_octets` octets, even where the
+    /// short form would do
+    fn long_form_tlv(tag: u8, content: &[u8], length_octets: usize) -> Vec<u8> {
+        let mut tlv = vec![tag, 0x80 | length_octets as u8];
+        tlv.extend_from_slice(&content.len().to_be_bytes()[8 - length_octets..]);
+        tlv.extend_from_slice(content);
+        tlv
+    }
+
+    #[test]
+    fn test_decode_fields_with_long_form_lengths() {
+        let refr_tm = [0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x0a];
+        let gm_identity = [0x00, 0x1b, 0x19, 0xff, 0xfe, 0x00, 0x00, 0x01];
+
+        let mut samples = long_form_tlv(0x83, &[0x27, 0x10], 1);
+        samples.extend(long_form_tlv(0x84, &[0x03, 0x40, 0x00], 2));
+
+        let asdu = [
+            long_form_tlv(0x80, b"MU01", 1),
+            long_form_tlv(0x81, b"IED1/LLN0$PhsMeas1", 2),
+            long_form_tlv(0x82, &[0x04, 0xd2], 2), // smpCnt 1234
+            long_form_tlv(0x83, &[0x01], 3),
+            long_form_tlv(0x84, &refr_tm, 2),
+            long_form_tlv(0x85, &[0x02], 1),
+            long_form_tlv(0x86, &[0x0f, 0xa0], 3), // smpRate 4000
+            long_form_tlv(0x87, &samples, 1),
+            long_form_tlv(0x88, &[0x01], 2),
+            long_form_tlv(0x89, &gm_identity, 1),
+        ]
+        .concat();
+        let sequence = long_form_tlv(0x30, &asdu, 1);
+        let mut content = long_form_tlv(0x80, &[0x01], 1);
+        content.extend(long_form_tlv(0xA2, &sequence, 2));
+        let frame = create_frame(&long_form_tlv(0x60, &content, 1));
+
+        let expected = SavAsdu {
+            msv_id: "MU01".to_string(),
+            dat_set: Some("IED1/LLN0$PhsMeas1".to_string()),
+            smp_cnt: 1234,
+            conf_rev: 1,
+            refr_tm: Some(refr_tm),
+            smp_synch: 2,
+            smp_rate: Some(4000),
+            all_data: vec![Sample::new(10000, 0x4000)],
+            smp_mod: Some(1),
+            gm_identity: Some(gm_identity),
+        };
+        let pdu = decode_smv(&frame, 22).unwrap();
+        assert_eq!(pdu.no_asdu, 1);
+        assert_eq!(pdu.sav_asdu, vec![expected]);
+    }
+
+    #[test]
+    fn test_decode_refr_tm_with_wrong_length() {
+        // svID, smpCnt, confRev, refrTm with 7 bytes, smpSynch, empty sample block
+        let asdu = [
+            0x80, 0x04, b'M', b'U', b'0', b'1', 0x82, 0x02, 0x00, 0x01, 0x83, 0x01, 0x01, 0x84,
+            0x07, 0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x85, 0x01, 0x02, 0x87, 0x00,
+        ];
+        let mut sav_pdu = vec![0x60, asdu.len() as u8 + 7, 0x80, 0x01, 0x01, 0xA2];
+        sav_pdu.push(asdu.len() as u8 + 2);
+        sav_pdu.extend_from_slice(&[0x30, asdu.len() as u8]);
+        sav_pdu.extend_from_slice(&asdu);
+
+        let frame = create_frame(&sav_pdu);
+        let err = decode_smv(&frame, 22).unwrap_err();
+        assert!(format!("{:?}", err).contains("refrTm must be 8 bytes, got 7"));
+    }
+
+    #[test]
+    fn test_decode_truncated_after_sv_id() {
+        let sav_pdu = create_sav_pdu_buffer();
+        // Cut the frame right after the svID
+        let frame = create_frame(&sav_pdu[..7 + 2 + 6]);
+        assert!(decode_smv(&frame, 22).is_err());
+    }
+
+    #[test]
+    fn test_decode_zero_length_no_asdu_at_buffer_end() {
+        let frame = create_frame(&[0x60, 0x02, 0x80, 0x00]);
+        assert!(decode_smv(&frame, 22).is_err());
+    }
+
+    #[test]
+    fn test_decode_smv_populates_pdu() {
+        // Sequence of ASDU after savPdu tag/length and noASDU
+        let asdus = &create_sav_pdu_buffer()[5..];
+        // savPdu with security field and the simulation bit set in reserved 1
+        let mut content = vec![0x80, 0x01, 0x01, 0x81, 0x03, 0xAA, 0xBB, 0xCC];
+        content.extend_from_slice(asdus);
+        let mut sav_pdu = vec![0x60, content.len() as u8];
+        sav_pdu.extend_from_slice(&content);
+        let mut frame = create_frame(&sav_pdu);
+        frame[18] = 0x80;
+
+        let pdu = decode_smv(&frame, 22).unwrap();
+
+        assert!(pdu.sim);
+        assert_eq!(pdu.no_asdu, 1);
+        assert_eq!(pdu.security, Some(vec![0xAA, 0xBB, 0xCC]));
+        assert_eq!(pdu.sav_asdu.len(), 1);
+        assert_eq!(pdu.sav_asdu[0].msv_id, "MU01");
+        assert_eq!(pdu.sav_asdu[0].smp_cnt, 256);
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 8);
+    }
+
+    #[test]
+    fn test_decode_smv_nth_asdu() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let asdus: Vec<SavAsdu> = (0..8)
+            .map(|i| SavAsdu {
+                msv_id: format!("MU{:02}", i),
+                smp_cnt: 100 + i as u16,
+                conf_rev: 1,
+                smp_synch: 2,
+                all_data: vec![Sample::new(i * 1000, 0), Sample::new(-i * 1000, 0)],
+                ..Default::default()
+            })
+            .collect();
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 8,
+            security: None,
+            sav_asdu: asdus.clone(),
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+
+        let asdu = decode_smv_nth_asdu(&frame, 22, 5).unwrap();
+        assert_eq!(asdu.msv_id, "MU05");
+        assert_eq!(asdu.smp_cnt, 105);
+        assert_eq!(
+            asdu.all_data,
+            vec![Sample::new(5000, 0), Sample::new(-5000, 0)]
+        );
+        assert_eq!(asdu, asdus[5]);
+
+        assert_eq!(decode_smv_nth_asdu(&frame, 22, 0).unwrap(), asdus[0]);
+        assert_eq!(decode_smv_nth_asdu(&frame, 22, 7).unwrap(), asdus[7]);
+        assert!(decode_smv_nth_asdu(&frame, 22, 8).is_err());
+    }
+
+    #[test]
+    fn test_decode_smv_nth_asdu_view() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let asdus: Vec<SavAsdu> = (0..2)
+            .map(|i| SavAsdu {
+                msv_id: format!("MU{:02}", i),
+                dat_set: Some("IED1/LLN0$PhsMeas1".to_string()),
+                smp_cnt: 4000 + i as u16,
+                conf_rev: 7,
+                refr_tm: Some([0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x0a]),
+                smp_synch: 2,
+                smp_rate: Some(4000),
+                all_data: vec![Sample::new(i * 10, 0), Sample::new(-i * 10, 0x4000)],
+                smp_mod: Some(1),
+                gm_identity: Some([0x00, 0x1b, 0x19, 0xff, 0xfe, 0x00, 0x00, 0x01]),
+            })
+            .collect();
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 2,
+            security: None,
+            sav_asdu: asdus.clone(),
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+
+        let view = decode_smv_nth_asdu_view(&frame, 22, 1).unwrap();
+        assert_eq!(view.msv_id, "MU01");
+        assert_eq!(view.dat_set, Some("IED1/LLN0$PhsMeas1"));
+        assert_eq!(view.smp_cnt, 4001);
+        // The svID borrows from the frame
+        let frame_range = frame.as_ptr_range();
+        assert!(frame_range.contains(&view.msv_id.as_ptr()));
+        assert_eq!(view.into_owned().unwrap(), asdus[1]);
+
+        assert_eq!(
+            decode_smv_nth_asdu_view(&frame, 22, 0)
+                .unwrap()
+                .into_owned()
+                .unwrap(),
+            decode_smv_nth_asdu(&frame, 22, 0).unwrap()
+        );
+        assert!(decode_smv_nth_asdu_view(&frame, 22, 2).is_err());
+
+        // An ASDU length that cuts the samples is detected
+        let minimal = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                all_data: vec![Sample::new(1, 0)],
+                ..Default::default()
+            }],
+        };
+        let mut frame = encode_smv(&header, &minimal).unwrap();
+        // savPdu, noASDU, seqASDU, then the ASDU length
+        frame[22 + 2 + 3 + 3] -= 1;
+        assert!(decode_smv_nth_asdu_view(&frame, 22, 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_smv_batch() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let pdus: Vec<SavPdu> = [1u16, 3, 2]
+            .iter()
+            .map(|&no_asdu| SavPdu {
+                sim: false,
+                no_asdu,
+                security: None,
+                sav_asdu: (0..no_asdu)
+                    .map(|i| SavAsdu {
+                        msv_id: "MU01".to_string(),
+                        smp_cnt: i,
+                        conf_rev: 1,
+                        all_data: vec![Sample::new(i as i32, 0); no_asdu as usize],
+                        ..Default::default()
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        assert!(encode_smv(&header, &pdus[0]).unwrap().len() < 60);
+
+        let mut buffer = Vec::new();
+        let mut offsets = Vec::new();
+        for pdu in &pdus {
+            let mut frame = encode_smv(&header, pdu).unwrap();
+            // Short frames are padded to the Ethernet minimum, as the first one
+            frame.resize(frame.len().max(60), 0);
+            offsets.push(buffer.len());
+            buffer.extend_from_slice(&frame);
+        }
+
+        let decoded: Vec<(usize, SavPdu)> =
+            decode_smv_batch(&buffer).collect::<Result<_, _>>().unwrap();
+        assert_eq!(decoded.len(), 3);
+        for ((offset, pdu), (expected_offset, expected)) in
+            decoded.iter().zip(offsets.iter().zip(&pdus))
+        {
+            assert_eq!(offset, expected_offset);
+            assert_eq!(pdu, expected);
+        }
+
+        // A truncated last frame ends the batch with an error at its offset
+        buffer.truncate(buffer.len() - 5);
+        let results: Vec<_> = decode_smv_batch(&buffer).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[..2].iter().all(|result| result.is_ok()));
+        assert_eq!(results[2].as_ref().unwrap_err().buffer_index, offsets[2]);
+
+        assert_eq!(decode_smv_batch(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_sample_iter_matches_decode_savs() {
+        let buffer = create_test_data_buffer();
+        let (_, expected) = decode_block(&buffer, MAX_SAMPLES).unwrap();
+
+        let samples: Vec<Sample> = SampleIter::new(&buffer, 0, buffer.len())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(samples, expected);
+
+        // A sample cut by the end of the block is reported once, then iteration ends
+        let mut iter = SampleIter::new(&buffer, 0, buffer.len() - 1);
+        assert_eq!(iter.by_ref().take_while(|s| s.is_ok()).count(), 7);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_smv_samples() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let asdus: Vec<SavAsdu> = (0..3)
+            .map(|i| SavAsdu {
+                msv_id: format!("MU{:02}", i),
+                dat_set: Some("IED1/LLN0$PhsMeas1".to_string()),
+                smp_cnt: i as u16,
+                conf_rev: 1,
+                refr_tm: Some([0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x0a]),
+                smp_synch: 2,
+                smp_rate: Some(4000),
+                all_data: vec![
+                    Sample::new(i * 1000, 0),
+                    Sample::new_float(0.5, 0x4000),
+                    Sample::new(-i * 1000, 0),
+                ],
+                ..Default::default()
+            })
+            .collect();
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 3,
+            security: None,
+            sav_asdu: asdus.clone(),
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+
+        for (n, asdu) in asdus.iter().enumerate() {
+            let samples: Vec<Sample> = smv_samples(&frame, 22, n)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(samples, asdu.all_data);
+        }
+        let sum: i32 = smv_samples(&frame, 22, 2)
+            .unwrap()
+            .filter_map(|sample| match sample.unwrap().value {
+                SampleValue::Int(value) => Some(value),
+                SampleValue::Float(_) => None,
+            })
+            .sum();
+        assert_eq!(sum, 0);
+        assert!(smv_samples(&frame, 22, 3).is_err());
+    }
+
+    #[test]
+    fn test_decode_truncated_frames_never_panic() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let asdu = SavAsdu {
+            msv_id: "MU01".to_string(),
+            dat_set: Some("IED1/LLN0$PhsMeas1".to_string()),
+            smp_cnt: 1,
+            conf_rev: 1,
+            refr_tm: Some([0x65, 0x4a, 0x2c, 0x80, 0x12, 0x34, 0x56, 0x0a]),
+            smp_synch: 2,
+            smp_rate: Some(4000),
+            all_data: vec![Sample::new(10000, 0), Sample::new(-10000, 0x4000)],
+            smp_mod: Some(0),
+            gm_identity: Some([0x00, 0x1b, 0x19, 0xff, 0xfe, 0x00, 0x00, 0x01]),
+        };
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 2,
+            security: Some(vec![0xAA; 4]),
+            sav_asdu: vec![asdu.clone(), asdu],
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+
+        for end in 0..frame.len() {
+            let truncated = &frame[..end];
+            let result = std::panic::catch_unwind(|| {
+                let _ = decode_smv(truncated, 22);
+                let _ = decode_smv_nth_asdu(truncated, 22, 1);
+            });
+            assert!(
+                result.is_ok(),
+                "decoding panicked when truncated to {} bytes",
+                end
+            );
+        }
+        assert_eq!(decode_smv(&frame, 22).unwrap(), pdu);
+    }
+
+    #[test]
+    fn test_decode_no_asdu_exceeds_sequence() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let asdu = SavAsdu {
+            msv_id: "MU01".to_string(),
+            all_data: vec![Sample::new(1, 0)],
+            ..Default::default()
+        };
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 2,
+            security: None,
+            sav_asdu: vec![asdu.clone(), asdu],
+        };
+        let mut frame = encode_smv(&header, &pdu).unwrap();
+        // The encoder refuses inconsistent PDUs, patch the noASDU value instead
+        assert_eq!(frame[24..27], [0x80, 0x01, 0x02]);
+        frame[26] = 3;
+
+        let err = decode_smv(&frame, 22).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("noASDU is 3, but the sequence of ASDU holds only 2"));
+    }
+
+    #[test]
+    fn test_decode_asdu_exceeds_sequence() {
+        let mut sav_pdu = create_sav_pdu_buffer();
+        // Sequence of ASDU one byte shorter than the ASDU it contains
+        assert_eq!(sav_pdu[5], 0xA2);
+        sav_pdu[6] -= 1;
+        let frame = create_frame(&sav_pdu);
+
+        let err = decode_smv(&frame, 22).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("ASDU 0 exceeds the sequence of ASDU"));
+    }
+
+    #[test]
+    fn test_decode_unexpected_asdu_tag() {
+        let mut sav_pdu = create_sav_pdu_buffer();
+        assert_eq!(sav_pdu[7], 0x30);
+        sav_pdu[7] = 0x31;
+        let frame = create_frame(&sav_pdu);
+
+        let err = decode_smv(&frame, 22).unwrap_err();
+        assert!(err.message.starts_with("Expected ASDU tag 0x30, got 0x31"));
+        assert_eq!(err.buffer_index, 22 + 7);
+
+        let options = DecodeOptions {
+            mode: DecodeMode::Lenient,
+            ..Default::default()
+        };
+        let (pdu, warnings) = decode_smv_with_options(&frame, 22, &options).unwrap();
+        assert_eq!(pdu.sav_asdu[0].msv_id, "MU01");
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 8);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_find_sav_pdu_with_extra_reserved_bytes() {
+        let sav_pdu = create_sav_pdu_buffer();
+        let standard = create_frame(&sav_pdu);
+        assert_eq!(find_sav_pdu(&standard).unwrap(), 22);
+
+        let mut frame = standard.clone();
+        frame.splice(22..22, [0x00, 0x00]);
+
+        let pos = find_sav_pdu(&frame).unwrap();
+        assert_eq!(pos, 24);
+        assert_eq!(
+            decode_smv(&frame, pos).unwrap(),
+            decode_smv(&standard, 22).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_find_sav_pdu_outside_window() {
+        let mut frame = create_frame(&create_sav_pdu_buffer());
+        frame.splice(22..22, [0x00; MAX_EXTRA_HEADER_BYTES + 1]);
+        assert!(find_sav_pdu(&frame).is_err());
+
+        assert!(find_sav_pdu(&frame[..20]).is_err());
+    }
+
+    #[test]
+    fn test_decode_wrapped_sav_pdu() {
+        let sav_pdu = create_sav_pdu_buffer();
+        let plain = decode_smv(&create_frame(&sav_pdu), 22).expect("Decoding plain savPdu failed");
+        assert_eq!(plain.sav_asdu[0].msv_id, "MU01");
+        assert_eq!(plain.sav_asdu[0].smp_cnt, 256);
+        assert_eq!(plain.sav_asdu[0].all_data.len(), 8);
+
+        // savPdu wrapped in one extra constructed application tag
+        let mut wrapped = vec![0x61, 0x81, sav_pdu.len() as u8];
+        wrapped.extend_from_slice(&sav_pdu);
+
+        let decoded =
+            decode_smv(&create_frame(&wrapped), 22).expect("Decoding wrapped savPdu failed");
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn test_decode_wrapped_sav_pdu_depth_limit() {
+        let mut buffer = create_sav_pdu_buffer();
+        for _ in 0..=MAX_WRAPPER_DEPTH {
+            let mut wrapped = vec![0xA0, 0x81, buffer.len() as u8];
+            wrapped.extend_from_slice(&buffer);
+            buffer = wrapped;
+        }
+        assert!(decode_smv(&create_frame(&buffer), 22).is_err());
+
+        // A primitive element in front of the savPdu is not a wrapper
+        let mut buffer = vec![0x80, 0x01, 0x00];
+        buffer.extend_from_slice(&create_sav_pdu_buffer());
+        assert!(decode_smv(&create_frame(&buffer), 22).is_err());
+    }
+
     #[test]
     fn test_is_smv_frame_no_vlan() {
         let frame = vec![
@@ -807,22 +2296,92 @@ mod tests {
         assert!(!is_smv_frame(&frame));
     }
 
+    fn security_frame(security: Vec<u8>) -> Vec<u8> {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x02; 6],
+            [0x40, 0x00],
+        );
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: Some(security),
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                all_data: vec![Sample::new(1, 0)],
+                ..Default::default()
+            }],
+        };
+        encode_smv(&header, &pdu).unwrap()
+    }
+
     #[test]
-    fn test_decode_sim_bit() {
-        // Without VLAN, SIM bit not set
-        let mut frame = vec![
-            0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, // dst
-            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src
-            0x88, 0xba, // EtherType
-            0x40, 0x00, // APPID
-            0x00, 0x64, // Length
-            0x00, 0x00, // Reserved 1 (SIM bit = 0)
+    fn test_decode_smv_with_signed_security() {
+        let mut security = vec![0x80, 0x01, 0x05, 0x81, 0x81, 0x80];
+        security.extend((0..128).map(|i| i as u8));
+        let frame = security_frame(security.clone());
+
+        let (pdu, info) = decode_smv_with_security(&frame, 22).unwrap();
+        assert_eq!(pdu.security, Some(security));
+        let info = info.unwrap();
+        assert_eq!(info.algorithm, 5);
+        assert_eq!(
+            info.signature,
+            (0..128).map(|i| i as u8).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_decode_smv_with_raw_security() {
+        for security in [
+            vec![0x00; 10],
+            // Algorithm longer than one byte
+            vec![0x80, 0x02, 0x00, 0x05, 0x81, 0x01, 0xaa],
+            // Bytes after the signature
+            vec![0x80, 0x01, 0x05, 0x81, 0x01, 0xaa, 0x00],
+            // Signature length beyond the field
+            vec![0x80, 0x01, 0x05, 0x81, 0x04, 0xaa],
+        ] {
+            let frame = security_frame(security.clone());
+            let (pdu, info) = decode_smv_with_security(&frame, 22).unwrap();
+            assert_eq!(pdu.security, Some(security));
+            assert_eq!(info, None);
+        }
+
+        let frame = security_frame(vec![]);
+        let (pdu, info) = decode_smv_with_security(&frame, 22).unwrap();
+        assert_eq!(pdu.security, Some(vec![]));
+        assert_eq!(info, None);
+    }
+
+    #[test]
+    fn test_smv_appid_no_vlan() {
+        let frame = vec![
+            0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, // dst MAC
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src MAC
+            0x88, 0xba, // EtherType = SMV
+            0x40, 0x01, // APPID
         ];
 
-        assert_eq!(decode_sim_bit(&frame), Some(false));
+        assert_eq!(smv_appid(&frame), Some(0x4001));
+        assert_eq!(smv_appid(&frame[..15]), None);
+    }
 
-        // Set SIM bit (MSB of reserved 1)
-        frame[18] = 0x80;
-        assert_eq!(decode_sim_bit(&frame), Some(true));
+    #[test]
+    fn test_smv_appid_with_vlan() {
+        let frame = vec![
+            0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, // dst MAC
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // src MAC
+            0x81, 0x00, // VLAN TPID
+            0x80, 0x64, // VLAN TCI
+            0x88, 0xba, // EtherType = SMV
+            0x40, 0x02, // APPID
+        ];
+
+        assert_eq!(smv_appid(&frame), Some(0x4002));
+
+        let mut goose = frame.clone();
+        goose[17] = 0xb8;
+        assert_eq!(smv_appid(&goose), None);
     }
 }
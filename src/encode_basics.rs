@@ -0,0 +1,276 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::constants::MIN_FRAME_LENGTH;
+use crate::types::{EncodeOptions, EthernetHeader};
+
+pub fn encode_ethernet_header(header: &EthernetHeader, length: u16) -> Vec<u8> {
+    // init buffer
+    let mut buffer = vec![0u8; ethernet_header_length(header)];
+    write_ethernet_header(&mut buffer, header, length, false);
+    buffer
+}
+
+/// Length of the encoded Ethernet header including APPID, length and reserved fields
+pub(crate) fn ethernet_header_length(header: &EthernetHeader) -> usize {
+    if header.tpid.is_some() && header.tci.is_some() {
+        26
+    } else {
+        22
+    }
+}
+
+/// Writes the Ethernet header to the start of `buffer` and returns its length.
+/// `simulation` sets the SIM bit, the most significant bit of reserved 1.
+pub(crate) fn write_ethernet_header(
+    buffer: &mut [u8],
+    header: &EthernetHeader,
+    length: u16,
+    simulation: bool,
+) -> usize {
+    let mut new_pos: usize = 0;
+    // Destination MAC address (6 bytes)
+    buffer[new_pos..new_pos + 6].copy_from_slice(&header.dst_addr);
+    new_pos += 6;
+
+    // Source MAC address (6 bytes)
+    buffer[new_pos..new_pos + 6].copy_from_slice(&header.src_addr);
+    new_pos += 6;
+
+    // VLAN tag (TPID and TCI) is optional
+    if let (Some(tpid), Some(tci)) = (&header.tpid, &header.tci) {
+        // Write TPID (2 bytes)
+        buffer[new_pos..new_pos + 2].copy_from_slice(tpid);
+        new_pos += 2;
+        // Write TCI (2 bytes)
+        buffer[new_pos..new_pos + 2].copy_from_slice(tci);
+        new_pos += 2;
+    }
+
+    // EtherType is fixed to 0x88B8 for GOOSE
+    buffer[new_pos..new_pos + 2].copy_from_slice(&header.ether_type);
+    new_pos += 2;
+
+    // APPID (2 bytes)
+    buffer[new_pos..new_pos + 2].copy_from_slice(&header.appid);
+    new_pos += 2;
+
+    // Length (2 bytes)
+    buffer[new_pos..new_pos + 2].copy_from_slice(&length.to_be_bytes());
+    new_pos += 2;
+
+    // Reserved 1 (2 bytes, 0 apart from the SIM bit)
+    let reserved1 = if simulation { [0x80, 0x00] } else { [0; 2] };
+    buffer[new_pos..new_pos + 2].copy_from_slice(&reserved1);
+    new_pos += 2;
+
+    // Reserved 2 (2 bytes, set to 0)
+    buffer[new_pos..new_pos + 2].copy_from_slice(&[0; 2]);
+    new_pos += 2;
+
+    new_pos
+}
+
+/// Computes the Ethernet frame check sequence, the CRC-32 of IEEE 802.3 (reflected
+/// polynomial 0xEDB88320), over `frame` from the destination address onwards.
+///
+/// The encoders leave the FCS out as most network interfaces append it on
+/// transmission. It is only needed when a frame is written somewhere that expects it,
+/// such as a pcap file with FCS or a raw socket that sends the bytes as they are.
+pub fn ethernet_fcs(frame: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in frame {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Appends the FCS of `frame` as computed by `ethernet_fcs`. The FCS is transmitted
+/// least significant byte first.
+pub fn append_fcs(frame: &mut Vec<u8>) {
+    let fcs = ethernet_fcs(frame);
+    frame.extend_from_slice(&fcs.to_le_bytes());
+}
+
+/// Zero-pads `frame` to the minimum Ethernet frame length of 60 bytes, excluding the
+/// FCS. Longer frames are left unchanged. The length field of the header does not
+/// count the padding, so decoders skip it.
+pub fn pad_to_min_frame(frame: &mut Vec<u8>) {
+    if frame.len() < MIN_FRAME_LENGTH {
+        frame.resize(MIN_FRAME_LENGTH, 0);
+    }
+}
+
+/// Applies the padding and FCS requested by `options` to an encoded frame
+pub(crate) fn finish_frame(frame: &mut Vec<u8>, options: &EncodeOptions) {
+    if options.pad_to_min_frame {
+        pad_to_min_frame(frame);
+    }
+    if options.append_fcs {
+        append_fcs(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ethernet_fcs() {
+        // Check value of CRC-32/IEEE 802.3
+        assert_eq!(ethernet_fcs(b"123456789"), 0xcbf4_3926);
+        assert_eq!(ethernet_fcs(&[]), 0);
+
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01],
+            [0x02; 6],
+            [0x00, 0x01],
+        );
+        let mut frame = encode_ethernet_header(&header, 8);
+        let length = frame.len();
+        append_fcs(&mut frame);
+        assert_eq!(frame.len(), length + 4);
+        assert_eq!(ethernet_fcs(&frame[..length]), 0xba3c_dc2e);
+        assert_eq!(frame[length..], [0x2e, 0xdc, 0x3c, 0xba]);
+        // The CRC over a frame including its FCS is the constant residue
+        assert_eq!(ethernet_fcs(&frame), 0x2144_df1c);
+    }
+
+    #[test]
+    fn test_encode_ethernet_header_without_vlan() {
+        let header = EthernetHeader {
+            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            tpid: None,
+            tci: None,
+            ether_type: [0x88, 0xB8],
+            appid: [0x10, 0x01],
+            length: [0x00, 0x00], // Not used in encoding, passed as parameter
+        };
+
+        let length: u16 = 140;
+        let encoded = encode_ethernet_header(&header, length);
+
+        let expected: &[u8] = &[
+            // Destination MAC
+            0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01, // Source MAC
+            0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C, // EtherType
+            0x88, 0xB8, // APPID
+            0x10, 0x01, // Length
+            0x00, 0x8C, // 140 in hex
+            // Reserved1
+            0x00, 0x00, // Reserved2
+            0x00, 0x00,
+        ];
+
+        assert_eq!(
+            encoded.len(),
+            22,
+            "Ethernet header without VLAN should be 22 bytes"
+        );
+        assert_eq!(
+            encoded, expected,
+            "Encoded Ethernet header does not match expected"
+        );
+    }
+
+    #[test]
+    fn test_encode_ethernet_header_with_vlan() {
+        let header = EthernetHeader {
+            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            tpid: Some([0x81, 0x00]),
+            tci: Some([0x00, 0x01]),
+            ether_type: [0x88, 0xB8],
+            appid: [0x10, 0x01],
+            length: [0x00, 0x00], // Not used in encoding
+        };
+
+        let length: u16 = 140;
+        let encoded = encode_ethernet_header(&header, length);
+
+        let expected: &[u8] = &[
+            // Destination MAC
+            0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01, // Source MAC
+            0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C, // VLAN TPID
+            0x81, 0x00, // VLAN TCI
+            0x00, 0x01, // EtherType
+            0x88, 0xB8, // APPID
+            0x10, 0x01, // Length
+            0x00, 0x8C, // 140 in hex
+            // Reserved1
+            0x00, 0x00, // Reserved2
+            0x00, 0x00,
+        ];
+
+        assert_eq!(
+            encoded.len(),
+            26,
+            "Ethernet header with VLAN should be 26 bytes"
+        );
+        assert_eq!(
+            encoded, expected,
+            "Encoded Ethernet header with VLAN does not match expected"
+        );
+    }
+
+    #[test]
+    fn test_encode_ethernet_header_length_field() {
+        let header = EthernetHeader {
+            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            tpid: Some([0x81, 0x00]),
+            tci: Some([0x00, 0x01]),
+            ether_type: [0x88, 0xB8],
+            appid: [0x10, 0x01],
+            length: [0x00, 0x00],
+        };
+
+        // Test different length values
+        let test_lengths = vec![
+            (140u16, [0x00, 0x8C]),
+            (256u16, [0x01, 0x00]),
+            (1500u16, [0x05, 0xDC]),
+        ];
+
+        for (length, expected_bytes) in test_lengths {
+            let encoded = encode_ethernet_header(&header, length);
+
+            // Length field is at positions 20-21 (with VLAN)
+            assert_eq!(
+                &encoded[20..22],
+                &expected_bytes,
+                "Length field mismatch for length {}",
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_ethernet_header_reserved_fields() {
+        let header = EthernetHeader {
+            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            tpid: Some([0x81, 0x00]),
+            tci: Some([0x00, 0x01]),
+            ether_type: [0x88, 0xB8],
+            appid: [0x10, 0x01],
+            length: [0x00, 0x00],
+        };
+
+        let encoded = encode_ethernet_header(&header, 140);
+
+        // Check Reserved1 (positions 22-23)
+        assert_eq!(&encoded[22..24], &[0x00, 0x00], "Reserved1 should be zero");
+
+        // Check Reserved2 (positions 24-25)
+        assert_eq!(&encoded[24..26], &[0x00, 0x00], "Reserved2 should be zero");
+    }
+}
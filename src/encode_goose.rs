@@ -1,76 +1,196 @@
 use rasn::ber::encode;
 
+use crate::constants::{ETHERTYPE_GOOSE, ETHERTYPE_GSE};
+use crate::encode_basics::{ethernet_header_length, finish_frame, write_ethernet_header};
 use crate::types::*;
 
-pub fn encode_ethernet_header(header: &EthernetHeader, length: u16) -> Vec<u8> {
-    // init buffer
-    let buffer_len: usize = if header.tpid.is_some() && header.tci.is_some() {
-        26
-    } else {
-        22
-    };
-    let mut buffer = vec![0u8; buffer_len];
-
-    let mut new_pos: usize = 0;
-    // Destination MAC address (6 bytes)
-    buffer[new_pos..new_pos + 6].copy_from_slice(&header.dst_addr);
-    new_pos += 6;
-
-    // Source MAC address (6 bytes)
-    buffer[new_pos..new_pos + 6].copy_from_slice(&header.src_addr);
-    new_pos += 6;
-
-    // VLAN tag (TPID and TCI) is optional
-    if let (Some(tpid), Some(tci)) = (&header.tpid, &header.tci) {
-        // Write TPID (2 bytes)
-        buffer[new_pos..new_pos + 2].copy_from_slice(tpid);
-        new_pos += 2;
-        // Write TCI (2 bytes)
-        buffer[new_pos..new_pos + 2].copy_from_slice(tci);
-        new_pos += 2;
-    }
+// Re-export encode_ethernet_header for backward compatibility, the other frame helpers
+// are in encode_basics
+pub use crate::encode_basics::encode_ethernet_header;
 
-    // EtherType is fixed to 0x88B8 for GOOSE
-    buffer[new_pos..new_pos + 2].copy_from_slice(&header.ether_type);
-    new_pos += 2;
+pub fn encode_goose(header: &EthernetHeader, pdu: &IECGoosePdu) -> Result<Vec<u8>, EncodeError> {
+    debug_assert!(
+        header.ether_type == ETHERTYPE_GOOSE || header.ether_type == ETHERTYPE_GSE,
+        "EtherType of a GOOSE frame must be 0x88B8 or 0x88B9"
+    );
 
-    // APPID (2 bytes)
-    buffer[new_pos..new_pos + 2].copy_from_slice(&header.appid);
-    new_pos += 2;
+    // Encode the GOOSE PDU using rasn
+    let pdu_bytes = encode(&IECGoosePduRasn::from(pdu))
+        .map_err(|e| EncodeError::new(&format!("Failed to encode GOOSE PDU: {:?}", e), 0))?;
 
-    // Length (2 bytes)
-    buffer[new_pos..new_pos + 2].copy_from_slice(&length.to_be_bytes());
-    new_pos += 2;
+    // calculate length based in pdu_bytes
+    let length = pdu_bytes.len() as u16 + 8; // 8 bytes for APPID, length, reserved1, reserved2 and 4 bytes for Ethernet header fields
+    let mut ether_buffer = vec![0u8; ethernet_header_length(header)];
+    write_ethernet_header(&mut ether_buffer, header, length, pdu.simulation);
 
-    // Reserved 1 (2 bytes, set to 0)
-    buffer[new_pos..new_pos + 2].copy_from_slice(&[0; 2]);
-    new_pos += 2;
+    // Combine Ethernet header and GOOSE PDU into a single buffer
+    Ok([ether_buffer, pdu_bytes].concat())
+}
 
-    // Reserved 2 (2 bytes, set to 0)
-    buffer[new_pos..new_pos + 2].copy_from_slice(&[0; 2]);
+/// Encodes a complete GOOSE frame like `encode_goose`, then pads it to the minimum
+/// frame length and appends the FCS as selected by `options`.
+pub fn encode_goose_with_options(
+    header: &EthernetHeader,
+    pdu: &IECGoosePdu,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut frame = encode_goose(header, pdu)?;
+    finish_frame(&mut frame, options);
+    Ok(frame)
+}
 
-    buffer
+/// Encodes a complete GOOSE frame like `encode_goose` after checking the PDU with
+/// `IECGoosePdu::validate_for_encode`.
+///
+/// # Returns
+/// The frame, or an EncodeError describing the first inconsistency of the PDU
+pub fn encode_goose_checked(
+    header: &EthernetHeader,
+    pdu: &IECGoosePdu,
+) -> Result<Vec<u8>, EncodeError> {
+    pdu.validate_for_encode()?;
+    encode_goose(header, pdu)
 }
 
-pub fn encode_goose(header: &EthernetHeader, pdu: &IECGoosePdu) -> Result<Vec<u8>, EncodeError> {
-    // Encode the GOOSE PDU using rasn
+/// Encodes a complete GOOSE frame into a caller-provided buffer.
+///
+/// Unlike `encode_goose` the frame is not collected in a new `Vec`, so a publisher can
+/// reuse one buffer for every frame. Bytes after the frame are left untouched.
+///
+/// This is not allocation free: the PDU is still encoded by rasn into a temporary `Vec`
+/// on every call and then copied behind the Ethernet header.
+///
+/// # Parameters
+/// - `header`: The Ethernet header to use
+/// - `pdu`: The GOOSE PDU to encode
+/// - `buffer`: Preallocated buffer to write into
+///
+/// # Returns
+/// The number of bytes written, or `EncodeError::BufferTooSmall` if the frame does not
+/// fit into `buffer`
+pub fn encode_goose_into(
+    header: &EthernetHeader,
+    pdu: &IECGoosePdu,
+    buffer: &mut [u8],
+) -> Result<usize, EncodeError> {
+    debug_assert!(
+        header.ether_type == ETHERTYPE_GOOSE || header.ether_type == ETHERTYPE_GSE,
+        "EtherType of a GOOSE frame must be 0x88B8 or 0x88B9"
+    );
+
     let pdu_bytes = encode(&IECGoosePduRasn::from(pdu))
         .map_err(|e| EncodeError::new(&format!("Failed to encode GOOSE PDU: {:?}", e), 0))?;
 
-    // calculate length based in pdu_bytes
-    let length = pdu_bytes.len() as u16 + 8; // 8 bytes for APPID, length, reserved1, reserved2 and 4 bytes for Ethernet header fields
-    let ether_buffer = encode_ethernet_header(header, length);
+    let required_size = ethernet_header_length(header) + pdu_bytes.len();
+    if buffer.len() < required_size {
+        return Err(EncodeError::BufferTooSmall {
+            required: required_size,
+            available: buffer.len(),
+        });
+    }
 
-    // Combine Ethernet header and GOOSE PDU into a single buffer
-    Ok([ether_buffer, pdu_bytes].concat())
+    let pos = write_ethernet_header(buffer, header, pdu_bytes.len() as u16 + 8, pdu.simulation);
+    buffer[pos..required_size].copy_from_slice(&pdu_bytes);
+    Ok(required_size)
+}
+
+/// Creates the sequence of GOOSE PDUs a publisher sends after a state change.
+///
+/// The state number is incremented once over `base` (rolling over to 1), the sequence
+/// number starts at 0 and increments with every repetition. All frames carry the
+/// event time `t` of `base`, as the timestamp marks the last state change. The time
+/// allowed to live of each frame is twice the interval to the next one according to
+/// `schedule`.
+///
+/// # Parameters
+/// - `base`: The PDU carrying the new data set and the time of the event.
+/// - `schedule`: The retransmission timing of the publisher.
+/// - `count`: The number of frames to generate.
+///
+/// # Returns
+/// The PDUs in transmission order.
+pub fn goose_event_burst(
+    base: &IECGoosePdu,
+    schedule: &RetransmissionSchedule,
+    count: usize,
+) -> Vec<IECGoosePdu> {
+    let st_num = next_counter(base.st_num);
+
+    (0..count)
+        .map(|i| {
+            let sq_num = i as u32;
+            let mut pdu = base.clone();
+            pdu.st_num = st_num;
+            pdu.sq_num = sq_num;
+            pdu.time_allowed_to_live = schedule.interval_ms(sq_num).saturating_mul(2);
+            pdu
+        })
+        .collect()
+}
+
+/// Builds the data set entry of a single point status (SPS/SPC) with the
+/// conventional layout `{stVal, q, t}`.
+///
+/// # Parameters
+/// - `value`: The status value `stVal`.
+/// - `quality`: The quality `q` of the value.
+/// - `t`: The time of the last change of the value.
+///
+/// # Returns
+/// The structure to use as entry of `all_data`.
+pub fn goose_spc(value: bool, quality: Quality, t: Timestamp) -> IECData {
+    IECData::Structure(vec![
+        IECData::Boolean(value),
+        quality_bit_string(&quality),
+        IECData::Timestamp(t),
+    ])
+}
+
+/// Builds the data set entry of a measured value (MV) with the conventional
+/// layout `{mag {f}, q, t}`.
+///
+/// # Parameters
+/// - `value`: The floating point magnitude `mag.f`, encoded as FLOAT32.
+/// - `quality`: The quality `q` of the value.
+/// - `t`: The time of the last change of the value.
+///
+/// # Returns
+/// The structure to use as entry of `all_data`.
+pub fn goose_mv(value: f32, quality: Quality, t: Timestamp) -> IECData {
+    IECData::Structure(vec![
+        IECData::Structure(vec![IECData::Float32(value)]),
+        quality_bit_string(&quality),
+        IECData::Timestamp(t),
+    ])
+}
+
+/// The 13-bit quality `q`, taken from the top bits of `Quality::to_u16`
+fn quality_bit_string(quality: &Quality) -> IECData {
+    IECData::BitString(format!("{:016b}", quality.to_u16())[..13].to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decode_goose::decode_goose_pdu;
 
     #[test]
-    fn test_encode_ethernet_header_without_vlan() {
+    fn test_encode_goose_checked_rejects_invalid_pdu() {
+        let header =
+            EthernetHeader::for_goose([0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01], [0; 6], [0, 1]);
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![IECData::Boolean(true)];
+
+        let err = encode_goose_checked(&header, &pdu).unwrap_err();
+        assert!(err.to_string().contains("numDatSetEntries is 0"), "{}", err);
+        pdu.num_dat_set_entries = 1;
+        pdu.go_id.clear();
+        let err = encode_goose_checked(&header, &pdu).unwrap_err();
+        assert!(err.to_string().contains("goID is empty"), "{}", err);
+    }
+
+    #[test]
+    fn test_encode_goose_new_pdu() {
         let header = EthernetHeader {
             dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
             src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
@@ -78,126 +198,192 @@ mod tests {
             tci: None,
             ether_type: [0x88, 0xB8],
             appid: [0x10, 0x01],
-            length: [0x00, 0x00], // Not used in encoding, passed as parameter
+            length: [0x00, 0x00],
         };
 
-        let length: u16 = 140;
-        let encoded = encode_ethernet_header(&header, length);
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        assert_eq!(pdu.time_allowed_to_live, 2000);
+        assert_eq!(pdu.st_num, 0);
+        assert_eq!(pdu.sq_num, 0);
+        assert!(pdu.all_data.is_empty());
+        assert!(pdu.t.seconds > 0);
+
+        // Empty data set as constructed
+        assert!(encode_goose(&header, &pdu).is_ok());
+
+        // After filling in the data set
+        pdu.all_data = vec![IECData::Boolean(true), IECData::Int(-5)];
+        pdu.num_dat_set_entries = pdu.all_data.len() as u32;
+        let encoded = encode_goose(&header, &pdu).expect("Encoding failed");
+        assert_eq!(&encoded[12..14], &[0x88, 0xB8]);
+    }
 
-        let expected: &[u8] = &[
-            // Destination MAC
-            0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01, // Source MAC
-            0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C, // EtherType
-            0x88, 0xB8, // APPID
-            0x10, 0x01, // Length
-            0x00, 0x8C, // 140 in hex
-            // Reserved1
-            0x00, 0x00, // Reserved2
-            0x00, 0x00,
+    #[test]
+    fn test_roundtrip_octet_string_data_set_entry() {
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            [0x10, 0x01],
+        );
+        let raw: Vec<u8> = (0..20u8).map(|i| i.wrapping_mul(37) ^ 0xA5).collect();
+
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![
+            IECData::OctetString(hex::encode(&raw)),
+            IECData::Boolean(true),
         ];
+        pdu.num_dat_set_entries = pdu.all_data.len() as u32;
 
-        assert_eq!(
-            encoded.len(),
-            22,
-            "Ethernet header without VLAN should be 22 bytes"
-        );
-        assert_eq!(
-            encoded, expected,
-            "Encoded Ethernet header does not match expected"
-        );
+        let encoded = encode_goose(&header, &pdu).unwrap();
+
+        // Raw bytes on the wire, tagged as octet-string (0x89)
+        let mut element = vec![0x89, raw.len() as u8];
+        element.extend_from_slice(&raw);
+        assert!(encoded
+            .windows(element.len())
+            .any(|w| w == element.as_slice()));
+
+        let decoded = decode_goose_pdu(&encoded, 22).unwrap();
+        assert_eq!(decoded.all_data, pdu.all_data);
+        assert_eq!(encode_goose(&header, &decoded).unwrap(), encoded);
     }
 
     #[test]
-    fn test_encode_ethernet_header_with_vlan() {
-        let header = EthernetHeader {
-            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
-            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
-            tpid: Some([0x81, 0x00]),
-            tci: Some([0x00, 0x01]),
-            ether_type: [0x88, 0xB8],
-            appid: [0x10, 0x01],
-            length: [0x00, 0x00], // Not used in encoding
-        };
+    fn test_simulation_bit_roundtrip() {
+        use crate::decode_basics::decode_sim_bit;
 
-        let length: u16 = 140;
-        let encoded = encode_ethernet_header(&header, length);
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            [0x10, 0x01],
+        );
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![IECData::Boolean(true)];
+        pdu.num_dat_set_entries = 1;
+
+        let encoded = encode_goose(&header, &pdu).unwrap();
+        assert_eq!(decode_sim_bit(&encoded), Some(false));
+
+        pdu.simulation = true;
+        let encoded = encode_goose(&header, &pdu).unwrap();
+        assert_eq!(&encoded[18..20], &[0x80, 0x00]);
+        assert_eq!(decode_sim_bit(&encoded), Some(true));
+        assert!(decode_goose_pdu(&encoded, 22).unwrap().simulation);
+    }
 
-        let expected: &[u8] = &[
-            // Destination MAC
-            0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01, // Source MAC
-            0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C, // VLAN TPID
-            0x81, 0x00, // VLAN TCI
-            0x00, 0x01, // EtherType
-            0x88, 0xB8, // APPID
-            0x10, 0x01, // Length
-            0x00, 0x8C, // 140 in hex
-            // Reserved1
-            0x00, 0x00, // Reserved2
-            0x00, 0x00,
-        ];
+    #[test]
+    fn test_encode_goose_into_reused_buffer() {
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            [0x10, 0x01],
+        );
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![IECData::Boolean(true), IECData::Int(-5)];
+        pdu.num_dat_set_entries = 2;
 
+        let mut buffer = [0xAAu8; 1518];
+        let first = encode_goose_into(&header, &pdu, &mut buffer).unwrap();
         assert_eq!(
-            encoded.len(),
-            26,
-            "Ethernet header with VLAN should be 26 bytes"
+            &buffer[..first],
+            encode_goose(&header, &pdu).unwrap().as_slice()
         );
+
+        // A shorter frame into the same buffer leaves the tail of the first one
+        pdu.all_data = vec![IECData::Boolean(false)];
+        pdu.num_dat_set_entries = 1;
+        pdu.sq_num = 1;
+        let second = encode_goose_into(&header, &pdu, &mut buffer).unwrap();
+        assert!(second < first);
         assert_eq!(
-            encoded, expected,
-            "Encoded Ethernet header with VLAN does not match expected"
+            &buffer[..second],
+            encode_goose(&header, &pdu).unwrap().as_slice()
         );
+
+        let mut small = [0u8; 40];
+        assert!(matches!(
+            encode_goose_into(&header, &pdu, &mut small),
+            Err(EncodeError::BufferTooSmall { required, available: 40 }) if required == second
+        ));
     }
 
     #[test]
-    fn test_encode_ethernet_header_length_field() {
-        let header = EthernetHeader {
-            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
-            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
-            tpid: Some([0x81, 0x00]),
-            tci: Some([0x00, 0x01]),
-            ether_type: [0x88, 0xB8],
-            appid: [0x10, 0x01],
-            length: [0x00, 0x00],
-        };
+    fn test_roundtrip_float64_data_set_entry() {
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            [0x10, 0x01],
+        );
 
-        // Test different length values
-        let test_lengths = vec![
-            (140u16, [0x00, 0x8C]),
-            (256u16, [0x01, 0x00]),
-            (1500u16, [0x05, 0xDC]),
-        ];
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![IECData::Float(3.141592653589793)];
+        pdu.num_dat_set_entries = 1;
 
-        for (length, expected_bytes) in test_lengths {
-            let encoded = encode_ethernet_header(&header, length);
+        let encoded = encode_goose(&header, &pdu).unwrap();
 
-            // Length field is at positions 20-21 (with VLAN)
-            assert_eq!(
-                &encoded[20..22],
-                &expected_bytes,
-                "Length field mismatch for length {}",
-                length
-            );
-        }
+        // Tag 0x87, length 9, exponent width 11, IEEE-754 double
+        let mut element = vec![0x87, 0x09, 0x0B];
+        element.extend_from_slice(&3.141592653589793f64.to_be_bytes());
+        assert!(encoded
+            .windows(element.len())
+            .any(|w| w == element.as_slice()));
+
+        let decoded = decode_goose_pdu(&encoded, 22).unwrap();
+        assert_eq!(decoded.all_data, pdu.all_data);
     }
 
     #[test]
-    fn test_encode_ethernet_header_reserved_fields() {
-        let header = EthernetHeader {
-            dst_addr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
-            src_addr: [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
-            tpid: Some([0x81, 0x00]),
-            tci: Some([0x00, 0x01]),
-            ether_type: [0x88, 0xB8],
-            appid: [0x10, 0x01],
-            length: [0x00, 0x00],
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "EtherType of a GOOSE frame must be 0x88B8 or 0x88B9")]
+    fn test_encode_goose_rejects_smv_ether_type() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01],
+            [0x00, 0x1A, 0xB6, 0x03, 0x2F, 0x1C],
+            [0x10, 0x01],
+        );
+        let pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+
+        let _ = encode_goose(&header, &pdu);
+    }
+
+    #[test]
+    fn test_goose_event_burst() {
+        let mut base = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        base.st_num = 7;
+        base.sq_num = 123;
+        base.all_data = vec![IECData::Boolean(true)];
+        base.num_dat_set_entries = 1;
+
+        let schedule = RetransmissionSchedule {
+            min_interval_ms: 2,
+            max_interval_ms: 1000,
         };
+        let burst = goose_event_burst(&base, &schedule, 12);
+
+        assert_eq!(burst.len(), 12);
+        assert_eq!(burst[0].st_num, 8);
+        assert_eq!(burst[0].sq_num, 0);
+        for (i, pdu) in burst.iter().enumerate() {
+            assert_eq!(pdu.st_num, 8);
+            assert_eq!(pdu.sq_num, i as u32);
+            assert_eq!(pdu.t, base.t);
+            assert_eq!(pdu.all_data, base.all_data);
+        }
 
-        let encoded = encode_ethernet_header(&header, 140);
+        // TAL follows the retransmission interval until the heartbeat is reached
+        assert_eq!(burst[0].time_allowed_to_live, 4);
+        assert_eq!(burst[1].time_allowed_to_live, 8);
+        assert_eq!(burst[11].time_allowed_to_live, 2000);
+    }
 
-        // Check Reserved1 (positions 22-23)
-        assert_eq!(&encoded[22..24], &[0x00, 0x00], "Reserved1 should be zero");
+    #[test]
+    fn test_goose_event_burst_st_num_roll_over() {
+        let mut base = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        base.st_num = u32::MAX;
 
-        // Check Reserved2 (positions 24-25)
-        assert_eq!(&encoded[24..26], &[0x00, 0x00], "Reserved2 should be zero");
+        let burst = goose_event_burst(&base, &RetransmissionSchedule::default(), 2);
+        assert_eq!(burst[0].st_num, 1);
+        assert_eq!(burst[1].sq_num, 1);
     }
 
     #[test]
@@ -267,4 +453,82 @@ mod tests {
             "Encoded buffer does not match expected output"
         );
     }
+
+    fn event_time() -> Timestamp {
+        Timestamp {
+            seconds: 1_700_000_000,
+            fraction: 0x800000,
+            quality: TimeQuality::default(),
+        }
+    }
+
+    #[test]
+    fn test_goose_spc_layout() {
+        let quality = Quality {
+            validity: Validity::Questionable,
+            test: true,
+            ..Default::default()
+        };
+
+        let entry = goose_spc(true, quality, event_time());
+
+        assert_eq!(
+            entry,
+            IECData::Structure(vec![
+                IECData::Boolean(true),
+                IECData::BitString("1100000000010".to_string()),
+                IECData::Timestamp(event_time()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_goose_mv_layout() {
+        let entry = goose_mv(230.5, Quality::default(), event_time());
+
+        assert_eq!(
+            entry,
+            IECData::Structure(vec![
+                IECData::Structure(vec![IECData::Float32(230.5)]),
+                IECData::BitString("0000000000000".to_string()),
+                IECData::Timestamp(event_time()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_goose_mv_encoding() {
+        let entry = goose_mv(230.5, Quality::default(), event_time());
+
+        let encoded = rasn::ber::encode(&IECDataRasn::from(&entry)).unwrap();
+
+        // mag {f} as FLOAT32: exponent width 8 followed by the IEEE 754 single
+        let mut mag = vec![0xa2, 0x07, 0x87, 0x05, 0x08];
+        mag.extend_from_slice(&230.5f32.to_be_bytes());
+        assert_eq!(&encoded[2..11], mag.as_slice());
+        // q as 13-bit BIT STRING with 3 unused bits
+        assert_eq!(&encoded[11..16], &[0x84, 0x03, 0x03, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_goose_spc_quality_roundtrip() {
+        let quality = Quality {
+            validity: Validity::Invalid,
+            failure: true,
+            ..Default::default()
+        };
+
+        let IECData::Structure(fields) = goose_spc(false, quality, event_time()) else {
+            panic!("Expected a structure");
+        };
+        let IECDataRasn::BitString(bits) = IECDataRasn::from(&fields[1]) else {
+            panic!("Expected a bit string");
+        };
+        let raw = bits.as_raw_slice();
+
+        assert_eq!(
+            Quality::from_u16(u16::from_be_bytes([raw[0], raw[1]])),
+            quality
+        );
+    }
 }
@@ -1,41 +1,52 @@
-use crate::types::{EncodeError, EthernetHeader, Sample, SavAsdu, SavPdu};
-
-/// Calculates the encoded length of an unsigned integer value
-/// Takes into account the extra 0x00 byte needed when MSB is set
-fn unsigned_integer_length(value: &[u8]) -> usize {
-    // For unsigned integers, strip leading zeros (but not 0xFF like signed integers)
-    let mut start = 0;
-    while start < value.len() - 1 && value[start] == 0x00 {
-        start += 1;
-    }
-    let minimal = &value[start..];
-
-    // If MSB is set, we need to prepend a zero byte
-    if !minimal.is_empty() && (minimal[0] & 0x80) != 0 {
-        minimal.len() + 1
-    } else {
-        minimal.len()
-    }
-}
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ber::{
+    encode_ber, encode_integer, encode_octet_string, encode_string, encode_tag_length,
+    encode_unsigned_integer, size_length, unsigned_integer_length,
+};
+use crate::constants::ETHERTYPE_SMV;
+use crate::encode_basics::finish_frame;
+use crate::types::{
+    ChannelMap, EncodeError, EncodeOptions, EthernetHeader, Quality, Sample, SampleValue, SavAsdu,
+    SavPdu,
+};
+
+/// Exponent width descriptor of an IEC 61850 FLOAT32 (8 bit exponent)
+pub(crate) const FLOAT32_EXPONENT_WIDTH: u8 = 0x08;
+/// Content length of an encoded FLOAT32: descriptor plus 4 bytes IEEE-754
+pub(crate) const FLOAT32_LENGTH: usize = 5;
 
 /// Calculates the encoded length of a single sample (value + quality)
 /// Returns the size in bytes without the wrapper tag/length
 fn sample_length(sample: &Sample) -> usize {
     let mut length = 0;
 
-    // Value (tag 0x83 + length + data)
-    let value_len = if sample.value >= -128 && sample.value <= 127 {
-        1
-    } else if sample.value >= -32768 && sample.value <= 32767 {
-        2
-    } else if sample.value >= -8388608 && sample.value <= 8388607 {
-        3
-    } else {
-        4
-    };
-    length += 1; // tag
-    length += 1; // length field (always 1 byte for small integers)
-    length += value_len;
+    match sample.value {
+        SampleValue::Int(value) => {
+            // Value (tag 0x83 + length + data)
+            let value_len = if (-128..=127).contains(&value) {
+                1
+            } else if (-32768..=32767).contains(&value) {
+                2
+            } else if (-8388608..=8388607).contains(&value) {
+                3
+            } else {
+                4
+            };
+            length += 1; // tag
+            length += 1; // length field (always 1 byte for small integers)
+            length += value_len;
+        }
+        SampleValue::Float(_) => {
+            // Value (tag 0x87 + length + exponent width descriptor + 4 bytes IEEE-754)
+            length += 1; // tag
+            length += 1; // length field
+            length += FLOAT32_LENGTH;
+        }
+    }
 
     // Quality (tag 0x84 + length + data)
     // BIT STRING: 1 byte unused bits + 2 bytes quality = 3 bytes
@@ -58,11 +69,11 @@ fn asdu_length(asdu: &SavAsdu) -> usize {
     let mut length = 0;
 
     // svID (tag 0x80)
-    length += 1 + 1 + asdu.msv_id.len(); // tag + length + value
+    length += 1 + size_length(asdu.msv_id.len()) + asdu.msv_id.len(); // tag + length + value
 
     // datSet (optional, tag 0x81)
     if let Some(ref dat_set) = asdu.dat_set {
-        length += 1 + 1 + dat_set.len();
+        length += 1 + size_length(dat_set.len()) + dat_set.len();
     }
 
     // smpCnt (tag 0x82) - Unsigned16
@@ -137,13 +148,38 @@ fn pdu_length(pdu: &SavPdu) -> usize {
 
 /// Calculates the required buffer size for encoding an SMV packet
 ///
+/// The size is exactly the number of bytes `encode_smv_into` writes, so a publisher
+/// can allocate its buffer once for the largest PDU it sends.
+///
 /// # Parameters
 /// - `header`: The Ethernet header
 /// - `pdu`: The SavPdu to encode
 ///
 /// # Returns
 /// The total size in bytes needed for the complete packet
-fn smv_size(header: &EthernetHeader, pdu: &SavPdu) -> usize {
+///
+/// # Example
+/// ```
+/// use iec_61850::encode_smv::{encode_smv_into, smv_size};
+/// use iec_61850::types::{EthernetHeader, Sample, SavAsdu, SavPdu};
+///
+/// let h = EthernetHeader::for_smv([0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01], [0x02; 6], [0x40, 0x00]);
+/// let pdu = SavPdu {
+///     sim: false,
+///     no_asdu: 1,
+///     security: None,
+///     sav_asdu: vec![SavAsdu {
+///         msv_id: "MU01".to_string(),
+///         all_data: vec![Sample::new(1000, 0); 8],
+///         ..Default::default()
+///     }],
+/// };
+///
+/// let mut buf = vec![0u8; smv_size(&h, &pdu)];
+/// let written = encode_smv_into(&h, &pdu, &mut buf).unwrap();
+/// assert_eq!(written, buf.len());
+/// ```
+pub fn smv_size(header: &EthernetHeader, pdu: &SavPdu) -> usize {
     // Ethernet header size
     let header_size = if header.tpid.is_some() && header.tci.is_some() {
         26 // With VLAN: 6 (dst) + 6 (src) + 4 (VLAN) + 2 (type) + 2 (appid) + 2 (length) + 4 (reserved)
@@ -159,251 +195,21 @@ fn smv_size(header: &EthernetHeader, pdu: &SavPdu) -> usize {
     header_size + total_pdu_size
 }
 
-/// Returns the number of bytes required to encode the length field in ASN.1 BER format.
-///
-/// This function determines how many bytes are needed to represent the given length value
-/// according to BER rules:
-/// - 1 byte for values < 128 (short form)
-/// - 2 bytes for values < 256 (0x81 + 1 byte)
-/// - 3 bytes for values < 65536 (0x82 + 2 bytes)
-/// - 4 bytes for larger values (0x83 + 3 bytes)
-fn size_length(value: usize) -> usize {
-    if value < 128 {
-        1
-    } else if value < 256 {
-        2
-    } else if value < 65535 {
-        3
-    } else {
-        4
-    }
-}
-
-/// Encodes an ASN.1 BER element (tag, length, value) into the buffer at the given position.
-///
-/// # Parameters
-/// - `tag`: The ASN.1 tag to write.
-/// - `value`: The value bytes to encode.
-/// - `buffer`: The output buffer.
-/// - `buffer_index`: The position in the buffer to start writing.
-///
-/// # Returns
-/// Result with the new position in the buffer after writing, or EncodeError.
-fn encode_ber(
-    tag: u8,
-    value: &[u8],
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    // Calculate required space: tag + length + value
-    let length_field_size = size_length(value.len());
-    let total_size = 1 + length_field_size + value.len();
-
-    if buffer.len() < buffer_index + total_size {
-        return Err(EncodeError::new(
-            "Buffer does not have enough capacity to encode the BER element.",
-            buffer_index,
-        ));
-    }
-
-    // Write tag and length
-    let mut pos = encode_tag_length(tag, value.len(), buffer, buffer_index)?;
-    // Write value
-    buffer[pos..pos + value.len()].copy_from_slice(value);
-    pos += value.len();
-
-    Ok(pos)
-}
-
-/// Returns the minimal two's complement representation of a signed integer as a byte slice.
-/// This is used for ASN.1 BER INTEGER encoding.
-fn minimal_twos_complement_bytes(value: &[u8]) -> &[u8] {
-    let mut significant_start = 0;
-    while significant_start < value.len() - 1 {
-        let curr = value[significant_start];
-        let next = value[significant_start + 1];
-        if (curr == 0x00 && (next & 0x80) == 0) || (curr == 0xFF && (next & 0x80) == 0x80) {
-            significant_start += 1;
-        } else {
-            break;
+/// Encodes a sample (value + quality) as ASN.1 BER sequence
+fn encode_sample(buffer: &mut [u8], mut pos: usize, sample: &Sample) -> Result<usize, EncodeError> {
+    match sample.value {
+        // Encode value as signed INTEGER using encode_integer_32
+        SampleValue::Int(value) => {
+            pos = encode_integer(0x83, &value.to_be_bytes(), buffer, pos)?;
         }
-    }
-    &value[significant_start..]
-}
-
-/// Encodes an unsigned integer in ASN.1 BER format with a leading zero byte to ensure positive interpretation.
-///
-/// # Parameters
-/// - `tag`: A `u8` representing the ASN.1 tag for the unsigned integer type.
-/// - `value`: A slice of `u8` containing the unsigned integer in big-endian format.
-/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
-/// - `buffer_index`: The starting position in the buffer to write the encoded data.
-///
-/// # Returns
-/// Result with the new position in the buffer after writing the encoded unsigned integer, or EncodeError.
-fn encode_unsigned_integer(
-    tag: u8,
-    value: &[u8],
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    // For unsigned integers, strip leading zeros but NOT leading 0xFF bytes
-    // (unlike signed integers where 0xFF is sign extension)
-    let mut start = 0;
-    while start < value.len() - 1 && value[start] == 0x00 {
-        start += 1;
-    }
-    let minimal = &value[start..];
-
-    // If MSB is set, prepend a zero byte to ensure positive interpretation
-    if !minimal.is_empty() && (minimal[0] & 0x80) != 0 {
-        let mut prepend = [0u8; 1 + 8];
-        prepend[0] = 0x00;
-        prepend[1..1 + minimal.len()].copy_from_slice(minimal);
-        encode_ber(tag, &prepend[..1 + minimal.len()], buffer, buffer_index)
-    } else {
-        encode_ber(tag, minimal, buffer, buffer_index)
-    }
-}
-
-/// Encodes an integer in minimal two's complement form according to ASN.1 BER.
-///
-/// # Parameters
-/// - `tag`: The ASN.1 tag for the integer type.
-/// - `value`: The integer as a big-endian byte slice (with possible leading sign extension bytes).
-/// - `buffer`: The output buffer.
-/// - `buffer_index`: Where to start writing in the buffer.
-///
-/// # Returns
-/// The new position in the buffer after writing the encoded integer.
-fn encode_integer(
-    tag: u8,
-    value: &[u8],
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    encode_ber(
-        tag,
-        minimal_twos_complement_bytes(value),
-        buffer,
-        buffer_index,
-    )
-}
-
-/// Encodes an ASN.1 octet string using BER rules.
-///
-/// # Parameters
-/// - `tag`: The ASN.1 tag to write.
-/// - `value`: A slice of `u8` containing the octet string to encode.
-/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
-/// - `buffer_index`: The starting position in the buffer to write the encoded data.
-///
-/// # Returns
-/// Result with the new position in the buffer after writing the encoded octet string, or EncodeError.
-fn encode_octet_string(
-    tag: u8,
-    value: &[u8],
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    encode_ber(tag, value, buffer, buffer_index)
-}
-
-/// # Parameters
-/// - `tag`: A `u8` representing the tag to be written to the buffer.
-/// - `value`: A `String` reference containing the string to encode.
-/// - `buffer`: A mutable slice of `u8` where the encoded data will be written.
-/// - `buffer_index`: The starting position in the buffer to write the encoded data.
-///
-/// # Returns
-/// Result with the new position in the buffer after writing the encoded string, or EncodeError.
-fn encode_string(
-    tag: u8,
-    value: &str,
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    let bytes = value.as_bytes();
-    encode_ber(tag, bytes, buffer, buffer_index)
-}
-
-/// Encodes an ASN.1 tag and its length field using BER rules.
-///
-/// This function writes the tag and the length field into the provided buffer at `buffer_index`.
-/// The length is encoded in short or long form depending on its value:
-/// - For values < 128, the length is encoded in a single byte.
-/// - For values < 256, the length is encoded as 0x81 followed by one byte.
-/// - For values < 65536, the length is encoded as 0x82 followed by two bytes (big-endian).
-/// - For larger values, the length is encoded as 0x83 followed by three bytes (big-endian).
-///
-/// # Parameters
-/// - `tag`: The ASN.1 tag to write.
-/// - `value`: The length value to encode.
-/// - `buffer`: The output buffer.
-/// - `buffer_index`: The position in the buffer to start writing.
-///
-/// # Returns
-/// Result with the new position in the buffer after writing the tag and length, or EncodeError.
-fn encode_tag_length(
-    tag: u8,
-    value: usize,
-    buffer: &mut [u8],
-    buffer_index: usize,
-) -> Result<usize, EncodeError> {
-    let required = 1 + size_length(value); // 1 for tag, rest for length field
-
-    if buffer.len() < buffer_index + required {
-        return Err(EncodeError::new(
-            "Buffer too small to write tag and length.",
-            buffer_index,
-        ));
-    }
-
-    let mut new_pos = buffer_index;
-    buffer[new_pos] = tag;
-    new_pos += 1;
-
-    // Now encode the length field as before
-    if value < 128 {
-        buffer[new_pos] = value as u8;
-        new_pos += 1;
-    } else if value < 256 {
-        buffer[new_pos] = 0x81;
-        new_pos += 1;
-        buffer[new_pos] = value as u8;
-        new_pos += 1;
-    } else if value < 65535 {
-        buffer[new_pos] = 0x82;
-        new_pos += 1;
-        buffer[new_pos] = (value >> 8) as u8;
-        new_pos += 1;
-        buffer[new_pos] = (value & 0xff) as u8;
-        new_pos += 1;
-    } else {
-        if value >= 1 << 24 {
-            return Err(EncodeError::new(
-                "Value exceeds the maximum range for three-byte encoding (2^24 - 1).",
-                buffer_index,
-            ));
+        // Encode value as FLOAT32: exponent width descriptor followed by the IEEE-754 bytes
+        SampleValue::Float(value) => {
+            let mut float_data = [FLOAT32_EXPONENT_WIDTH; FLOAT32_LENGTH];
+            float_data[1..].copy_from_slice(&value.to_be_bytes());
+            pos = encode_ber(0x87, &float_data, buffer, pos)?;
         }
-        buffer[new_pos] = 0x83;
-        new_pos += 1;
-        buffer[new_pos] = (value >> 16) as u8;
-        new_pos += 1;
-        buffer[new_pos] = ((value >> 8) & 0xff) as u8;
-        new_pos += 1;
-        buffer[new_pos] = (value & 0xff) as u8;
-        new_pos += 1;
     }
 
-    Ok(new_pos)
-}
-
-/// Encodes a sample (value + quality) as ASN.1 BER sequence
-fn encode_sample(buffer: &mut [u8], mut pos: usize, sample: &Sample) -> Result<usize, EncodeError> {
-    // Encode value as signed INTEGER using encode_integer_32
-    pos = encode_integer(0x83, &sample.value.to_be_bytes(), buffer, pos)?;
-
     // Encode quality as BIT STRING (tag 0x84)
     let quality_u16 = sample.quality.to_u16();
     let quality_bytes = quality_u16.to_be_bytes();
@@ -572,7 +378,11 @@ fn encode_ethernet_header(buffer: &mut [u8], header: &EthernetHeader, length: u1
 /// Encodes a complete SMV packet including Ethernet header with preallocated buffer
 ///
 /// This version writes directly to the buffer using encode_*_buff functions,
-/// avoiding all intermediate allocations.
+/// avoiding all intermediate allocations, so a publisher can encode into a fixed
+/// stack array, or reuse one buffer for every frame of a stream. The buffer must hold
+/// at least `smv_size(header, pdu)` bytes, otherwise `EncodeError::BufferTooSmall` is
+/// returned and nothing is written. A `no_asdu` that does not match the number of
+/// ASDUs is rejected as well.
 ///
 /// # Parameters
 /// - `header`: The Ethernet header to use
@@ -581,11 +391,27 @@ fn encode_ethernet_header(buffer: &mut [u8], header: &EthernetHeader, length: u1
 ///
 /// # Returns
 /// The number of bytes written, or an EncodeError if encoding fails
-fn encode_smv_into(
+pub fn encode_smv_into(
     header: &EthernetHeader,
     pdu: &SavPdu,
     buffer: &mut [u8],
 ) -> Result<usize, EncodeError> {
+    debug_assert_eq!(
+        header.ether_type, ETHERTYPE_SMV,
+        "EtherType of a Sampled Values frame must be 0x88BA"
+    );
+
+    if pdu.no_asdu as usize != pdu.sav_asdu.len() {
+        return Err(EncodeError::new(
+            &format!(
+                "noASDU is {}, but the PDU holds {} ASDUs",
+                pdu.no_asdu,
+                pdu.sav_asdu.len()
+            ),
+            0,
+        ));
+    }
+
     let required_size = smv_size(header, pdu);
 
     if buffer.len() < required_size {
@@ -631,7 +457,8 @@ fn encode_smv_into(
 /// - `pdu`: The SavPdu to encode
 ///
 /// # Returns
-/// The encoded packet as a byte vector, or an EncodeError if encoding fails
+/// The encoded packet as a byte vector, or an EncodeError if encoding fails, e.g.
+/// when `no_asdu` does not match the number of ASDUs
 pub fn encode_smv(header: &EthernetHeader, pdu: &SavPdu) -> Result<Vec<u8>, EncodeError> {
     let size = smv_size(header, pdu);
     let mut buffer = vec![0u8; size];
@@ -640,13 +467,111 @@ pub fn encode_smv(header: &EthernetHeader, pdu: &SavPdu) -> Result<Vec<u8>, Enco
     Ok(buffer)
 }
 
+/// Encodes a complete SMV packet like `encode_smv`, then pads it to the minimum frame
+/// length and appends the FCS as selected by `options`.
+pub fn encode_smv_with_options(
+    header: &EthernetHeader,
+    pdu: &SavPdu,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut frame = encode_smv(header, pdu)?;
+    finish_frame(&mut frame, options);
+    Ok(frame)
+}
+
+/// Encodes a single-ASDU SMV frame from physical channel values
+///
+/// # Parameters
+/// - `header`: The Ethernet header to use
+/// - `svid`: The svID of the stream
+/// - `smp_cnt`: The sample count of the ASDU
+/// - `map`: The mapping of the physical values to sample values
+/// - `values`: One physical value per channel of `map`
+/// - `quality`: The quality of all samples
+///
+/// # Returns
+/// The encoded packet as a byte vector, or an EncodeError if encoding fails
+pub fn publish_sv_frame(
+    header: &EthernetHeader,
+    svid: &str,
+    smp_cnt: u16,
+    map: &ChannelMap,
+    values: &[f32],
+    quality: Quality,
+) -> Result<Vec<u8>, EncodeError> {
+    let pdu = SavPdu {
+        sim: false,
+        no_asdu: 1,
+        security: None,
+        sav_asdu: vec![SavAsdu {
+            msv_id: svid.to_string(),
+            smp_cnt,
+            conf_rev: 1,
+            all_data: map.to_samples(values, quality)?,
+            ..Default::default()
+        }],
+    };
+
+    encode_smv(header, &pdu)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::decode_basics::decode_ethernet_header;
     use crate::decode_smv::decode_smv;
+    use crate::encode_basics::pad_to_min_frame;
     use crate::types::Sample;
 
+    #[test]
+    fn test_encode_smv_padded_to_min_frame() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x02; 6],
+            [0x40, 0x00],
+        );
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU".to_string(),
+                all_data: vec![Sample::new(1, 0)],
+                ..Default::default()
+            }],
+        };
+
+        let exact = encode_smv(&header, &pdu).unwrap();
+        assert!(exact.len() < 60);
+
+        let options = EncodeOptions {
+            pad_to_min_frame: true,
+            append_fcs: false,
+        };
+        let padded = encode_smv_with_options(&header, &pdu, &options).unwrap();
+        assert_eq!(padded.len(), 60);
+        assert_eq!(padded[..exact.len()], exact[..]);
+        assert!(padded[exact.len()..].iter().all(|&b| b == 0));
+        assert_eq!(decode_smv(&padded, 22).unwrap(), pdu);
+
+        let options = EncodeOptions {
+            pad_to_min_frame: true,
+            append_fcs: true,
+        };
+        let framed = encode_smv_with_options(&header, &pdu, &options).unwrap();
+        assert_eq!(framed.len(), 64);
+        assert_eq!(framed[..60], padded[..]);
+
+        // Frames at or above the minimum stay as they are
+        let mut long = vec![0xff; 61];
+        pad_to_min_frame(&mut long);
+        assert_eq!(long.len(), 61);
+        assert_eq!(
+            encode_smv_with_options(&header, &pdu, &EncodeOptions::default()).unwrap(),
+            exact
+        );
+    }
+
     #[test]
     fn test_encode_decode_roundtrip_simple() {
         // Create a simple SMV packet
@@ -1185,6 +1110,269 @@ mod tests {
         assert!(result.is_ok(), "Decoding failed: {:?}", result.err());
     }
 
+    #[test]
+    fn test_roundtrip_security_lengths() {
+        // 130 and 300 bytes force long form length fields (0x81 0x82, 0x82 0x01 0x2c)
+        // for the security element
+        for security_len in [0usize, 3, 8, 130, 300] {
+            let header = EthernetHeader {
+                dst_addr: [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+                src_addr: [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+                tpid: None,
+                tci: None,
+                ether_type: [0x88, 0xba],
+                appid: [0x40, 0x01],
+                length: [0x00, 0x00],
+            };
+
+            let pdu = SavPdu {
+                sim: false,
+                no_asdu: 1,
+                security: Some((0..security_len).map(|i| i as u8).collect()),
+                sav_asdu: vec![SavAsdu {
+                    msv_id: "SEC_TEST".to_string(),
+                    dat_set: None,
+                    smp_cnt: 75,
+                    conf_rev: 1,
+                    refr_tm: None,
+                    smp_synch: 1,
+                    smp_rate: Some(4000),
+                    all_data: vec![Sample::new(750, 0x0000), Sample::new(-750, 0x4000)],
+                    smp_mod: None,
+                    gm_identity: None,
+                }],
+            };
+
+            let encoded = encode_smv(&header, &pdu).expect("Encoding failed");
+            assert_eq!(encoded.len(), smv_size(&header, &pdu));
+
+            let mut decoded_header = EthernetHeader::default();
+            let pos = decode_ethernet_header(&mut decoded_header, &encoded);
+            let decoded = decode_smv(&encoded, pos)
+                .unwrap_or_else(|e| panic!("Decoding failed for {} bytes: {:?}", security_len, e));
+
+            assert_eq!(decoded, pdu, "Mismatch for {} security bytes", security_len);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_long_sv_id_and_dat_set() {
+        let header = EthernetHeader {
+            dst_addr: [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            src_addr: [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            tpid: None,
+            tci: None,
+            ether_type: [0x88, 0xba],
+            appid: [0x40, 0x01],
+            length: [0x00, 0x00],
+        };
+
+        // 300 bytes need a 3 byte length field (0x82 0x01 0x2C), 200 bytes a 2 byte one
+        let msv_id: String = (0..300).map(|i| (b'A' + (i % 26) as u8) as char).collect();
+        let dat_set: String = "D".repeat(200);
+
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: msv_id.clone(),
+                dat_set: Some(dat_set.clone()),
+                smp_cnt: 1,
+                conf_rev: 1,
+                refr_tm: None,
+                smp_synch: 0,
+                smp_rate: None,
+                all_data: vec![Sample::new(1, 0x0000)],
+                smp_mod: None,
+                gm_identity: None,
+            }],
+        };
+
+        let encoded = encode_smv(&header, &pdu).expect("Encoding failed");
+        assert_eq!(encoded.len(), smv_size(&header, &pdu));
+
+        // Every length field must match the bytes that follow it exactly
+        // savPdu: 0x60 0x82 <2 bytes>
+        assert_eq!(&encoded[22..24], &[0x60, 0x82]);
+        let pdu_len = u16::from_be_bytes([encoded[24], encoded[25]]) as usize;
+        assert_eq!(pdu_len, encoded.len() - 26);
+        // noASDU: 0x80 0x01 0x01, then sequence of ASDU: 0xA2 0x82 <2 bytes>
+        assert_eq!(&encoded[26..31], &[0x80, 0x01, 0x01, 0xA2, 0x82]);
+        let seq_len = u16::from_be_bytes([encoded[31], encoded[32]]) as usize;
+        assert_eq!(seq_len, encoded.len() - 33);
+        // ASDU: 0x30 0x82 <2 bytes>
+        assert_eq!(&encoded[33..35], &[0x30, 0x82]);
+        let asdu_len = u16::from_be_bytes([encoded[35], encoded[36]]) as usize;
+        assert_eq!(asdu_len, encoded.len() - 37);
+        // svID: 0x80 0x82 0x01 0x2C
+        assert_eq!(&encoded[37..41], &[0x80, 0x82, 0x01, 0x2C]);
+        // datSet: 0x81 0x81 0xC8
+        assert_eq!(&encoded[341..344], &[0x81, 0x81, 0xC8]);
+
+        let mut decoded_header = EthernetHeader::default();
+        let pos = decode_ethernet_header(&mut decoded_header, &encoded);
+        let decoded = decode_smv(&encoded, pos).expect("Decoding failed");
+
+        assert_eq!(decoded.sav_asdu[0].msv_id, msv_id);
+        assert_eq!(decoded.sav_asdu[0].dat_set, Some(dat_set));
+        assert_eq!(decoded, pdu);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "EtherType of a Sampled Values frame must be 0x88BA")]
+    fn test_encode_rejects_goose_ether_type() {
+        let header = EthernetHeader::for_goose(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            [0x40, 0x01],
+        );
+
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                all_data: vec![Sample::new(1, 0x0000)],
+                ..Default::default()
+            }],
+        };
+
+        let _ = encode_smv(&header, &pdu);
+    }
+
+    #[test]
+    fn test_encode_into_stack_array() {
+        const FRAME_SIZE: usize = 66;
+
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            [0x40, 0x01],
+        );
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                smp_cnt: 1,
+                conf_rev: 1,
+                smp_synch: 2,
+                all_data: vec![Sample::new(1000, 0x0000), Sample::new(-1000, 0x0000)],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(smv_size(&header, &pdu), FRAME_SIZE);
+
+        let mut buffer = [0u8; FRAME_SIZE];
+        let written = encode_smv_into(&header, &pdu, &mut buffer).unwrap();
+        assert_eq!(written, FRAME_SIZE);
+
+        let decoded = decode_smv(&buffer, 22).unwrap();
+        assert_eq!(decoded, pdu);
+    }
+
+    #[test]
+    fn test_encode_into_reused_buffer() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            [0x40, 0x01],
+        );
+        let mut pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                smp_cnt: 1,
+                conf_rev: 1,
+                smp_synch: 2,
+                all_data: vec![Sample::new(100, 0x0000), Sample::new(200_000, 0x0000)],
+                ..Default::default()
+            }],
+        };
+
+        let mut buffer = vec![0xAA; 256];
+        let first = encode_smv_into(&header, &pdu, &mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..first],
+            encode_smv(&header, &pdu).unwrap().as_slice()
+        );
+
+        // A shorter second frame overwrites the start of the same buffer
+        pdu.sav_asdu[0].smp_cnt = 2;
+        pdu.sav_asdu[0].all_data[1] = Sample::new(-5, 0x0000);
+        let second = encode_smv_into(&header, &pdu, &mut buffer).unwrap();
+        assert!(second < first);
+        assert_eq!(
+            &buffer[..second],
+            encode_smv(&header, &pdu).unwrap().as_slice()
+        );
+        assert_eq!(decode_smv(&buffer[..second], 22).unwrap(), pdu);
+    }
+
+    #[test]
+    fn test_encode_checks_no_asdu() {
+        let header = EthernetHeader::for_smv([0x01; 6], [0x02; 6], [0x40, 0x01]);
+        let asdu = SavAsdu {
+            msv_id: "MU01".to_string(),
+            all_data: vec![Sample::new(1, 0x0000)],
+            ..Default::default()
+        };
+        let mut pdu = SavPdu {
+            sim: false,
+            no_asdu: 2,
+            security: None,
+            sav_asdu: vec![asdu.clone(), asdu],
+        };
+        let frame = encode_smv(&header, &pdu).unwrap();
+        assert_eq!(decode_smv(&frame, 22).unwrap(), pdu);
+
+        for no_asdu in [1, 3] {
+            pdu.no_asdu = no_asdu;
+            let err = encode_smv(&header, &pdu).unwrap_err();
+            assert!(
+                format!("{:?}", err)
+                    .contains(&format!("noASDU is {}, but the PDU holds 2 ASDUs", no_asdu)),
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_into_buffer_too_small() {
+        let header = EthernetHeader::for_smv([0x01; 6], [0x02; 6], [0x40, 0x01]);
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                all_data: vec![Sample::new(1, 0x0000)],
+                ..Default::default()
+            }],
+        };
+        let required = smv_size(&header, &pdu);
+
+        let mut buffer = vec![0u8; required - 1];
+        let result = encode_smv_into(&header, &pdu, &mut buffer);
+        assert!(
+            matches!(
+                result,
+                Err(EncodeError::BufferTooSmall { required: r, available: a })
+                    if r == required && a == required - 1
+            ),
+            "{:?}",
+            result
+        );
+        assert!(buffer.iter().all(|byte| *byte == 0));
+    }
+
     #[test]
     fn test_roundtrip_all_optional_fields() {
         // Test with ALL optional fields present
@@ -1421,6 +1609,71 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_float_matches_rasn_floating_point() {
+        use crate::types::{FloatingPoint, IECData, IECDataRasn};
+        use rasn::types::OctetString;
+
+        for value in [230.25f32, -0.001, 0.0, f32::MAX] {
+            let mut buffer = [0u8; 16];
+            encode_sample(&mut buffer, 0, &Sample::new_float(value, 0x0000)).unwrap();
+            assert_eq!(&buffer[..2], &[0x87, 0x05]);
+            let float_data = &buffer[2..2 + FLOAT32_LENGTH];
+
+            // Manual encoding decoded through the rasn data types
+            let rasn = IECDataRasn::Float(FloatingPoint(OctetString::from(float_data.to_vec())));
+            assert_eq!(IECData::from(&rasn), IECData::Float32(value));
+
+            // And the rasn side produces the same octets for a FLOAT32
+            assert_eq!(FloatingPoint::from_f32(value).0.as_ref(), float_data);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_mixed_int_and_float_samples() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            [0x40, 0x01],
+        );
+
+        let samples = vec![
+            Sample::new(1000, 0x0000),
+            Sample::new_float(230.25, 0x0000),
+            Sample::new(-70000, 0x4000),
+            Sample::new_float(-0.001, 0x2000),
+            Sample::new_float(f32::MAX, 0x0000),
+        ];
+        let asdu = SavAsdu {
+            msv_id: "MU01".to_string(),
+            smp_cnt: 42,
+            conf_rev: 1,
+            smp_synch: 2,
+            all_data: samples.clone(),
+            ..Default::default()
+        };
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![asdu],
+        };
+
+        let encoded = encode_smv(&header, &pdu).expect("Encoding failed");
+        assert_eq!(encoded.len(), smv_size(&header, &pdu));
+        // FLOAT32: tag 0x87, length 5, exponent width 8, IEEE-754 bytes
+        let mut float_tlv = vec![0x87, 0x05, 0x08];
+        float_tlv.extend_from_slice(&230.25f32.to_be_bytes());
+        assert!(encoded
+            .windows(float_tlv.len())
+            .any(|window| window == float_tlv.as_slice()));
+
+        let decoded = decode_smv(&encoded, 22).expect("Decoding failed");
+        assert_eq!(decoded.sav_asdu[0].all_data, samples);
+        assert_eq!(decoded.sav_asdu[0].all_data[0].value, 1000);
+    }
+
     #[test]
     fn test_roundtrip_large_packet() {
         // Test large realistic packet (8 ASDUs x 12 samples each)
@@ -1471,4 +1724,44 @@ mod tests {
         // Just verify decoding succeeds for large packet (8 ASDUs x 12 samples)
         decode_smv(&encoded, pos).expect("Decoding failed");
     }
+
+    #[test]
+    fn test_publish_sv_frame() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c],
+            [0x40, 0x01],
+        );
+        let map = ChannelMap::le_9_2();
+        let values = [
+            12.345, -12.345, 0.5, 0.0, 63508.51, -31754.25, -31754.26, 0.0,
+        ];
+
+        let frame =
+            publish_sv_frame(&header, "MU01", 42, &map, &values, Quality::default()).unwrap();
+
+        let pdu = decode_smv(&frame, 22).unwrap();
+        let asdu = &pdu.sav_asdu[0];
+        assert_eq!(asdu.msv_id, "MU01");
+        assert_eq!(asdu.smp_cnt, 42);
+        assert!(asdu.all_data.iter().all(|sample| sample.quality.is_good()));
+
+        for (decoded, value) in map.to_values(&asdu.all_data).iter().zip(values) {
+            assert!((decoded - value).abs() < 0.01, "{} != {}", decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_publish_sv_frame_channel_count_mismatch() {
+        let header = EthernetHeader::for_smv([0; 6], [0; 6], [0x40, 0x01]);
+        let result = publish_sv_frame(
+            &header,
+            "MU01",
+            0,
+            &ChannelMap::le_9_2(),
+            &[1.0, 2.0],
+            Quality::default(),
+        );
+        assert!(result.is_err());
+    }
 }
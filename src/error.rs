@@ -0,0 +1,67 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+#[derive(Debug)]
+pub enum EncodeError {
+    General {
+        message: String,
+        buffer_index: usize,
+    },
+    BufferTooSmall {
+        required: usize,
+        available: usize,
+    },
+}
+
+impl EncodeError {
+    pub fn new(msg: &str, buffer_index: usize) -> Self {
+        EncodeError::General {
+            message: msg.to_string(),
+            buffer_index,
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::General {
+                message,
+                buffer_index,
+            } => write!(f, "{} (at byte {})", message, buffer_index),
+            EncodeError::BufferTooSmall {
+                required,
+                available,
+            } => write!(
+                f,
+                "Buffer too small: {} bytes required, {} available",
+                required, available
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+#[derive(Debug)]
+pub struct DecodeError {
+    pub message: String,
+    pub buffer_index: usize,
+}
+
+impl DecodeError {
+    pub fn new(msg: &str, buffer_index: usize) -> Self {
+        DecodeError {
+            message: msg.to_string(),
+            buffer_index,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.buffer_index)
+    }
+}
+
+impl core::error::Error for DecodeError {}
@@ -1,8 +1,80 @@
+//! IEC 61850 GOOSE and Sampled Values encoding and decoding.
+//!
+//! The functions re-exported at the crate root cover the usual path: build a PDU,
+//! encode it into a frame and decode received frames. The modules hold the variants
+//! with options, limits and statistics.
+//!
+//! ```
+//! use iec_61850::types::{EthernetHeader, Sample, SavAsdu, SavPdu};
+//! use iec_61850::{decode_frame, decode_smv, encode_smv, DecodedFrame};
+//!
+//! let header = EthernetHeader::for_smv([0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01], [0x02; 6], [0x40, 0x00]);
+//! let pdu = SavPdu {
+//!     sim: false,
+//!     no_asdu: 1,
+//!     security: None,
+//!     sav_asdu: vec![SavAsdu {
+//!         msv_id: "MU01".to_string(),
+//!         all_data: vec![Sample::new(1000, 0); 8],
+//!         ..Default::default()
+//!     }],
+//! };
+//!
+//! let frame = encode_smv(&header, &pdu).unwrap();
+//! assert_eq!(decode_smv(&frame, 22).unwrap(), pdu);
+//!
+//! // Frames of unknown type are dispatched on their EtherType
+//! match decode_frame(&frame).unwrap() {
+//!     DecodedFrame::Smv(_, decoded) => assert_eq!(decoded, pdu),
+//!     _ => unreachable!(),
+//! }
+//! ```
+//!
+//! Without the default `std` feature the crate builds for `no_std` targets with an
+//! allocator: the BER primitives, the types, the Ethernet header codec and the Sampled
+//! Values codec remain, without serde support. GOOSE encoding and decoding (based on
+//! rasn), the analysis, pcap, SCL and client modules require `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod annotate;
+mod ber;
+#[cfg(feature = "pnet")]
+pub mod capture;
+#[cfg(feature = "std")]
 pub mod client;
+pub mod constants;
 pub mod decode_basics;
+#[cfg(feature = "std")]
 pub mod decode_goose;
 pub mod decode_smv;
+pub mod encode_basics;
+#[cfg(feature = "std")]
 pub mod encode_goose;
 pub mod encode_smv;
+mod error;
+#[cfg(feature = "std")]
 pub mod mms;
+#[cfg(feature = "std")]
+pub mod pcap;
+#[cfg(feature = "std")]
+pub mod replay_smv;
+#[cfg(feature = "std")]
+pub mod scl;
 pub mod types;
+
+pub use ber::{decode_tag, decode_tag_length, encode_ber, encode_tag_length};
+pub use error::{DecodeError, EncodeError};
+
+#[cfg(feature = "std")]
+pub use analysis::{decode_frame, DecodedFrame};
+#[cfg(feature = "std")]
+pub use decode_goose::decode_goose_pdu;
+pub use decode_smv::decode_smv;
+#[cfg(feature = "std")]
+pub use encode_goose::{encode_goose, encode_goose_into};
+pub use encode_smv::{encode_smv, encode_smv_into, smv_size};
@@ -1,10 +1,11 @@
 use crate::client::{DataReference, Error, Transport};
 use crate::types::{
     AddCause, AnalogueValue, BufferedReportControlBlock, CancelObject, CancelResponse, Check,
-    ControlObject, ControlResponse, CtlVal, DataDefinition, DataType, EntryTime, IECData,
-    OriginCategory, Originator, ReasonForInclusion, Report, ReportDataPoint, ReportMetadata,
-    ReportOptFields, ReportType, SetBrcbValuesSettings, SetUrcbValuesSettings, Tcmd, TimeQuality,
-    Timestamp, TriggerOptions, UnbufferedReportControlBlock, UnbufferedReportOptFields,
+    ControlObject, ControlResponse, CtlVal, DataDefinition, DataType, EntryTime, FloatingPoint,
+    IECData, OriginCategory, Originator, ReasonForInclusion, Report, ReportDataPoint,
+    ReportMetadata, ReportOptFields, ReportType, SetBrcbValuesSettings, SetUrcbValuesSettings,
+    Tcmd, TimeQuality, Timestamp, TriggerOptions, UnbufferedReportControlBlock,
+    UnbufferedReportOptFields,
 };
 use async_trait::async_trait;
 use mms::messages::iso_9506_mms_1::{AnonymousWriteResponse, TypeSpecification, UtcTime};
@@ -82,19 +83,8 @@ pub fn mms_data_to_iec(data: &Data) -> IECData {
         Data::integer(i) => IECData::Int(i64::try_from(i).unwrap_or(0)),
         Data::unsigned(u) => IECData::UInt(u64::try_from(u).unwrap_or(0)),
         Data::floating_point(fp) => {
-            let bytes = fp.0.as_ref();
             // MMS FloatingPoint: first byte is format (0x08 = FLOAT32, 0x0B = FLOAT64)
-            if bytes.len() == 5 && bytes[0] == 0x08 {
-                let value = f32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
-                IECData::Float(value as f64)
-            } else if bytes.len() == 9 && bytes[0] == 0x0B {
-                let value = f64::from_be_bytes([
-                    bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7], bytes[8],
-                ]);
-                IECData::Float(value)
-            } else {
-                IECData::Float(0.0)
-            }
+            IECData::Float(FloatingPoint(fp.0.clone()).as_f64().unwrap_or(0.0))
         }
         Data::octet_string(octets) => IECData::OctetString(hex::encode(octets.as_ref())),
         Data::visible_string(s) => IECData::VisibleString(s.to_string()),
@@ -179,12 +169,13 @@ pub fn iec_data_to_mms(data: &IECData) -> Result<Data, Error> {
         }
         IECData::Float(f) => {
             // MMS FLOAT32: first byte = 0x08 (8-bit exponent, IEEE 754 single), then 4 data bytes
-            let mut bytes = vec![0x08u8];
-            bytes.extend_from_slice(&(*f as f32).to_be_bytes());
             Ok(Data::floating_point(mms::FloatingPoint(
-                rasn::types::OctetString::from(bytes),
+                FloatingPoint::from_f32(*f as f32).0,
             )))
         }
+        IECData::Float32(f) => Ok(Data::floating_point(mms::FloatingPoint(
+            FloatingPoint::from_f32(*f).0,
+        ))),
         IECData::OctetString(hex_str) => {
             // Decode hex string to bytes
             match hex::decode(hex_str) {
@@ -704,11 +695,7 @@ fn ctl_fields_to_data(
             let mut av_fields: Vec<Data> = Vec::new();
             if let Some(f) = av.f {
                 av_fields.push(Data::floating_point(mms::FloatingPoint(
-                    rasn::types::OctetString::from({
-                        let mut b = vec![0x08u8];
-                        b.extend_from_slice(&f.to_be_bytes());
-                        b
-                    }),
+                    FloatingPoint::from_f32(f).0,
                 )));
             }
             if let Some(i) = av.i {
@@ -2025,12 +2012,7 @@ impl Transport for MmsTransport {
                         for elem in s.iter() {
                             match elem {
                                 Data::floating_point(fp) => {
-                                    let bytes = fp.0.as_ref();
-                                    if bytes.len() == 5 && bytes[0] == 0x08 {
-                                        av.f = Some(f32::from_be_bytes([
-                                            bytes[1], bytes[2], bytes[3], bytes[4],
-                                        ]));
-                                    }
+                                    av.f = FloatingPoint(fp.0.clone()).as_f32();
                                 }
                                 Data::integer(i) => {
                                     av.i = Some(i64::try_from(i).unwrap_or(0) as i32);
@@ -0,0 +1,419 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::analysis::DecodedFrame;
+use crate::types::{DecodeError, EncodeError};
+
+/// Length of the pcap global header
+const GLOBAL_HEADER_LENGTH: usize = 24;
+/// Length of the header preceding every captured frame
+const RECORD_HEADER_LENGTH: usize = 16;
+/// Link-layer header type of Ethernet frames
+const LINKTYPE_ETHERNET: u32 = 1;
+/// Maximum frame length announced in written captures
+const SNAPLEN: u32 = 65535;
+
+/// A decoded PDU together with the time it was captured
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedFrame<T> {
+    /** Capture time since the Unix epoch, as recorded in the capture file */
+    pub capture_time: Duration,
+    /** The decoded content of the frame */
+    pub pdu: T,
+}
+
+/// Iterator over the records of a classic libpcap capture held in memory
+#[derive(Debug)]
+pub struct PcapRecords<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+    nanosecond: bool,
+}
+
+impl<'a> PcapRecords<'a> {
+    /// Parses the global header of the capture.
+    ///
+    /// Microsecond and nanosecond resolution captures in either byte order are supported.
+    ///
+    /// # Parameters
+    /// - `data`: The content of the capture file.
+    ///
+    /// # Returns
+    /// The iterator over the records, or a DecodeError if the global header is invalid.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        if data.len() < GLOBAL_HEADER_LENGTH {
+            return Err(DecodeError::new(
+                "Capture is shorter than the pcap header",
+                0,
+            ));
+        }
+
+        let (big_endian, nanosecond) = match [data[0], data[1], data[2], data[3]] {
+            [0xd4, 0xc3, 0xb2, 0xa1] => (false, false),
+            [0xa1, 0xb2, 0xc3, 0xd4] => (true, false),
+            [0x4d, 0x3c, 0xb2, 0xa1] => (false, true),
+            [0xa1, 0xb2, 0x3c, 0x4d] => (true, true),
+            magic => {
+                return Err(DecodeError::new(
+                    &format!("Unknown pcap magic number {:02x?}", magic),
+                    0,
+                ))
+            }
+        };
+
+        Ok(PcapRecords {
+            data,
+            pos: GLOBAL_HEADER_LENGTH,
+            big_endian,
+            nanosecond,
+        })
+    }
+
+    fn read_u32(&self, pos: usize) -> u32 {
+        let bytes = [
+            self.data[pos],
+            self.data[pos + 1],
+            self.data[pos + 2],
+            self.data[pos + 3],
+        ];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+impl<'a> Iterator for PcapRecords<'a> {
+    /// The capture time and the raw link-layer frame
+    type Item = Result<(Duration, &'a [u8]), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+
+        let header_pos = self.pos;
+        if header_pos + RECORD_HEADER_LENGTH > self.data.len() {
+            // Stop after reporting the truncated record
+            self.pos = self.data.len();
+            return Some(Err(DecodeError::new(
+                "Truncated pcap record header",
+                header_pos,
+            )));
+        }
+
+        let seconds = self.read_u32(header_pos);
+        let fraction = self.read_u32(header_pos + 4);
+        let captured_length = self.read_u32(header_pos + 8) as usize;
+
+        let frame_pos = header_pos + RECORD_HEADER_LENGTH;
+        if frame_pos + captured_length > self.data.len() {
+            self.pos = self.data.len();
+            return Some(Err(DecodeError::new("Truncated pcap record", header_pos)));
+        }
+        self.pos = frame_pos + captured_length;
+
+        let subsec_nanos = if self.nanosecond {
+            fraction
+        } else {
+            fraction.saturating_mul(1000)
+        };
+        let capture_time =
+            Duration::new(seconds as u64, 0) + Duration::from_nanos(subsec_nanos as u64);
+
+        Some(Ok((
+            capture_time,
+            &self.data[frame_pos..frame_pos + captured_length],
+        )))
+    }
+}
+
+/// Decodes all frames of a classic libpcap capture held in memory.
+///
+/// # Parameters
+/// - `data`: The content of the capture file.
+///
+/// # Returns
+/// An iterator over the decoded frames with their capture time in capture order, or a
+/// DecodeError if the global header is invalid.
+pub fn decode_pcap(
+    data: &[u8],
+) -> Result<impl Iterator<Item = Result<TimedFrame<DecodedFrame>, DecodeError>> + '_, DecodeError> {
+    Ok(PcapRecords::new(data)?.map(|record| {
+        let (capture_time, frame) = record?;
+        Ok(TimedFrame {
+            capture_time,
+            pdu: DecodedFrame::decode(frame)?,
+        })
+    }))
+}
+
+/// Reads the raw link-layer frames of a classic libpcap capture file.
+///
+/// The frames can be passed on to `is_goose_frame`/`is_smv_frame` and the decoders.
+/// Microsecond and nanosecond resolution captures in either byte order are supported.
+///
+/// # Parameters
+/// - `path`: The path of the capture file.
+///
+/// # Returns
+/// The frames in capture order, or a DecodeError if the file cannot be read or is not
+/// a valid capture.
+pub fn read_pcap(path: impl AsRef<Path>) -> Result<Vec<Vec<u8>>, DecodeError> {
+    let path = path.as_ref();
+    let data = std::fs::read(path)
+        .map_err(|e| DecodeError::new(&format!("Failed to read {}: {}", path.display(), e), 0))?;
+
+    PcapRecords::new(&data)?
+        .map(|record| record.map(|(_, frame)| frame.to_vec()))
+        .collect()
+}
+
+/// Encodes frames as classic libpcap capture (little endian, microsecond resolution,
+/// LINKTYPE_ETHERNET).
+///
+/// # Parameters
+/// - `frames`: The capture time since the Unix epoch and the raw Ethernet frame of
+///   every record, in capture order.
+///
+/// # Returns
+/// The content of the capture file.
+pub fn encode_pcap<'a>(frames: impl IntoIterator<Item = (Duration, &'a [u8])>) -> Vec<u8> {
+    let mut data = Vec::with_capacity(GLOBAL_HEADER_LENGTH);
+    data.extend_from_slice(&[0xd4, 0xc3, 0xb2, 0xa1]);
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(&4u16.to_le_bytes());
+    data.extend_from_slice(&[0; 8]); // thiszone and sigfigs
+    data.extend_from_slice(&SNAPLEN.to_le_bytes());
+    data.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+
+    for (capture_time, frame) in frames {
+        data.extend_from_slice(&(capture_time.as_secs() as u32).to_le_bytes());
+        data.extend_from_slice(&capture_time.subsec_micros().to_le_bytes());
+        data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        data.extend_from_slice(frame);
+    }
+    data
+}
+
+/// Writes frames to a classic libpcap capture file, e.g. to inspect the output of
+/// `encode_goose` or `encode_smv` in Wireshark.
+///
+/// The first frame is stamped with the current time, every further frame one
+/// microsecond later. Use `write_pcap_timed` to supply the capture times.
+///
+/// # Parameters
+/// - `path`: The path of the capture file, replaced if it exists.
+/// - `frames`: The raw Ethernet frames.
+///
+/// # Returns
+/// An EncodeError if the file cannot be written.
+pub fn write_pcap(path: impl AsRef<Path>, frames: &[Vec<u8>]) -> Result<(), EncodeError> {
+    let start = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    write_pcap_timed(
+        path,
+        frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| (start + Duration::from_micros(i as u64), frame.as_slice())),
+    )
+}
+
+/// Writes frames with the given capture times to a classic libpcap capture file.
+///
+/// # Parameters
+/// - `path`: The path of the capture file, replaced if it exists.
+/// - `frames`: The capture time since the Unix epoch and the raw Ethernet frame of
+///   every record.
+///
+/// # Returns
+/// An EncodeError if the file cannot be written.
+pub fn write_pcap_timed<'a>(
+    path: impl AsRef<Path>,
+    frames: impl IntoIterator<Item = (Duration, &'a [u8])>,
+) -> Result<(), EncodeError> {
+    let path = path.as_ref();
+    std::fs::write(path, encode_pcap(frames))
+        .map_err(|e| EncodeError::new(&format!("Failed to write {}: {}", path.display(), e), 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_smv::encode_smv;
+    use crate::types::{EthernetHeader, Sample, SavAsdu, SavPdu};
+
+    fn smv_frame(smp_cnt: u16) -> Vec<u8> {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let pdu = SavPdu {
+            sim: false,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                smp_cnt,
+                all_data: vec![Sample::new(1000, 0)],
+                ..Default::default()
+            }],
+        };
+        encode_smv(&header, &pdu).unwrap()
+    }
+
+    fn ipv4_frame() -> Vec<u8> {
+        let mut frame = vec![0u8; 60];
+        frame[12] = 0x08;
+        frame[13] = 0x00;
+        frame
+    }
+
+    /// Builds a little endian microsecond capture
+    fn pcap(records: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0xd4, 0xc3, 0xb2, 0xa1, 0x02, 0x00, 0x04, 0x00];
+        data.extend_from_slice(&[0; 8]); // thiszone and sigfigs
+        data.extend_from_slice(&65535u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes()); // LINKTYPE_ETHERNET
+        for (seconds, micros, frame) in records {
+            data.extend_from_slice(&seconds.to_le_bytes());
+            data.extend_from_slice(&micros.to_le_bytes());
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_pcap_attaches_capture_times() {
+        let data = pcap(&[
+            (1_700_000_000, 250, smv_frame(1)),
+            (1_700_000_000, 500, ipv4_frame()),
+            (1_700_000_001, 0, smv_frame(2)),
+        ]);
+
+        let frames: Vec<TimedFrame<DecodedFrame>> =
+            decode_pcap(&data).unwrap().map(Result::unwrap).collect();
+
+        let capture_times: Vec<Duration> = frames.iter().map(|f| f.capture_time).collect();
+        assert_eq!(
+            capture_times,
+            vec![
+                Duration::new(1_700_000_000, 250_000),
+                Duration::new(1_700_000_000, 500_000),
+                Duration::new(1_700_000_001, 0),
+            ]
+        );
+
+        let smp_cnts: Vec<Option<u16>> = frames
+            .iter()
+            .map(|frame| match &frame.pdu {
+                DecodedFrame::Smv(_, pdu) => Some(pdu.sav_asdu[0].smp_cnt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(smp_cnts, vec![Some(1), None, Some(2)]);
+    }
+
+    #[test]
+    fn test_pcap_records_big_endian_nanoseconds() {
+        let frame = ipv4_frame();
+        let mut data = vec![0xa1, 0xb2, 0x3c, 0x4d, 0x00, 0x02, 0x00, 0x04];
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&65535u32.to_be_bytes());
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&10u32.to_be_bytes());
+        data.extend_from_slice(&123_456_789u32.to_be_bytes());
+        data.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        data.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        data.extend_from_slice(&frame);
+
+        let records: Vec<_> = PcapRecords::new(&data)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records, vec![(Duration::new(10, 123_456_789), &frame[..])]);
+    }
+
+    #[test]
+    fn test_read_pcap_fixture() {
+        use crate::decode_goose::{decode_goose_header, is_goose_frame};
+        use crate::decode_smv::is_smv_frame;
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/goose_smv.pcap");
+        let frames = read_pcap(path).unwrap();
+        assert_eq!(frames.len(), 2);
+
+        assert!(is_goose_frame(&frames[0]));
+        let header = decode_goose_header(&frames[0], 26).unwrap();
+        assert_eq!((header.st_num, header.sq_num), (1, 42));
+
+        assert!(is_smv_frame(&frames[1]));
+        let DecodedFrame::Smv(_, pdu) = DecodedFrame::decode(&frames[1]).unwrap() else {
+            panic!("Expected an SMV frame");
+        };
+        assert_eq!(pdu.sav_asdu[0].msv_id, "MU01");
+        assert_eq!(pdu.sav_asdu[0].smp_cnt, 7);
+        assert_eq!(pdu.sav_asdu[0].all_data.len(), 2);
+    }
+
+    #[test]
+    fn test_read_pcap_missing_file() {
+        let err = read_pcap("does/not/exist.pcap").unwrap_err();
+        assert!(err.to_string().contains("does/not/exist.pcap"), "{}", err);
+    }
+
+    #[test]
+    fn test_write_pcap_roundtrip() {
+        let frames = vec![smv_frame(1), smv_frame(2)];
+        let path = std::env::temp_dir().join(format!("iec_61850_{}.pcap", std::process::id()));
+
+        write_pcap(&path, &frames).unwrap();
+        let read = read_pcap(&path);
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), frames);
+
+        // Capture times increase from frame to frame
+        let times: Vec<Duration> = PcapRecords::new(&data)
+            .unwrap()
+            .map(|record| record.unwrap().0)
+            .collect();
+        assert!(times[0] < times[1]);
+    }
+
+    #[test]
+    fn test_encode_pcap_matches_reader() {
+        let frame = ipv4_frame();
+        let data = encode_pcap([
+            (Duration::new(1_700_000_000, 250_000), frame.as_slice()),
+            (Duration::new(1_700_000_001, 0), frame.as_slice()),
+        ]);
+        assert_eq!(
+            data,
+            pcap(&[
+                (1_700_000_000, 250, frame.clone()),
+                (1_700_000_001, 0, frame.clone()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_pcap_invalid_input() {
+        assert!(PcapRecords::new(&[0u8; 24]).is_err());
+        assert!(PcapRecords::new(&[0xd4, 0xc3, 0xb2, 0xa1]).is_err());
+
+        let mut data = pcap(&[(1, 0, smv_frame(1))]);
+        data.truncate(data.len() - 1);
+        let mut records = PcapRecords::new(&data).unwrap();
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use crate::encode_smv::{encode_smv, encode_smv_into, smv_size};
+use crate::types::{
+    EncodeError, EthernetHeader, Sample, SampleCounter, SavAsdu, SavPdu, Timestamp,
+};
+
+/// Republishes captured Sampled Values ASDUs as a live stream.
+///
+/// Every published frame carries the next captured ASDU with its sample values
+/// unchanged. The sample count is replaced by a counter that increments with every
+/// frame and the refresh time by the time of publishing. Once all ASDUs have been
+/// published the replay starts over with the first one.
+#[derive(Debug)]
+pub struct SvReplayer {
+    header: EthernetHeader,
+    asdus: Vec<SavAsdu>,
+    sim: bool,
+    next: usize,
+    smp_cnt: u16,
+}
+
+impl SvReplayer {
+    /// Creates a replayer for the captured ASDUs.
+    ///
+    /// # Parameters
+    /// - `header`: The Ethernet header of the published frames.
+    /// - `asdus`: The decoded ASDUs in publishing order.
+    pub fn new(header: EthernetHeader, asdus: Vec<SavAsdu>) -> Self {
+        SvReplayer {
+            header,
+            asdus,
+            sim: false,
+            next: 0,
+            smp_cnt: 0,
+        }
+    }
+
+    /// Sets the simulation flag of the published frames.
+    pub fn with_simulation(mut self, sim: bool) -> Self {
+        self.sim = sim;
+        self
+    }
+
+    /// Encodes the next frame with the current time as refresh time.
+    pub fn publish(&mut self) -> Result<Vec<u8>, EncodeError> {
+        self.publish_at(&Timestamp::now())
+    }
+
+    /// Encodes the next frame with `now` as refresh time.
+    ///
+    /// # Parameters
+    /// - `now`: The time of the publish tick.
+    ///
+    /// # Returns
+    /// The encoded frame, or an EncodeError if there is nothing to replay or encoding fails
+    pub fn publish_at(&mut self, now: &Timestamp) -> Result<Vec<u8>, EncodeError> {
+        if self.asdus.is_empty() {
+            return Err(EncodeError::new("No ASDU to replay", 0));
+        }
+
+        let mut asdu = self.asdus[self.next].clone();
+        asdu.smp_cnt = self.smp_cnt;
+        asdu.set_refresh_time(now);
+
+        let pdu = SavPdu {
+            sim: self.sim,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![asdu],
+        };
+        let frame = encode_smv(&self.header, &pdu)?;
+
+        self.next = (self.next + 1) % self.asdus.len();
+        self.smp_cnt = self.smp_cnt.wrapping_add(1);
+
+        Ok(frame)
+    }
+}
+
+/// Publishes a Sampled Values stream from live sample values.
+///
+/// Each frame carries one ASDU built from the template with the current samples, the
+/// next sample count and the time of publishing as refresh time. The sample count
+/// counts up to `smp_rate - 1` and starts over at 0, as for a stream synchronized to
+/// the second. Frames are encoded into a buffer owned by the publisher that is reused
+/// for every frame.
+#[derive(Debug)]
+pub struct SvPublisher {
+    header: EthernetHeader,
+    pdu: SavPdu,
+    counter: SampleCounter,
+    buffer: Vec<u8>,
+}
+
+impl SvPublisher {
+    /// Creates a publisher for the stream described by `asdu`.
+    ///
+    /// # Parameters
+    /// - `header`: The Ethernet header of the published frames.
+    /// - `asdu`: The template of the published ASDUs, holding svID, confRev and the
+    ///   initial samples.
+    /// - `smp_rate`: The number of samples per second.
+    pub fn new(header: EthernetHeader, mut asdu: SavAsdu, smp_rate: u16) -> Self {
+        asdu.smp_rate = Some(smp_rate);
+        SvPublisher {
+            header,
+            pdu: SavPdu {
+                sim: false,
+                no_asdu: 1,
+                security: None,
+                sav_asdu: vec![asdu],
+            },
+            counter: SampleCounter::new(smp_rate),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Sets the simulation flag of the published frames.
+    pub fn with_simulation(mut self, sim: bool) -> Self {
+        self.pdu.sim = sim;
+        self
+    }
+
+    /// Replaces the samples published with the following frames.
+    pub fn update(&mut self, samples: Vec<Sample>) {
+        self.pdu.sav_asdu[0].all_data = samples;
+    }
+
+    /// Sample count of the next published frame.
+    pub fn smp_cnt(&self) -> u16 {
+        self.counter.count
+    }
+
+    /// Time between two frames at the configured sample rate.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(1) / self.counter.rate.max(1) as u32
+    }
+
+    /// Encodes the next frame with the current time as refresh time.
+    pub fn publish(&mut self) -> Result<&[u8], EncodeError> {
+        self.publish_at(&Timestamp::now())
+    }
+
+    /// Encodes the next frame with `now` as refresh time.
+    ///
+    /// # Parameters
+    /// - `now`: The time of the publish tick.
+    ///
+    /// # Returns
+    /// The encoded frame, valid until the next call, or an EncodeError if encoding fails
+    pub fn publish_at(&mut self, now: &Timestamp) -> Result<&[u8], EncodeError> {
+        // Only advance the counter once the frame is encoded, a failed encode keeps it
+        let mut counter = self.counter;
+        let asdu = &mut self.pdu.sav_asdu[0];
+        asdu.smp_cnt = counter.next();
+        asdu.set_refresh_time(now);
+
+        let size = smv_size(&self.header, &self.pdu);
+        if self.buffer.len() < size {
+            self.buffer.resize(size, 0);
+        }
+        let written = encode_smv_into(&self.header, &self.pdu, &mut self.buffer)?;
+        self.counter = counter;
+        Ok(&self.buffer[..written])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_smv::decode_smv;
+    use crate::types::{Sample, TimeQuality};
+
+    fn captured_asdu(smp_cnt: u16, values: &[i32]) -> SavAsdu {
+        SavAsdu {
+            msv_id: "MU01".to_string(),
+            smp_cnt,
+            conf_rev: 1,
+            refr_tm: Some([0x11; 8]),
+            smp_synch: 2,
+            all_data: values.iter().map(|v| Sample::new(*v, 0)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn timestamp(seconds: u32) -> Timestamp {
+        Timestamp {
+            seconds,
+            fraction: 0,
+            quality: TimeQuality::default(),
+        }
+    }
+
+    #[test]
+    fn test_replay_rewrites_smp_cnt_and_refr_tm() {
+        let captured = vec![
+            captured_asdu(3000, &[100, -200, 300]),
+            captured_asdu(3001, &[110, -210, 310]),
+        ];
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let mut replayer = SvReplayer::new(header, captured.clone());
+
+        for tick in 0..4u16 {
+            let now = timestamp(1_700_000_000 + tick as u32);
+            let frame = replayer.publish_at(&now).unwrap();
+
+            let pdu = decode_smv(&frame, 22).unwrap();
+            let asdu = &pdu.sav_asdu[0];
+            let original = &captured[tick as usize % captured.len()];
+
+            assert_eq!(asdu.smp_cnt, tick);
+            assert_eq!(asdu.refresh_time(), Some(now));
+            assert_eq!(asdu.msv_id, original.msv_id);
+            assert_eq!(asdu.all_data, original.all_data);
+        }
+    }
+
+    #[test]
+    fn test_replay_without_asdus() {
+        let mut replayer = SvReplayer::new(EthernetHeader::default(), vec![]);
+        assert!(replayer.publish().is_err());
+    }
+
+    #[test]
+    fn test_publisher_wraps_smp_cnt_at_smp_rate() {
+        let header = EthernetHeader::for_smv(
+            [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            [0x40, 0x00],
+        );
+        let mut publisher = SvPublisher::new(header, captured_asdu(1234, &[1, 2, 3]), 4);
+        assert_eq!(publisher.interval(), Duration::from_millis(250));
+
+        let mut smp_cnts = vec![];
+        for tick in 0..6u32 {
+            if tick == 3 {
+                publisher.update(vec![Sample::new(7, 0); 5]);
+            }
+            let now = timestamp(1_700_000_000 + tick);
+            let frame = publisher.publish_at(&now).unwrap().to_vec();
+
+            let pdu = decode_smv(&frame, 22).unwrap();
+            let asdu = &pdu.sav_asdu[0];
+            assert_eq!(asdu.refresh_time(), Some(now));
+            assert_eq!(asdu.smp_rate, Some(4));
+            assert_eq!(asdu.all_data.len(), if tick < 3 { 3 } else { 5 });
+            smp_cnts.push(asdu.smp_cnt);
+        }
+        assert_eq!(smp_cnts, vec![0, 1, 2, 3, 0, 1]);
+        assert_eq!(publisher.smp_cnt(), 2);
+    }
+
+    #[test]
+    fn test_publisher_keeps_smp_cnt_on_failed_encode() {
+        let header = EthernetHeader::for_smv([0x01; 6], [0x02; 6], [0x40, 0x00]);
+        let mut publisher = SvPublisher::new(header, captured_asdu(0, &[1]), 4000);
+        publisher.publish_at(&timestamp(1_700_000_000)).unwrap();
+        assert_eq!(publisher.smp_cnt(), 1);
+
+        publisher.pdu.no_asdu = 2;
+        assert!(publisher.publish_at(&timestamp(1_700_000_001)).is_err());
+        assert_eq!(publisher.smp_cnt(), 1);
+
+        publisher.pdu.no_asdu = 1;
+        let frame = publisher
+            .publish_at(&timestamp(1_700_000_002))
+            .unwrap()
+            .to_vec();
+        assert_eq!(decode_smv(&frame, 22).unwrap().sav_asdu[0].smp_cnt, 1);
+    }
+}
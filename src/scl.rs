@@ -0,0 +1,667 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use roxmltree::{Document, Node, ParsingOptions, TextPos};
+
+use crate::types::{
+    DataDefinition, DataTemplate, DataType, DecodeError, EncodeError, EthernetHeader, MacAddress,
+};
+
+/// VLAN priority of GOOSE and Sampled Values if the SCL does not configure one
+const DEFAULT_VLAN_PRIORITY: u8 = 4;
+
+/// Type of an SCL control block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlBlockKind {
+    /// GSEControl, published as GOOSE
+    Goose,
+    /// SampledValueControl, published as Sampled Values
+    Smv,
+}
+
+/// A GOOSE or Sampled Values control block read from SCL, with the types of its data
+/// set and the address its frames are sent to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SclControlBlock {
+    /** GSEControl or SampledValueControl */
+    pub kind: ControlBlockKind,
+    /** Reference to the control block, e.g. `IED1LD0/LLN0$GO$gcb1` */
+    pub cb_ref: String,
+    /** Reference to the data set, e.g. `IED1LD0/LLN0$DataSet1` */
+    pub dat_set: String,
+    /** GOOSE ID as defined in GSEControl.appID, or SampledValueControl.smvID */
+    pub id: String,
+    /** Configuration revision of the control block */
+    pub conf_rev: u32,
+    /** Destination (multicast) MAC-Address */
+    pub dst_addr: [u8; 6],
+    /** APPID of the frames */
+    pub appid: [u8; 2],
+    /** VLAN-ID, or None if the frames are not VLAN tagged */
+    pub vlan_id: Option<u16>,
+    /** VLAN-PRIORITY, 4 if not configured */
+    pub vlan_priority: u8,
+    /** Types of the data set entries */
+    pub template: DataTemplate,
+}
+
+impl SclControlBlock {
+    /// Builds the Ethernet header for the frames of the control block.
+    ///
+    /// # Parameters
+    /// - `src_addr`: The MAC address of the publishing interface.
+    ///
+    /// # Returns
+    /// The header, VLAN tagged if a VLAN-ID is configured, or an EncodeError if the VLAN
+    /// configuration is out of range
+    pub fn ethernet_header(&self, src_addr: [u8; 6]) -> Result<EthernetHeader, EncodeError> {
+        let mut header = match self.kind {
+            ControlBlockKind::Goose => {
+                EthernetHeader::for_goose(self.dst_addr, src_addr, self.appid)
+            }
+            ControlBlockKind::Smv => EthernetHeader::for_smv(self.dst_addr, src_addr, self.appid),
+        };
+        if let Some(vid) = self.vlan_id {
+            header.set_vlan(self.vlan_priority, vid)?;
+        }
+        Ok(header)
+    }
+}
+
+/// Reads the GOOSE and Sampled Values control blocks of an SCL file (SCD, ICD, CID).
+///
+/// Only what is needed to encode or decode the frames is read: the references of the
+/// control block and its data set, the GSE or SMV address from the Communication
+/// section and the type of every FCDA, resolved through the DataTypeTemplates.
+///
+/// # Parameters
+/// - `xml`: The content of the SCL file.
+///
+/// # Returns
+/// The control blocks of all IEDs in document order, or a DecodeError pointing to the
+/// byte offset of the offending element
+pub fn parse_scl(xml: &str) -> Result<Vec<SclControlBlock>, DecodeError> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(xml, options).map_err(|err| {
+        DecodeError::new(
+            &format!("Invalid XML: {}", err),
+            text_offset(xml, err.pos()),
+        )
+    })?;
+    let root = document.root_element();
+    if root.tag_name().name() != "SCL" {
+        return Err(DecodeError::new(
+            &format!(
+                "Expected SCL root element, found {}",
+                root.tag_name().name()
+            ),
+            root.range().start,
+        ));
+    }
+    let templates = Templates::new(root);
+
+    let mut blocks = vec![];
+    for ied in children(root, "IED") {
+        let ied_name = required_attr(ied, "name")?;
+        for access_point in children(ied, "AccessPoint") {
+            for server in children(access_point, "Server") {
+                for ldevice in children(server, "LDevice") {
+                    let Some(ln0) = child(ldevice, "LN0") else {
+                        continue;
+                    };
+                    let ld_inst = required_attr(ldevice, "inst")?;
+                    let ld_name = match ldevice.attribute("ldName") {
+                        Some(ld_name) => ld_name.to_string(),
+                        None => format!("{}{}", ied_name, ld_inst),
+                    };
+
+                    for control in ln0.children().filter(Node::is_element) {
+                        let (kind, fc, id_attr, address_name) = match control.tag_name().name() {
+                            "GSEControl" => (ControlBlockKind::Goose, "GO", "appID", "GSE"),
+                            "SampledValueControl" => (ControlBlockKind::Smv, "MS", "smvID", "SMV"),
+                            _ => continue,
+                        };
+                        let cb_name = required_attr(control, "name")?;
+                        let dat_set_name = required_attr(control, "datSet")?;
+                        let data_set = children(ln0, "DataSet")
+                            .find(|data_set| data_set.attribute("name") == Some(dat_set_name))
+                            .ok_or_else(|| {
+                                DecodeError::new(
+                                    &format!("Data set {} of {} not found", dat_set_name, cb_name),
+                                    control.range().start,
+                                )
+                            })?;
+                        let entries = children(data_set, "FCDA")
+                            .map(|fcda| templates.fcda_type(server, fcda))
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        let address = children(root, "Communication")
+                            .flat_map(|communication| children(communication, "SubNetwork"))
+                            .flat_map(|subnetwork| children(subnetwork, "ConnectedAP"))
+                            .filter(|ap| {
+                                ap.attribute("iedName") == Some(ied_name)
+                                    && ap.attribute("apName") == access_point.attribute("name")
+                            })
+                            .flat_map(|ap| children(ap, address_name))
+                            .find(|address| {
+                                address.attribute("ldInst") == Some(ld_inst)
+                                    && address.attribute("cbName") == Some(cb_name)
+                            })
+                            .and_then(|address| child(address, "Address"))
+                            .ok_or_else(|| {
+                                DecodeError::new(
+                                    &format!("No {} address for {}", address_name, cb_name),
+                                    control.range().start,
+                                )
+                            })?;
+
+                        let conf_rev = match control.attribute("confRev") {
+                            Some(conf_rev) => conf_rev.parse().map_err(|_| {
+                                DecodeError::new(
+                                    &format!("Invalid confRev {:?}", conf_rev),
+                                    control.range().start,
+                                )
+                            })?,
+                            None => 0,
+                        };
+                        let mut block = SclControlBlock {
+                            kind,
+                            cb_ref: format!("{}/LLN0${}${}", ld_name, fc, cb_name),
+                            dat_set: format!("{}/LLN0${}", ld_name, dat_set_name),
+                            id: control.attribute(id_attr).unwrap_or_default().to_string(),
+                            conf_rev,
+                            dst_addr: [0; 6],
+                            appid: [0; 2],
+                            vlan_id: None,
+                            vlan_priority: DEFAULT_VLAN_PRIORITY,
+                            template: DataTemplate { entries },
+                        };
+                        read_address(&mut block, address)?;
+                        blocks.push(block);
+                    }
+                }
+            }
+        }
+    }
+    Ok(blocks)
+}
+
+/// Reads MAC-Address, APPID, VLAN-ID and VLAN-PRIORITY of a GSE or SMV Address
+fn read_address(block: &mut SclControlBlock, address: Node) -> Result<(), DecodeError> {
+    let mut has_mac = false;
+    for p in children(address, "P") {
+        let value = p.text().unwrap_or_default().trim();
+        let invalid = || {
+            DecodeError::new(
+                &format!(
+                    "Invalid {} {:?}",
+                    p.attribute("type").unwrap_or_default(),
+                    value
+                ),
+                p.range().start,
+            )
+        };
+        match p.attribute("type") {
+            Some("MAC-Address") => {
+                block.dst_addr = MacAddress::from_str(&value.replace('-', ":"))
+                    .map_err(|_| invalid())?
+                    .0;
+                has_mac = true;
+            }
+            Some("APPID") => {
+                block.appid = u16::from_str_radix(value, 16)
+                    .map_err(|_| invalid())?
+                    .to_be_bytes();
+            }
+            Some("VLAN-ID") => {
+                block.vlan_id = Some(u16::from_str_radix(value, 16).map_err(|_| invalid())?);
+            }
+            Some("VLAN-PRIORITY") => {
+                block.vlan_priority = value.parse().map_err(|_| invalid())?;
+            }
+            _ => {}
+        }
+    }
+    if !has_mac {
+        return Err(DecodeError::new(
+            "Address misses the MAC-Address",
+            address.range().start,
+        ));
+    }
+    Ok(())
+}
+
+/// The type definitions of the DataTypeTemplates section by id
+struct Templates<'a, 'input> {
+    lnode_types: HashMap<&'a str, Node<'a, 'input>>,
+    do_types: HashMap<&'a str, Node<'a, 'input>>,
+    da_types: HashMap<&'a str, Node<'a, 'input>>,
+}
+
+impl<'a, 'input> Templates<'a, 'input> {
+    fn new(root: Node<'a, 'input>) -> Self {
+        let by_id = |name: &'a str| {
+            children(root, "DataTypeTemplates")
+                .flat_map(move |templates| children(templates, name))
+                .filter_map(|element| Some((element.attribute("id")?, element)))
+                .collect::<HashMap<_, _>>()
+        };
+        Templates {
+            lnode_types: by_id("LNodeType"),
+            do_types: by_id("DOType"),
+            da_types: by_id("DAType"),
+        }
+    }
+
+    /// Looks up the type definition referenced by the `type` attribute of `element`
+    fn lookup(
+        &self,
+        types: &HashMap<&'a str, Node<'a, 'input>>,
+        element: Node,
+    ) -> Result<Node<'a, 'input>, DecodeError> {
+        let id = required_attr(element, "type")?;
+        types.get(id).copied().ok_or_else(|| {
+            DecodeError::new(&format!("Type {} not found", id), element.range().start)
+        })
+    }
+
+    /// Looks up the type of a nested SDO or structured attribute and returns it with
+    /// `resolving` extended by its id. A type that is already being resolved contains
+    /// itself, which is an error instead of endless recursion.
+    fn lookup_nested(
+        &self,
+        types: &HashMap<&'a str, Node<'a, 'input>>,
+        element: Node<'a, 'input>,
+        resolving: &[&'a str],
+    ) -> Result<(Node<'a, 'input>, Vec<&'a str>), DecodeError> {
+        let id = required_attr(element, "type")?;
+        if resolving.contains(&id) {
+            return Err(DecodeError::new(
+                &format!("Type {} contains itself", id),
+                element.range().start,
+            ));
+        }
+        Ok((self.lookup(types, element)?, [resolving, &[id]].concat()))
+    }
+
+    /// Resolves the type of an FCDA of a data set in `server`
+    fn fcda_type(&self, server: Node, fcda: Node) -> Result<DataType, DecodeError> {
+        let ld_inst = required_attr(fcda, "ldInst")?;
+        let ln_class = required_attr(fcda, "lnClass")?;
+        let do_name = required_attr(fcda, "doName")?;
+        let fc = required_attr(fcda, "fc")?;
+        let not_found = |what: &str| {
+            DecodeError::new(
+                &format!("FCDA {} {} not found", what, do_name),
+                fcda.range().start,
+            )
+        };
+
+        let ldevice = children(server, "LDevice")
+            .find(|ldevice| ldevice.attribute("inst") == Some(ld_inst))
+            .ok_or_else(|| not_found("logical device of"))?;
+        let ln = if ln_class == "LLN0" {
+            child(ldevice, "LN0")
+        } else {
+            children(ldevice, "LN").find(|ln| {
+                ln.attribute("lnClass") == Some(ln_class)
+                    && ln.attribute("prefix").unwrap_or_default()
+                        == fcda.attribute("prefix").unwrap_or_default()
+                    && ln.attribute("inst").unwrap_or_default()
+                        == fcda.attribute("lnInst").unwrap_or_default()
+            })
+        }
+        .ok_or_else(|| not_found("logical node of"))?;
+        let lnode_type = self
+            .lnode_types
+            .get(required_attr(ln, "lnType")?)
+            .ok_or_else(|| not_found("logical node type of"))?;
+
+        // doName may address a sub data object, e.g. A.phsA
+        let mut names = do_name.split('.');
+        let data_object = children(*lnode_type, "DO")
+            .find(|data_object| data_object.attribute("name") == names.next())
+            .ok_or_else(|| not_found("data object"))?;
+        let mut do_type = self.lookup(&self.do_types, data_object)?;
+        for name in names {
+            let sdo = children(do_type, "SDO")
+                .find(|sdo| sdo.attribute("name") == Some(name))
+                .ok_or_else(|| not_found("sub data object of"))?;
+            do_type = self.lookup(&self.do_types, sdo)?;
+        }
+
+        let Some(da_name) = fcda.attribute("daName") else {
+            let members = self.functional_constraint_members(do_type, fc, &[])?;
+            if members.is_empty() {
+                return Err(not_found(&format!("{} attributes of", fc)));
+            }
+            return Ok(DataType::Structure(members));
+        };
+
+        // daName may address a member of a structured attribute, e.g. mag.f
+        let mut names = da_name.split('.');
+        let mut attribute = children(do_type, "DA")
+            .find(|da| da.attribute("name") == names.next() && da.attribute("fc") == Some(fc))
+            .ok_or_else(|| not_found("data attribute of"))?;
+        for name in names {
+            attribute = children(self.lookup(&self.da_types, attribute)?, "BDA")
+                .find(|bda| bda.attribute("name") == Some(name))
+                .ok_or_else(|| not_found("data attribute of"))?;
+        }
+        self.attribute_type(attribute, &[])
+    }
+
+    /// The attributes and sub data objects of a DOType with the functional constraint
+    /// `fc`, in the order of the type definition. `resolving` holds the ids of the
+    /// enclosing types.
+    fn functional_constraint_members(
+        &self,
+        do_type: Node<'a, 'input>,
+        fc: &str,
+        resolving: &[&'a str],
+    ) -> Result<Vec<DataDefinition>, DecodeError> {
+        let mut members = vec![];
+        for child in do_type.children().filter(Node::is_element) {
+            let data_type = match child.tag_name().name() {
+                "DA" if child.attribute("fc") == Some(fc) => {
+                    self.attribute_type(child, resolving)?
+                }
+                "SDO" => {
+                    let (sdo_type, resolving) =
+                        self.lookup_nested(&self.do_types, child, resolving)?;
+                    let sdo_members =
+                        self.functional_constraint_members(sdo_type, fc, &resolving)?;
+                    if sdo_members.is_empty() {
+                        continue;
+                    }
+                    DataType::Structure(sdo_members)
+                }
+                _ => continue,
+            };
+            members.push(DataDefinition {
+                name: required_attr(child, "name")?.to_string(),
+                data_type,
+            });
+        }
+        Ok(members)
+    }
+
+    /// The type of a DA or BDA, an Array if it has a count. `resolving` holds the ids of
+    /// the enclosing types.
+    fn attribute_type(
+        &self,
+        attribute: Node<'a, 'input>,
+        resolving: &[&'a str],
+    ) -> Result<DataType, DecodeError> {
+        let b_type = required_attr(attribute, "bType")?;
+        let data_type = match b_type {
+            "BOOLEAN" => DataType::Boolean,
+            "INT8" | "INT16" | "INT24" | "INT32" | "INT64" | "Enum" => DataType::Int,
+            "INT8U" | "INT16U" | "INT24U" | "INT32U" => DataType::UInt,
+            "FLOAT32" | "FLOAT64" => DataType::Float,
+            "Dbpos" | "Tcmd" | "Quality" | "Check" => DataType::BitString,
+            "Timestamp" => DataType::Timestamp,
+            "Octet64" | "Octet16" | "Octet6" => DataType::OctetString,
+            "VisString32" | "VisString64" | "VisString65" | "VisString129" | "VisString255"
+            | "ObjRef" => DataType::VisibleString,
+            "Unicode255" => DataType::MmsString,
+            "Struct" => {
+                let (da_type, resolving) =
+                    self.lookup_nested(&self.da_types, attribute, resolving)?;
+                let members = children(da_type, "BDA")
+                    .map(|bda| {
+                        Ok(DataDefinition {
+                            name: required_attr(bda, "name")?.to_string(),
+                            data_type: self.attribute_type(bda, &resolving)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, DecodeError>>()?;
+                DataType::Structure(members)
+            }
+            _ => {
+                return Err(DecodeError::new(
+                    &format!("Unsupported bType {}", b_type),
+                    attribute.range().start,
+                ))
+            }
+        };
+
+        match attribute.attribute("count") {
+            Some(count) => {
+                let count = count.parse().map_err(|_| {
+                    DecodeError::new(
+                        &format!("Invalid count {:?}", count),
+                        attribute.range().start,
+                    )
+                })?;
+                Ok(DataType::Array {
+                    count,
+                    element_type: Box::new(data_type),
+                })
+            }
+            None => Ok(data_type),
+        }
+    }
+}
+
+/// The child elements of `element` with the local name `name`
+fn children<'a, 'input>(
+    element: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    element
+        .children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+/// The first child element of `element` with the local name `name`
+fn child<'a, 'input>(element: Node<'a, 'input>, name: &'a str) -> Option<Node<'a, 'input>> {
+    children(element, name).next()
+}
+
+fn required_attr<'a>(element: Node<'a, '_>, name: &str) -> Result<&'a str, DecodeError> {
+    element.attribute(name).ok_or_else(|| {
+        DecodeError::new(
+            &format!("{} misses attribute {}", element.tag_name().name(), name),
+            element.range().start,
+        )
+    })
+}
+
+/// Byte offset of a row and column of `xml`, both counted from 1
+fn text_offset(xml: &str, pos: TextPos) -> usize {
+    let line_start: usize = xml
+        .split_inclusive('\n')
+        .take(pos.row.saturating_sub(1) as usize)
+        .map(str::len)
+        .sum();
+    let column: usize = xml[line_start..]
+        .chars()
+        .take(pos.col.saturating_sub(1) as usize)
+        .map(char::len_utf8)
+        .sum();
+    line_start + column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCD: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<SCL xmlns="http://www.iec.ch/61850/2003/SCL" version="2007" revision="B">
+  <!-- Station bus with one merging unit and breaker controller -->
+  <Communication>
+    <SubNetwork name="StationBus" type="8-MMS">
+      <ConnectedAP iedName="IED1" apName="AP1">
+        <GSE ldInst="LD0" cbName="gcb1">
+          <Address>
+            <P type="MAC-Address">01-0C-CD-01-00-01</P>
+            <P type="APPID">3001</P>
+            <P type="VLAN-ID">005</P>
+            <P type="VLAN-PRIORITY">6</P>
+          </Address>
+        </GSE>
+        <SMV ldInst="LD0" cbName="msvcb1">
+          <Address>
+            <P type="MAC-Address">01-0C-CD-04-00-01</P>
+            <P type="APPID">4000</P>
+          </Address>
+        </SMV>
+      </ConnectedAP>
+    </SubNetwork>
+  </Communication>
+  <IED name="IED1">
+    <AccessPoint name="AP1">
+      <Server>
+        <LDevice inst="LD0">
+          <LN0 lnClass="LLN0" inst="" lnType="LLN0_T">
+            <DataSet name="DS1">
+              <FCDA ldInst="LD0" lnClass="XCBR" lnInst="1" doName="Pos" daName="stVal" fc="ST"/>
+              <FCDA ldInst="LD0" lnClass="XCBR" lnInst="1" doName="Pos" fc="ST"/>
+              <FCDA ldInst="LD0" prefix="I" lnClass="MMXU" lnInst="1" doName="A.phsA" daName="cVal.mag.f" fc="MX"/>
+              <FCDA ldInst="LD0" lnClass="LLN0" doName="Beh" daName="stVal" fc="ST"/>
+            </DataSet>
+            <GSEControl name="gcb1" datSet="DS1" appID="IED1 &amp; GOOSE" confRev="2"/>
+            <SampledValueControl name="msvcb1" datSet="DS1" smvID="MU01" confRev="1" smpRate="80"/>
+          </LN0>
+          <LN lnClass="XCBR" inst="1" lnType="XCBR_T"/>
+          <LN prefix="I" lnClass="MMXU" inst="1" lnType="MMXU_T"/>
+        </LDevice>
+      </Server>
+    </AccessPoint>
+  </IED>
+  <DataTypeTemplates>
+    <LNodeType id="LLN0_T" lnClass="LLN0"><DO name="Beh" type="ENS_T"/></LNodeType>
+    <LNodeType id="XCBR_T" lnClass="XCBR"><DO name="Pos" type="DPC_T"/></LNodeType>
+    <LNodeType id="MMXU_T" lnClass="MMXU"><DO name="A" type="WYE_T"/></LNodeType>
+    <DOType id="ENS_T" cdc="ENS">
+      <DA name="stVal" bType="Enum" type="Beh" fc="ST"/>
+    </DOType>
+    <DOType id="DPC_T" cdc="DPC">
+      <DA name="stVal" bType="Dbpos" fc="ST"/>
+      <DA name="q" bType="Quality" fc="ST"/>
+      <DA name="t" bType="Timestamp" fc="ST"/>
+      <DA name="stSeld" bType="BOOLEAN" fc="ST"/>
+      <DA name="ctlNum" bType="INT8U" fc="ST"/>
+      <DA name="ctlModel" bType="Enum" type="CtlModel" fc="CF"/>
+      <DA name="pulseCnt" bType="INT32U" fc="ST" count="2"/>
+    </DOType>
+    <DOType id="WYE_T" cdc="WYE"><SDO name="phsA" type="CMV_T"/></DOType>
+    <DOType id="CMV_T" cdc="CMV">
+      <DA name="cVal" bType="Struct" type="Vector_T" fc="MX"/>
+    </DOType>
+    <DAType id="Vector_T"><BDA name="mag" bType="Struct" type="AV_T"/></DAType>
+    <DAType id="AV_T"><BDA name="f" bType="FLOAT32"/></DAType>
+  </DataTypeTemplates>
+</SCL>
+"#;
+
+    #[test]
+    fn test_parse_scl() {
+        let blocks = parse_scl(SCD).unwrap();
+        assert_eq!(blocks.len(), 2);
+
+        let goose = &blocks[0];
+        assert_eq!(goose.kind, ControlBlockKind::Goose);
+        assert_eq!(goose.cb_ref, "IED1LD0/LLN0$GO$gcb1");
+        assert_eq!(goose.dat_set, "IED1LD0/LLN0$DS1");
+        assert_eq!(goose.id, "IED1 & GOOSE");
+        assert_eq!(goose.conf_rev, 2);
+        assert_eq!(goose.dst_addr, [0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01]);
+        assert_eq!(goose.appid, [0x30, 0x01]);
+        assert_eq!(goose.vlan_id, Some(5));
+        assert_eq!(goose.vlan_priority, 6);
+
+        let member = |name: &str, data_type| DataDefinition {
+            name: name.to_string(),
+            data_type,
+        };
+        assert_eq!(
+            goose.template.entries,
+            vec![
+                DataType::BitString,
+                DataType::Structure(vec![
+                    member("stVal", DataType::BitString),
+                    member("q", DataType::BitString),
+                    member("t", DataType::Timestamp),
+                    member("stSeld", DataType::Boolean),
+                    member("ctlNum", DataType::UInt),
+                    member(
+                        "pulseCnt",
+                        DataType::Array {
+                            count: 2,
+                            element_type: Box::new(DataType::UInt),
+                        }
+                    ),
+                ]),
+                DataType::Float,
+                DataType::Int,
+            ]
+        );
+
+        let header = goose.ethernet_header([0x02; 6]).unwrap();
+        assert_eq!(header.ether_type, [0x88, 0xb8]);
+        assert_eq!(header.appid, [0x30, 0x01]);
+        assert_eq!(header.vlan_id(), Some(5));
+        assert_eq!(header.vlan_priority(), Some(6));
+
+        let smv = &blocks[1];
+        assert_eq!(smv.kind, ControlBlockKind::Smv);
+        assert_eq!(smv.cb_ref, "IED1LD0/LLN0$MS$msvcb1");
+        assert_eq!(smv.id, "MU01");
+        assert_eq!(smv.template, goose.template);
+        let header = smv.ethernet_header([0x02; 6]).unwrap();
+        assert_eq!(header.ether_type, [0x88, 0xba]);
+        assert_eq!(header.dst_addr, [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01]);
+        assert_eq!(header.tpid, None);
+    }
+
+    #[test]
+    fn test_parse_scl_errors() {
+        // Data set entry pointing to a data object the type does not have
+        let scd = SCD.replace(r#"doName="Beh""#, r#"doName="Mod""#);
+        let err = parse_scl(&scd).unwrap_err();
+        assert!(err.message.contains("data object Mod"), "{}", err.message);
+        assert_eq!(&scd[err.buffer_index..err.buffer_index + 5], "<FCDA");
+
+        // Control block without address
+        let scd = SCD.replace(r#"cbName="msvcb1""#, r#"cbName="other""#);
+        let err = parse_scl(&scd).unwrap_err();
+        assert!(err.message.contains("No SMV address"), "{}", err.message);
+
+        let err = parse_scl(&SCD.replace("</Server>", "")).unwrap_err();
+        assert!(err.message.starts_with("Invalid XML"), "{}", err.message);
+        assert!(parse_scl("<SCL><IED name=IED1/></SCL>").is_err());
+        assert!(parse_scl("<SCL/><SCL/>").is_err());
+        assert!(parse_scl("<SCL a=\"&unknown;\"/>").is_err());
+        assert!(
+            parse_scl("<scl:SCL xmlns:scl=\"x\"><!-- <IED> --></scl:SCL>")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_parse_scl_cyclic_templates() {
+        // DAType with a BDA of its own type
+        let scd = SCD.replace(
+            r#"<BDA name="f" bType="FLOAT32"/>"#,
+            r#"<BDA name="f" bType="Struct" type="AV_T"/>"#,
+        );
+        let err = parse_scl(&scd).unwrap_err();
+        assert_eq!(err.message, "Type AV_T contains itself");
+        assert_eq!(&scd[err.buffer_index..err.buffer_index + 4], "<BDA");
+
+        // DOType with an SDO of its own type
+        let scd = SCD.replace(
+            r#"<DOType id="DPC_T" cdc="DPC">"#,
+            r#"<DOType id="DPC_T" cdc="DPC"><SDO name="sub" type="DPC_T"/>"#,
+        );
+        let err = parse_scl(&scd).unwrap_err();
+        assert_eq!(err.message, "Type DPC_T contains itself");
+        assert_eq!(&scd[err.buffer_index..err.buffer_index + 4], "<SDO");
+    }
+}
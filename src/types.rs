@@ -1,18 +1,86 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 use core::str;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+#[cfg(feature = "std")]
 use rasn::{types::*, AsnType, Decode, Encode};
+#[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::ber::decode_tag_length;
+use crate::constants::{ETHERTYPE_GOOSE, ETHERTYPE_SMV, GOOSE_MCAST_BASE};
+#[cfg(feature = "std")]
+use crate::encode_smv::FLOAT32_EXPONENT_WIDTH;
+
+pub use crate::error::{DecodeError, EncodeError};
+
+#[cfg(feature = "std")]
 #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
 #[rasn(delegate)]
 pub struct MMSString(pub VisibleString);
 
+#[cfg(feature = "std")]
 #[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq, Eq, Hash)]
 #[rasn(delegate)]
 pub struct FloatingPoint(pub OctetString);
 
+/// Exponent width descriptor of an IEC 61850 FLOAT64 (11 bit exponent)
+#[cfg(feature = "std")]
+const FLOAT64_EXPONENT_WIDTH: u8 = 0x0B;
+
+#[cfg(feature = "std")]
+impl FloatingPoint {
+    /// Encodes a FLOAT32: the exponent width 0x08 followed by 4 bytes IEEE-754
+    pub fn from_f32(value: f32) -> Self {
+        let mut bytes = vec![FLOAT32_EXPONENT_WIDTH];
+        bytes.extend_from_slice(&value.to_be_bytes());
+        FloatingPoint(OctetString::from(bytes))
+    }
+
+    /// Encodes a FLOAT64: the exponent width 0x0B followed by 8 bytes IEEE-754
+    pub fn from_f64(value: f64) -> Self {
+        let mut bytes = vec![FLOAT64_EXPONENT_WIDTH];
+        bytes.extend_from_slice(&value.to_be_bytes());
+        FloatingPoint(OctetString::from(bytes))
+    }
+
+    /// Returns the IEEE-754 bytes without the exponent width descriptor. The bare 4 and
+    /// 8 byte forms some publishers send are returned as they are.
+    fn ieee_bytes(&self) -> &[u8] {
+        let bytes = self.0.as_ref();
+        match bytes.split_first() {
+            Some((&FLOAT32_EXPONENT_WIDTH, rest)) if rest.len() == 4 => rest,
+            Some((&FLOAT64_EXPONENT_WIDTH, rest)) if rest.len() == 8 => rest,
+            _ => bytes,
+        }
+    }
+
+    /// Decodes a FLOAT32, or None if the value is not 32 bit
+    pub fn as_f32(&self) -> Option<f32> {
+        let bytes: [u8; 4] = self.ieee_bytes().try_into().ok()?;
+        Some(f32::from_be_bytes(bytes))
+    }
+
+    /// Decodes a FLOAT64 or a FLOAT32 widened to f64, or None for any other length
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.ieee_bytes().try_into() {
+            Ok(bytes) => Some(f64::from_be_bytes(bytes)),
+            Err(_) => self.as_f32().map(f64::from),
+        }
+    }
+}
+
 /// Time quality flags according to IEC 61850-7-2 Table 30
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct TimeQuality {
     pub leap_second_known: bool,
     pub clock_failure: bool,
@@ -54,10 +122,21 @@ impl TimeQuality {
             _ => None,
         }
     }
+
+    /// Gets the time accuracy in seconds, 2^-bits
+    pub fn accuracy_as_seconds(&self) -> Option<f64> {
+        self.accuracy_bits().map(|bits| 1.0 / (1u32 << bits) as f64)
+    }
+
+    /// Gets the time accuracy as a Duration, 2^-bits seconds
+    pub fn accuracy_as_duration(&self) -> Option<Duration> {
+        self.accuracy_as_seconds().map(Duration::from_secs_f64)
+    }
 }
 
 /// Quality flags for IEC 61850 sampled values - 13 bits total
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Quality {
     // Validity (2 bits) - bits 0-1
     pub validity: Validity,
@@ -82,7 +161,8 @@ pub struct Quality {
     pub operator_blocked: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Validity {
     #[default]
     Good = 0,
@@ -97,13 +177,7 @@ impl Quality {
     pub fn from_u16(value: u16) -> Self {
         Quality {
             // Validity is bits 0-1 (most significant bits)
-            validity: match (value >> 14) & 0x03 {
-                0 => Validity::Good,
-                1 => Validity::Invalid,
-                2 => Validity::Reserved,
-                3 => Validity::Questionable,
-                _ => Validity::Good,
-            },
+            validity: Validity::try_from(((value >> 14) & 0x03) as u8).unwrap_or_default(),
 
             // Detail quality flags (bits 2-9)
             overflow: (value & (1 << 13)) != 0,
@@ -126,6 +200,19 @@ impl Quality {
         }
     }
 
+    /// Decodes quality from a 16-bit value like `from_u16`, but rejects the reserved
+    /// validity encoding
+    pub fn try_from_u16(value: u16) -> Result<Self, DecodeError> {
+        let quality = Self::from_u16(value);
+        if quality.validity == Validity::Reserved {
+            return Err(DecodeError::new(
+                &format!("Quality 0x{:04x} uses the reserved validity value 2", value),
+                0,
+            ));
+        }
+        Ok(quality)
+    }
+
     /// Encodes quality to a 16-bit value
     pub fn to_u16(&self) -> u16 {
         let mut value = 0u16;
@@ -192,16 +279,259 @@ impl Quality {
             && !self.test
             && !self.operator_blocked
     }
+
+    /// Creates a quality with the given validity and no flags set.
+    ///
+    /// Combine with the flag setters to build a quality in one expression, e.g.
+    /// `Quality::with_validity(Validity::Questionable).test()`.
+    pub fn with_validity(validity: Validity) -> Self {
+        Quality {
+            validity,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a good quality with no flags set
+    pub fn good() -> Self {
+        Self::with_validity(Validity::Good)
+    }
+
+    /// Creates an invalid quality with no flags set
+    pub fn invalid() -> Self {
+        Self::with_validity(Validity::Invalid)
+    }
+
+    /// Starts a `QualityBuilder` from a good quality
+    pub fn builder() -> QualityBuilder {
+        QualityBuilder::default()
+    }
+
+    /// Sets or clears the test flag
+    pub fn with_test(mut self, test: bool) -> Self {
+        self.test = test;
+        self
+    }
+
+    /// Sets the overflow flag
+    pub fn overflow(mut self) -> Self {
+        self.overflow = true;
+        self
+    }
+
+    /// Sets the out of range flag
+    pub fn out_of_range(mut self) -> Self {
+        self.out_of_range = true;
+        self
+    }
+
+    /// Sets the bad reference flag
+    pub fn bad_reference(mut self) -> Self {
+        self.bad_reference = true;
+        self
+    }
+
+    /// Sets the oscillatory flag
+    pub fn oscillatory(mut self) -> Self {
+        self.oscillatory = true;
+        self
+    }
+
+    /// Sets the failure flag
+    pub fn failure(mut self) -> Self {
+        self.failure = true;
+        self
+    }
+
+    /// Sets the old data flag
+    pub fn old_data(mut self) -> Self {
+        self.old_data = true;
+        self
+    }
+
+    /// Sets the inconsistent flag
+    pub fn inconsistent(mut self) -> Self {
+        self.inconsistent = true;
+        self
+    }
+
+    /// Sets the inaccurate flag
+    pub fn inaccurate(mut self) -> Self {
+        self.inaccurate = true;
+        self
+    }
+
+    /// Sets the source to substituted
+    pub fn substituted(mut self) -> Self {
+        self.source_substituted = true;
+        self
+    }
+
+    /// Sets the test flag
+    pub fn test(mut self) -> Self {
+        self.test = true;
+        self
+    }
+
+    /// Sets the operator blocked flag
+    pub fn operator_blocked(mut self) -> Self {
+        self.operator_blocked = true;
+        self
+    }
+
+    /// Names of the set flags in bit order, without the validity
+    pub fn flag_names(&self) -> Vec<&'static str> {
+        [
+            (self.overflow, "overflow"),
+            (self.out_of_range, "out_of_range"),
+            (self.bad_reference, "bad_reference"),
+            (self.oscillatory, "oscillatory"),
+            (self.failure, "failure"),
+            (self.old_data, "old_data"),
+            (self.inconsistent, "inconsistent"),
+            (self.inaccurate, "inaccurate"),
+            (self.source_substituted, "substituted"),
+            (self.test, "test"),
+            (self.operator_blocked, "operator_blocked"),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
+/// Builder for a `Quality`, taking an explicit value for each flag
+#[derive(Debug, Default, Clone, Copy)]
+pub struct QualityBuilder {
+    quality: Quality,
+}
+
+impl QualityBuilder {
+    /// Sets the validity
+    pub fn validity(mut self, validity: Validity) -> Self {
+        self.quality.validity = validity;
+        self
+    }
+
+    /// Sets the overflow flag
+    pub fn overflow(self, overflow: bool) -> Self {
+        self.flag(overflow, Quality::overflow)
+    }
+
+    /// Sets the out of range flag
+    pub fn out_of_range(self, out_of_range: bool) -> Self {
+        self.flag(out_of_range, Quality::out_of_range)
+    }
+
+    /// Sets the bad reference flag
+    pub fn bad_reference(self, bad_reference: bool) -> Self {
+        self.flag(bad_reference, Quality::bad_reference)
+    }
+
+    /// Sets the oscillatory flag
+    pub fn oscillatory(self, oscillatory: bool) -> Self {
+        self.flag(oscillatory, Quality::oscillatory)
+    }
+
+    /// Sets the failure flag
+    pub fn failure(self, failure: bool) -> Self {
+        self.flag(failure, Quality::failure)
+    }
+
+    /// Sets the old data flag
+    pub fn old_data(self, old_data: bool) -> Self {
+        self.flag(old_data, Quality::old_data)
+    }
+
+    /// Sets the inconsistent flag
+    pub fn inconsistent(self, inconsistent: bool) -> Self {
+        self.flag(inconsistent, Quality::inconsistent)
+    }
+
+    /// Sets the inaccurate flag
+    pub fn inaccurate(self, inaccurate: bool) -> Self {
+        self.flag(inaccurate, Quality::inaccurate)
+    }
+
+    /// Sets the source to substituted (true) or process (false)
+    pub fn substituted(self, substituted: bool) -> Self {
+        self.flag(substituted, Quality::substituted)
+    }
+
+    /// Sets the test flag
+    pub fn test(self, test: bool) -> Self {
+        self.flag(test, Quality::test)
+    }
+
+    /// Sets the operator blocked flag
+    pub fn operator_blocked(self, operator_blocked: bool) -> Self {
+        self.flag(operator_blocked, Quality::operator_blocked)
+    }
+
+    /// Sets the flag that the `Quality` setter `set` sets, or clears it again
+    fn flag(mut self, value: bool, set: fn(Quality) -> Quality) -> Self {
+        let mask = set(Quality::good()).to_u16();
+        let bits = self.quality.to_u16();
+        self.quality = Quality::from_u16(if value { bits | mask } else { bits & !mask });
+        self
+    }
+
+    pub fn build(self) -> Quality {
+        self.quality
+    }
+}
+
+/// Renders the validity followed by the set flags, e.g. `invalid|overflow|test`
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.validity)?;
+        for name in self.flag_names() {
+            write!(f, "|{}", name)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<u8> for Validity {
+    type Error = DecodeError;
+
+    /// Maps the 2-bit validity field, rejecting values above 3
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Validity::Good),
+            1 => Ok(Validity::Invalid),
+            2 => Ok(Validity::Reserved),
+            3 => Ok(Validity::Questionable),
+            _ => Err(DecodeError::new(
+                &format!("Validity {} does not fit in 2 bits", value),
+                0,
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Validity::Good => "good",
+            Validity::Invalid => "invalid",
+            Validity::Reserved => "reserved",
+            Validity::Questionable => "questionable",
+        };
+        f.write_str(name)
+    }
 }
 
 /// IEC 61850 UtcTime - 8 bytes with specific structure
 /// Bytes 0-3: Seconds since epoch (Jan 1, 1970)
 /// Bytes 4-6: Fraction of second (24 bits)
 /// Byte 7: Time quality flags
+#[cfg(feature = "std")]
 #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
 #[rasn(delegate)]
 pub struct TimestampRasn(pub OctetString);
 
+#[cfg(feature = "std")]
 impl TimestampRasn {
     /// Creates a new Timestamp from raw 8 bytes
     pub fn from_bytes(bytes: [u8; 8]) -> Self {
@@ -245,7 +575,8 @@ impl TimestampRasn {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Timestamp {
     /// Seconds since Unix epoch (January 1, 1970)
     pub seconds: u32,
@@ -297,42 +628,19 @@ impl Timestamp {
 
         // Calculate date components from Unix epoch
         const SECONDS_PER_DAY: u32 = 86400;
-        const DAYS_PER_YEAR: u32 = 365;
-        const DAYS_PER_4_YEARS: u32 = DAYS_PER_YEAR * 4 + 1;
 
         let mut days = self.seconds / SECONDS_PER_DAY;
         let remaining_seconds = self.seconds % SECONDS_PER_DAY;
 
-        // Start from 1970
+        // Unwind whole years from 1970. The u32 seconds end in 2106, so at most 136
+        // iterations; cycles of 4, 100 or 400 years are not aligned to 1970.
         let mut year = 1970;
-
-        // Handle 400-year cycles
-        while days >= 146097 {
-            days -= 146097;
-            year += 400;
-        }
-
-        // Handle 100-year cycles
-        while days >= 36524 {
-            if days == 36524 && Self::is_leap_year(year) {
-                break;
-            }
-            days -= 36524;
-            year += 100;
-        }
-
-        // Handle 4-year cycles
-        while days >= DAYS_PER_4_YEARS {
-            days -= DAYS_PER_4_YEARS;
-            year += 4;
-        }
-
-        // Handle individual years
-        while days >= DAYS_PER_YEAR {
-            if days == DAYS_PER_YEAR && Self::is_leap_year(year) {
+        loop {
+            let days_in_year = if Self::is_leap_year(year) { 366 } else { 365 };
+            if days < days_in_year {
                 break;
             }
-            days -= DAYS_PER_YEAR;
+            days -= days_in_year;
             year += 1;
         }
 
@@ -366,6 +674,108 @@ impl Timestamp {
         )
     }
 
+    /// Parses a UTC datetime string in the format produced by `to_utc_string`
+    /// Example: "2024-10-28T14:30:45.123456Z"
+    ///
+    /// The microseconds are rounded up to the next 24-bit fraction, so
+    /// `to_utc_string` gives back the same string. The time quality is the default.
+    pub fn from_utc_string(s: &str) -> Result<Self, DecodeError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 27 {
+            return Err(DecodeError::new(
+                &format!(
+                    "Invalid UTC time {:?}: expected 27 characters like 2024-10-28T14:30:45.123456Z",
+                    s
+                ),
+                bytes.len().min(27),
+            ));
+        }
+        for (index, separator) in [
+            (4, b'-'),
+            (7, b'-'),
+            (10, b'T'),
+            (13, b':'),
+            (16, b':'),
+            (19, b'.'),
+            (26, b'Z'),
+        ] {
+            if bytes[index] != separator {
+                return Err(DecodeError::new(
+                    &format!(
+                        "Invalid UTC time {:?}: expected '{}' at position {}",
+                        s, separator as char, index
+                    ),
+                    index,
+                ));
+            }
+        }
+
+        let field = |start: usize, len: usize, max: u32, name: &str| -> Result<u32, DecodeError> {
+            let digits = &bytes[start..start + len];
+            if !digits.iter().all(|b| b.is_ascii_digit()) {
+                return Err(DecodeError::new(
+                    &format!("Invalid UTC time {:?}: {} is not a number", s, name),
+                    start,
+                ));
+            }
+            let value = digits.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32);
+            if value > max {
+                return Err(DecodeError::new(
+                    &format!(
+                        "Invalid UTC time {:?}: {} {} is out of range",
+                        s, name, value
+                    ),
+                    start,
+                ));
+            }
+            Ok(value)
+        };
+        let year = field(0, 4, 2106, "year")?;
+        let month = field(5, 2, 12, "month")?;
+        let day = field(8, 2, 31, "day")?;
+        let hours = field(11, 2, 23, "hour")?;
+        let minutes = field(14, 2, 59, "minute")?;
+        let secs = field(17, 2, 59, "second")?;
+        let micros = field(20, 6, 999_999, "fraction")?;
+
+        let days_in_months = if Self::is_leap_year(year) {
+            [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+        } else {
+            [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+        };
+        if year < 1970 || month == 0 || day == 0 || day > days_in_months[month as usize - 1] {
+            return Err(DecodeError::new(
+                &format!(
+                    "Invalid UTC time {:?}: no such date in the UtcTime range",
+                    s
+                ),
+                0,
+            ));
+        }
+
+        let mut days = (1970..year)
+            .map(|y| if Self::is_leap_year(y) { 366u64 } else { 365 })
+            .sum::<u64>();
+        days += days_in_months[..month as usize - 1]
+            .iter()
+            .map(|&d| d as u64)
+            .sum::<u64>();
+        days += (day - 1) as u64;
+        let seconds = days * 86400 + (hours * 3600 + minutes * 60 + secs) as u64;
+        if seconds > u32::MAX as u64 {
+            return Err(DecodeError::new(
+                &format!("Invalid UTC time {:?}: after 2106-02-07T06:28:15Z", s),
+                0,
+            ));
+        }
+
+        Ok(Timestamp {
+            seconds: seconds as u32,
+            fraction: (((micros as u64) << 24).div_ceil(1_000_000)) as u32,
+            quality: TimeQuality::default(),
+        })
+    }
+
     /// Helper function to check if a year is a leap year
     #[allow(unknown_lints)]
     #[allow(clippy::manual_is_multiple_of)]
@@ -373,6 +783,56 @@ impl Timestamp {
         (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
     }
 
+    /// Nanoseconds since Unix epoch
+    fn as_nanos(&self) -> u64 {
+        self.seconds as u64 * 1_000_000_000 + self.fraction_as_nanos() as u64
+    }
+
+    /// Creates a Timestamp for the current system time
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now(), TimeQuality::default())
+    }
+
+    /// Creates a Timestamp from a SystemTime
+    ///
+    /// Nanoseconds are rounded to the nearest 24-bit fraction, so converting back with
+    /// `to_system_time` is exact for any Timestamp. Times outside the UtcTime range are
+    /// clamped like in `from_unix_timestamp`.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: SystemTime, quality: TimeQuality) -> Self {
+        let since_epoch = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration,
+            Err(_) => {
+                return Timestamp {
+                    seconds: 0,
+                    fraction: 0,
+                    quality,
+                }
+            }
+        };
+        if since_epoch.as_secs() > u32::MAX as u64 {
+            return Timestamp {
+                seconds: u32::MAX,
+                fraction: 16_777_215,
+                quality,
+            };
+        }
+
+        let fraction = (((since_epoch.subsec_nanos() as u64) << 24) + 500_000_000) / 1_000_000_000;
+        Timestamp {
+            seconds: since_epoch.as_secs() as u32,
+            fraction: (fraction as u32).min(16_777_215),
+            quality,
+        }
+    }
+
+    /// Converts the timestamp to a SystemTime
+    #[cfg(feature = "std")]
+    pub fn to_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::new(self.seconds as u64, self.fraction_as_nanos())
+    }
+
     /// Converts timestamp to Unix timestamp (seconds since epoch) as f64
     pub fn to_unix_timestamp(&self) -> f64 {
         let seconds = self.seconds as f64;
@@ -381,9 +841,30 @@ impl Timestamp {
     }
 
     /// Creates a Timestamp from a Unix timestamp (seconds since epoch)
+    ///
+    /// UtcTime holds unsigned 32 bit seconds, so times before the epoch are clamped to
+    /// the epoch and times after 2106-02-07T06:28:15Z to that time. Use
+    /// `try_from_unix_timestamp` to reject them instead.
     pub fn from_unix_timestamp(unix_timestamp: f64, quality: TimeQuality) -> Self {
-        let seconds = unix_timestamp.floor() as u32;
-        let fraction = ((unix_timestamp.fract() * 16_777_216.0) as u32).min(16_777_215);
+        if unix_timestamp.is_nan() || unix_timestamp < 0.0 {
+            return Timestamp {
+                seconds: 0,
+                fraction: 0,
+                quality,
+            };
+        }
+        if unix_timestamp >= u32::MAX as f64 + 1.0 {
+            return Timestamp {
+                seconds: u32::MAX,
+                fraction: 16_777_215,
+                quality,
+            };
+        }
+
+        // Truncation is the floor for the non-negative range left
+        let seconds = unix_timestamp as u32;
+        let fract = unix_timestamp - seconds as f64;
+        let fraction = ((fract * 16_777_216.0) as u32).min(16_777_215);
 
         Timestamp {
             seconds,
@@ -391,9 +872,31 @@ impl Timestamp {
             quality,
         }
     }
+
+    /// Creates a Timestamp from a Unix timestamp (seconds since epoch)
+    ///
+    /// # Returns
+    /// The timestamp, or an EncodeError if the time is before the epoch, after
+    /// 2106-02-07T06:28:15Z or not a number
+    pub fn try_from_unix_timestamp(
+        unix_timestamp: f64,
+        quality: TimeQuality,
+    ) -> Result<Self, EncodeError> {
+        if !(0.0..u32::MAX as f64 + 1.0).contains(&unix_timestamp) {
+            return Err(EncodeError::new(
+                &format!(
+                    "Unix timestamp {} is outside the UtcTime range of 0 to 2^32 seconds",
+                    unix_timestamp
+                ),
+                0,
+            ));
+        }
+        Ok(Self::from_unix_timestamp(unix_timestamp, quality))
+    }
 }
 
 // Add this conversion implementation
+#[cfg(feature = "std")]
 impl From<&TimestampRasn> for Timestamp {
     fn from(rasn_ts: &TimestampRasn) -> Self {
         Timestamp {
@@ -405,6 +908,7 @@ impl From<&TimestampRasn> for Timestamp {
 }
 
 // Also add the reverse conversion for encoding
+#[cfg(feature = "std")]
 impl From<&Timestamp> for TimestampRasn {
     fn from(ts: &Timestamp) -> Self {
         TimestampRasn::from_bytes(ts.to_bytes())
@@ -413,6 +917,7 @@ impl From<&Timestamp> for TimestampRasn {
 
 /** Data types allowed with a GOOSE */
 #[non_exhaustive]
+#[cfg(feature = "std")]
 #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
 #[rasn(choice)]
 pub enum IECDataRasn {
@@ -460,8 +965,13 @@ pub enum IECDataRasn {
 }
 
 /// Serializable IEC data types for JSON/external use
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "type", content = "value")]
+///
+/// This is the single logical representation of data set values: GOOSE encoding and
+/// decoding convert it to and from the wire form `IECDataRasn`, and the MMS client
+/// produces it from MMS `Data`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(tag = "type", content = "value"))]
 pub enum IECData {
     /// Array of IEC data elements
     Array(Vec<IECData>),
@@ -475,15 +985,20 @@ pub enum IECData {
     /// Bit string (binary encoded, e.g. "0000000000001000")
     BitString(String),
 
-    /// Signed integer
+    /// Signed integer of any width (INT8 to INT64). BER keeps the content in the fewest
+    /// octets, so the width is not needed to re-encode it. Non-minimal encodings sent
+    /// by other publishers re-encode in the minimal form.
     Int(i64),
 
-    /// Unsigned integer
+    /// Unsigned integer of any width (INT8U to INT32U)
     UInt(u64),
 
-    /// Floating point number
+    /// Floating point number, encoded as FLOAT64
     Float(f64),
 
+    /// Single precision floating point number, encoded as FLOAT32
+    Float32(f32),
+
     /// Octet string (hex encoded)
     OctetString(String),
 
@@ -497,12 +1012,89 @@ pub enum IECData {
     Timestamp(Timestamp),
 }
 
+impl IECData {
+    /// Name of the variant, as used for the `type` field of the JSON form
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            IECData::Array(_) => "Array",
+            IECData::Structure(_) => "Structure",
+            IECData::Boolean(_) => "Boolean",
+            IECData::BitString(_) => "BitString",
+            IECData::Int(_) => "Int",
+            IECData::UInt(_) => "UInt",
+            IECData::Float(_) => "Float",
+            IECData::Float32(_) => "Float32",
+            IECData::OctetString(_) => "OctetString",
+            IECData::VisibleString(_) => "VisibleString",
+            IECData::MmsString(_) => "MmsString",
+            IECData::Timestamp(_) => "Timestamp",
+        }
+    }
+
+    /// Returns the value of a Boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            IECData::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an Int, or of a UInt that fits into i64
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            IECData::Int(value) => Some(*value),
+            IECData::UInt(value) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a UInt, or of a non-negative Int
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            IECData::UInt(value) => Some(*value),
+            IECData::Int(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a Float or Float32. Int and UInt values are converted, which
+    /// loses precision above 2^53.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            IECData::Float(value) => Some(*value),
+            IECData::Float32(value) => Some(*value as f64),
+            IECData::Int(value) => Some(*value as f64),
+            IECData::UInt(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the nested element at `path`, where each index selects a child of an
+    /// Array or Structure. An empty path returns the element itself, an index out of
+    /// range or a step into a value that is not a container returns None.
+    pub fn get_path(&self, path: &[usize]) -> Option<&IECData> {
+        path.iter().try_fold(self, |data, &index| match data {
+            IECData::Array(children) | IECData::Structure(children) => children.get(index),
+            _ => None,
+        })
+    }
+
+    /// Returns the text of a VisibleString or MmsString
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            IECData::VisibleString(value) | IECData::MmsString(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
 /// The type and structural shape of a data element, as returned by MMS
 /// GetDataDefinition (GetVariableAccessAttributes). Leaf variants carry no
 /// value payload — the actual value lives in the corresponding [`IECData`]
 /// node at the same positional index.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "kind")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(tag = "kind"))]
 pub enum DataType {
     /// Ordered, named fields — paired positionally with [`IECData::Structure`]
     Structure(Vec<DataDefinition>),
@@ -526,7 +1118,8 @@ pub enum DataType {
 }
 
 /// Maps to the BIT STRING `TrgOps` attribute. Bit 0 is reserved.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct TriggerOptions {
     /// Data change (dchg) — bit 0
     pub data_change: bool,
@@ -568,7 +1161,8 @@ impl TriggerOptions {
 /// Optional fields included in each report entry (OptFlds), IEC 61850-7-2 Table 97
 ///
 /// Maps to the BIT STRING `OptFlds` attribute. Bit 0 is reserved.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ReportOptFields {
     /// Sequence number — bit 1
     pub sequence_number: bool,
@@ -630,7 +1224,8 @@ impl ReportOptFields {
 /// Same bit layout as [`ReportOptFields`] but `buffer_overflow` (bit 6) and
 /// `entry_id` (bit 7) are not applicable to unbuffered reports and are always
 /// encoded as `0`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct UnbufferedReportOptFields {
     /// Sequence number — bit 1
     pub sequence_number: bool,
@@ -684,7 +1279,8 @@ impl UnbufferedReportOptFields {
 /// Settings for a Buffered Report Control Block (BRCB) write operation
 /// All fields are optional — set only the attributes you want to write.
 /// Read-only attributes (`SqNum`, `TimeOfEntry`, `ConfRev`, `Owner`) are excluded.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SetBrcbValuesSettings {
     /// Report identifier (RptID)
     pub rpt_id: Option<String>,
@@ -717,7 +1313,8 @@ pub struct SetBrcbValuesSettings {
 /// - Bytes 4–5: days since **1 January 1984** (big-endian `u16`)
 ///
 /// This differs from `UtcTime`/`TimeStamp` which uses the Unix epoch (1 Jan 1970).
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EntryTime(pub Vec<u8>);
 
 impl EntryTime {
@@ -738,7 +1335,8 @@ impl EntryTime {
 
 /// Full set of attributes returned by reading a Buffered Report Control Block (BRCB).
 /// Includes both settable and read-only attributes.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BufferedReportControlBlock {
     /// Report identifier (RptID)
     pub rpt_id: String,
@@ -776,7 +1374,8 @@ pub struct BufferedReportControlBlock {
 /// All fields are optional — set only the attributes you want to write.
 /// Read-only attributes (`SqNum`, `ConfRev`, `Owner`) are excluded.
 /// URCB has no `PurgeBuf`, no `EntryID`, and no `ResvTms`; instead it has `Resv` (boolean reservation).
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SetUrcbValuesSettings {
     /// Report identifier (RptID)
     pub rpt_id: Option<String>,
@@ -801,7 +1400,8 @@ pub struct SetUrcbValuesSettings {
 /// Full set of attributes returned by reading an Unbuffered Report Control Block (URCB).
 /// Includes both settable and read-only attributes.
 /// URCB has no `PurgeBuf`, `EntryID`, `TimeOfEntry`, or `ResvTms`; instead it has `Resv`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct UnbufferedReportControlBlock {
     /// Report identifier (RptID)
     pub rpt_id: String,
@@ -830,14 +1430,16 @@ pub struct UnbufferedReportControlBlock {
 }
 
 /// Whether a report originates from a Buffered (BRCB) or Unbuffered (URCB) report control block.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ReportType {
     Buffered,
     Unbuffered,
 }
 
 /// Per-dataset-member reason why it was included in the report (IEC 61850-7-2).
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ReasonForInclusion {
     /// Data value changed (bit 1)
     pub data_change: bool,
@@ -864,7 +1466,8 @@ impl ReasonForInclusion {
 }
 
 /// Header/metadata fields present in every IEC 61850 report.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ReportMetadata {
     /// Whether this came from a BRCB or URCB
     pub report_type: ReportType,
@@ -894,7 +1497,8 @@ pub struct ReportMetadata {
 }
 
 /// A single dataset member value included in a report.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ReportDataPoint {
     /// MMS data reference (e.g. `"BCUApp/XCBR1$ST$Pos"`) —
     /// present if `opt_flds.data_reference`
@@ -906,14 +1510,16 @@ pub struct ReportDataPoint {
 }
 
 /// A fully decoded IEC 61850 report produced by `subscribe_reports`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Report {
     pub metadata: ReportMetadata,
     pub data: Vec<ReportDataPoint>,
 }
 
 /// Step command direction for BSC (Binary Step Control) and BAC (Binary Analog Control).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Tcmd {
     Stop = 0,
     Lower = 1,
@@ -922,7 +1528,8 @@ pub enum Tcmd {
 }
 
 /// Analogue value for APC (Analog Process Control).
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct AnalogueValue {
     /// IEEE 754 FLOAT32
     pub f: Option<f32>,
@@ -931,7 +1538,8 @@ pub struct AnalogueValue {
 }
 
 /// Control value for a control action. The variant must match the data object's `ctlVal` type.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum CtlVal {
     /// For SPC and DPC common data classes — BOOLEAN
     Bool(bool),
@@ -944,7 +1552,8 @@ pub enum CtlVal {
 }
 
 /// Originator category (orCat).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum OriginCategory {
     #[default]
     NotSupported = 0,
@@ -975,7 +1584,8 @@ impl OriginCategory {
 }
 
 /// Interlocking and synchronism-check conditions (Check BIT STRING, 2 bits).
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Check {
     /// Synchronism check required — bit 0
     pub synchrocheck: bool,
@@ -994,7 +1604,8 @@ impl Check {
 }
 
 /// Originator of a control command (orCat + orIdent).
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Originator {
     /// Originator category
     pub or_cat: OriginCategory,
@@ -1003,7 +1614,8 @@ pub struct Originator {
 }
 
 /// Parameters for a control service request (`operate`, `select-with-value`).
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ControlObject {
     /// IEC 61850 reference to the control object, e.g. `"IEDLD/CSWI1.Pos"`
     pub ctrl_obj_ref: String,
@@ -1024,7 +1636,8 @@ pub struct ControlObject {
 }
 
 /// Parameters for a cancel service request.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CancelObject {
     /// IEC 61850 reference to the control object, e.g. `"IEDLD/CSWI1.Pos"`
     pub ctrl_obj_ref: String,
@@ -1043,7 +1656,8 @@ pub struct CancelObject {
 }
 
 /// Additional cause for a rejected control commands.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AddCause {
     Unknown = 0,
     NotSupported = 1,
@@ -1114,7 +1728,8 @@ impl AddCause {
 /// Response to a control service request (`operate`, `select-with-value`)
 /// A positive response has `add_cause = None`.  A negative response
 /// has `add_cause = Some(cause)`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ControlResponse {
     /// IEC 61850 reference to the control object (e.g. `"IEDLD/CSWI1.Pos"`)
     pub ctrl_obj_ref: String,
@@ -1138,7 +1753,8 @@ pub struct ControlResponse {
 
 /// Response to a cancel service request. A positive response has `add_cause = None`.  
 /// A negative response has `add_cause = Some(cause)`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CancelResponse {
     pub ctrl_obj_ref: String,
     pub ctl_val: CtlVal,
@@ -1156,12 +1772,26 @@ pub struct CancelResponse {
 /// Combines the element name (from the service response) with a [`DataType`]
 /// that mirrors the structural shape of [`IECData`], enabling positional
 /// resolution of received values without an additional schema look-up.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct DataDefinition {
     pub name: String,
     pub data_type: DataType,
 }
 
+/// Declared types of the data set entries of a GOOSE control block, as configured in
+/// the SCL of the publisher.
+///
+/// The BER tags of GOOSE data do not tell signed from unsigned integers reliably, the
+/// template resolves them. See `decode_goose::decode_goose_pdu_typed`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DataTemplate {
+    /// One type per data set entry, in data set order
+    pub entries: Vec<DataType>,
+}
+
+#[cfg(feature = "std")]
 impl From<&IECDataRasn> for IECData {
     fn from(data: &IECDataRasn) -> Self {
         match data {
@@ -1171,32 +1801,21 @@ impl From<&IECDataRasn> for IECData {
             }
             IECDataRasn::Boolean(b) => IECData::Boolean(*b),
             IECDataRasn::BitString(bits) => {
-                // Convert BitString to binary string
-                let bytes = bits.as_raw_slice();
-                let mut binary_string = String::new();
-                for byte in bytes {
-                    binary_string.push_str(&format!("{:08b}", byte));
-                }
-                IECData::BitString(binary_string)
+                // One character per bit, so the unused bits of the last octet are dropped
+                IECData::BitString(
+                    bits.iter()
+                        .map(|bit| if *bit { '1' } else { '0' })
+                        .collect(),
+                )
             }
             IECDataRasn::Int(i) => IECData::Int(i64::try_from(i).unwrap_or(0)),
             IECDataRasn::UInt(u) => IECData::UInt(u64::try_from(u).unwrap_or(0)),
-            IECDataRasn::Float(fp) => {
-                // Decode FloatingPoint to f64
-                let bytes = fp.0.as_ref();
-                if bytes.len() == 4 {
-                    let value = f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-                    IECData::Float(value as f64)
-                } else if bytes.len() == 8 {
-                    let value = f64::from_be_bytes([
-                        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6],
-                        bytes[7],
-                    ]);
-                    IECData::Float(value)
-                } else {
-                    IECData::Float(0.0)
-                }
-            }
+            // FLOAT32 stays single precision so it re-encodes in 5 octets, values of
+            // unexpected length decode as 0.0
+            IECDataRasn::Float(fp) => match fp.as_f32() {
+                Some(value) => IECData::Float32(value),
+                None => IECData::Float(fp.as_f64().unwrap_or(0.0)),
+            },
             IECDataRasn::OctetString(octets) => IECData::OctetString(hex::encode(octets.as_ref())),
             IECDataRasn::VisibleString(s) => IECData::VisibleString(s.to_string()),
             IECDataRasn::MmsString(mms) => IECData::MmsString(mms.0.to_string()),
@@ -1205,6 +1824,7 @@ impl From<&IECDataRasn> for IECData {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<&IECData> for IECDataRasn {
     fn from(data: &IECData) -> Self {
         match data {
@@ -1214,23 +1834,19 @@ impl From<&IECData> for IECDataRasn {
             }
             IECData::Boolean(b) => IECDataRasn::Boolean(*b),
             IECData::BitString(binary_str) => {
-                // Parse binary string to BitString
-                let mut bytes = Vec::new();
-                for chunk in binary_str.as_bytes().chunks(8) {
-                    let byte_str = std::str::from_utf8(chunk).unwrap_or("00000000");
-                    if let Ok(byte) = u8::from_str_radix(byte_str, 2) {
-                        bytes.push(byte);
-                    }
-                }
-                IECDataRasn::BitString(BitString::from_vec(bytes))
+                // One bit per character, so the BER unused-bit count follows the string length
+                IECDataRasn::BitString(binary_str.chars().map(|c| c == '1').collect::<BitString>())
             }
             IECData::Int(i) => IECDataRasn::Int(Integer::from(*i)),
-            IECData::UInt(u) => IECDataRasn::UInt(Integer::from(*u as i64)),
-            IECData::Float(f) => {
-                // Encode f64 to FloatingPoint (8 bytes)
-                let bytes = f.to_be_bytes();
-                IECDataRasn::Float(FloatingPoint(OctetString::from(bytes.to_vec())))
+            IECData::UInt(u) => {
+                // Values above i64::MAX would wrap to negative numbers as i64
+                let value = i64::try_from(*u)
+                    .map(Integer::from)
+                    .unwrap_or_else(|_| Integer::from(*u));
+                IECDataRasn::UInt(value)
             }
+            IECData::Float(f) => IECDataRasn::Float(FloatingPoint::from_f64(*f)),
+            IECData::Float32(f) => IECDataRasn::Float(FloatingPoint::from_f32(*f)),
             IECData::OctetString(hex_str) => {
                 let bytes = hex::decode(hex_str).unwrap_or_default();
                 IECDataRasn::OctetString(OctetString::from(bytes))
@@ -1246,7 +1862,69 @@ impl From<&IECData> for IECDataRasn {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+/// An Ethernet MAC address, displayed and parsed in the colon form `01:0c:cd:04:00:01`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MacAddress(pub [u8; 6]);
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+impl str::FromStr for MacAddress {
+    type Err = DecodeError;
+
+    /// Parses the colon form with six groups of two hex digits, in either case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 6];
+        let mut groups = s.split(':');
+        let mut offset = 0;
+        for byte in bytes.iter_mut() {
+            let group = groups.next().unwrap_or_default();
+            // from_str_radix alone would accept a sign, e.g. "+f"
+            if group.len() != 2 || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(DecodeError::new(
+                    &format!(
+                        "Invalid MAC address {:?}: expected two hex digits, got {:?}",
+                        s, group
+                    ),
+                    offset,
+                ));
+            }
+            *byte = u8::from_str_radix(group, 16).unwrap_or_default();
+            offset += group.len() + 1;
+        }
+        if groups.next().is_some() {
+            return Err(DecodeError::new(
+                &format!("Invalid MAC address {:?}: more than six groups", s),
+                offset - 1,
+            ));
+        }
+        Ok(MacAddress(bytes))
+    }
+}
+
+impl From<[u8; 6]> for MacAddress {
+    fn from(bytes: [u8; 6]) -> Self {
+        MacAddress(bytes)
+    }
+}
+
+impl From<MacAddress> for [u8; 6] {
+    fn from(mac: MacAddress) -> Self {
+        mac.0
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EthernetHeader {
     /** Source MAC-Address */
     pub dst_addr: [u8; 6],
@@ -1264,6 +1942,97 @@ pub struct EthernetHeader {
     pub length: [u8; 2],
 }
 
+impl EthernetHeader {
+    /// Creates a header without VLAN tag for a Sampled Values stream (EtherType 0x88BA)
+    pub fn for_smv(dst_addr: [u8; 6], src_addr: [u8; 6], appid: [u8; 2]) -> Self {
+        EthernetHeader {
+            dst_addr,
+            src_addr,
+            tpid: None,
+            tci: None,
+            ether_type: ETHERTYPE_SMV,
+            appid,
+            length: [0x00, 0x00],
+        }
+    }
+
+    /// Creates a header without VLAN tag for a GOOSE message (EtherType 0x88B8)
+    pub fn for_goose(dst_addr: [u8; 6], src_addr: [u8; 6], appid: [u8; 2]) -> Self {
+        EthernetHeader {
+            dst_addr,
+            src_addr,
+            tpid: None,
+            tci: None,
+            ether_type: ETHERTYPE_GOOSE,
+            appid,
+            length: [0x00, 0x00],
+        }
+    }
+
+    /// Gets the destination address
+    pub fn dst_mac(&self) -> MacAddress {
+        MacAddress(self.dst_addr)
+    }
+
+    /// Gets the source address
+    pub fn src_mac(&self) -> MacAddress {
+        MacAddress(self.src_addr)
+    }
+
+    /// Gets the VLAN priority (PCP, upper 3 bits of the TCI), if the frame is VLAN tagged
+    pub fn vlan_priority(&self) -> Option<u8> {
+        self.tci.map(|tci| tci[0] >> 5)
+    }
+
+    /// Gets the VLAN ID (lower 12 bits of the TCI), if the frame is VLAN tagged
+    pub fn vlan_id(&self) -> Option<u16> {
+        self.tci.map(|tci| u16::from_be_bytes(tci) & 0x0FFF)
+    }
+
+    /// Tags the frame with a VLAN, as configured by VLAN-PRIORITY and VLAN-ID in SCL.
+    ///
+    /// # Parameters
+    /// - `priority`: The VLAN priority (PCP), 0 to 7.
+    /// - `vid`: The VLAN ID, 0 to 4094 (0 for priority tagged frames without VLAN).
+    ///
+    /// # Returns
+    /// An EncodeError if the priority or VLAN ID is out of range, the header is unchanged then
+    pub fn set_vlan(&mut self, priority: u8, vid: u16) -> Result<(), EncodeError> {
+        if priority > 7 {
+            return Err(EncodeError::new(
+                &format!("VLAN priority must be 0 to 7, got {}", priority),
+                0,
+            ));
+        }
+        if vid > 4094 {
+            return Err(EncodeError::new(
+                &format!("VLAN ID must be 0 to 4094, got {}", vid),
+                0,
+            ));
+        }
+        self.tpid = Some([0x81, 0x00]);
+        self.tci = Some((((priority as u16) << 13) | vid).to_be_bytes());
+        Ok(())
+    }
+
+    /// Serializes the header in its wire format, e.g. to store it as template.
+    /// The reserved fields are written as zero.
+    pub fn to_wire(&self) -> Vec<u8> {
+        crate::encode_basics::encode_ethernet_header(self, u16::from_be_bytes(self.length))
+    }
+
+    /// Parses a header in its wire format, as written by `to_wire`.
+    ///
+    /// # Returns
+    /// The header and the number of bytes it occupies
+    pub fn from_wire(bytes: &[u8]) -> Result<(EthernetHeader, usize), DecodeError> {
+        let mut header = EthernetHeader::default();
+        let pos = crate::decode_basics::try_decode_ethernet_header(&mut header, bytes)?;
+        Ok((header, pos))
+    }
+}
+
+#[cfg(feature = "std")]
 #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
 #[rasn(tag(application, 1))] // <-- ADD THIS! GOOSE uses APPLICATION tag class
 pub struct IECGoosePduRasn {
@@ -1316,7 +2085,8 @@ pub struct IECGoosePduRasn {
     pub all_data: SequenceOf<IECDataRasn>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct IECGoosePdu {
     /** Reference to GOOSE control block in the data model of the sending IED */
     pub go_cb_ref: String,
@@ -1344,14 +2114,374 @@ pub struct IECGoosePdu {
     pub all_data: Vec<IECData>,
 }
 
-impl From<&IECGoosePduRasn> for IECGoosePdu {
-    fn from(pdu: &IECGoosePduRasn) -> Self {
+/// The GOOSE PDU fields needed to track a publisher, decoded without the data set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GooseHeaderView {
+    /** Reference to GOOSE control block in the data model of the sending IED */
+    pub go_cb_ref: String,
+    /** Time allowed to live until the next GOOSE packet */
+    pub time_allowed_to_live: u32,
+    /** Reference to the data set the GOOSE is shipping */
+    pub dat_set: String,
+    /** GOOSE ID as defined in GSEControl.appID, empty if not sent */
+    pub go_id: String,
+    /** Status number - counter for changes in GOOSE data */
+    pub st_num: u32,
+    /** Sequence number - counter for repeating GOOSE packets */
+    pub sq_num: u32,
+    /** Whether the GOOSE is a simulated */
+    pub simulation: bool,
+}
+
+/// Increments a GOOSE state or sequence number, rolling over to 1 as 0 is reserved for
+/// the first frame
+pub(crate) fn next_counter(counter: u32) -> u32 {
+    if counter == u32::MAX {
+        1
+    } else {
+        counter + 1
+    }
+}
+
+impl IECGoosePdu {
+    /// Creates a GOOSE PDU for the given control block with an empty data set.
+    ///
+    /// The timestamp is set to now, state and sequence number start at 0, all flags
+    /// are cleared, the configuration revision is 1 and the time allowed to live is
+    /// 2000 ms. The data set entries are supposed to be filled in before publishing.
+    #[cfg(feature = "std")]
+    pub fn new(go_cb_ref: &str, dat_set: &str, go_id: &str) -> Self {
         IECGoosePdu {
-            go_cb_ref: pdu.go_cb_ref.to_string(),
-            time_allowed_to_live: u32::try_from(&pdu.time_allowed_to_live).unwrap_or(0),
-            dat_set: pdu.dat_set.to_string(),
-            go_id: pdu.go_id.to_string(),
-            t: Timestamp::from(&pdu.t),
+            go_cb_ref: go_cb_ref.to_string(),
+            time_allowed_to_live: 2000,
+            dat_set: dat_set.to_string(),
+            go_id: go_id.to_string(),
+            t: Timestamp::now(),
+            st_num: 0,
+            sq_num: 0,
+            simulation: false,
+            conf_rev: 1,
+            nds_com: false,
+            num_dat_set_entries: 0,
+            all_data: vec![],
+        }
+    }
+
+    /// Prepares the PDU for a retransmission of the current state.
+    ///
+    /// The sequence number is incremented, rolling over to 1 as 0 marks the first frame
+    /// of a state. State number, timestamp and data set are left unchanged.
+    pub fn tick(&mut self) {
+        self.sq_num = next_counter(self.sq_num);
+    }
+
+    /// Replaces the data set after an event.
+    ///
+    /// The state number is incremented (rolling over to 1), the sequence number reset to
+    /// 0 and the timestamp set to now. `num_dat_set_entries` follows the new data set.
+    #[cfg(feature = "std")]
+    pub fn state_change(&mut self, all_data: Vec<IECData>) {
+        self.st_num = next_counter(self.st_num);
+        self.sq_num = 0;
+        self.t = Timestamp::now();
+        self.num_dat_set_entries = all_data.len() as u32;
+        self.all_data = all_data;
+    }
+
+    /// Checks whether the PDU has exceeded its time allowed to live at `now`.
+    ///
+    /// A frame is stale once `now` lies after `t` plus the time allowed to live.
+    /// Subscribers use this to detect a publisher that stopped sending.
+    pub fn is_stale(&self, now: &Timestamp) -> bool {
+        let expires_at = self.t.as_nanos() + self.time_allowed_to_live as u64 * 1_000_000;
+        now.as_nanos() > expires_at
+    }
+
+    /// Checks the PDU for inconsistencies between its fields.
+    ///
+    /// The data set is decoded with however many entries are present, so a
+    /// `num_dat_set_entries` that does not match `all_data` is only found here.
+    ///
+    /// # Returns
+    /// `Ok(())`, or a description of the first inconsistency found
+    pub fn validate(&self) -> Result<(), String> {
+        if self.num_dat_set_entries as usize != self.all_data.len() {
+            return Err(format!(
+                "numDatSetEntries is {}, but the data set holds {} entries",
+                self.num_dat_set_entries,
+                self.all_data.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks the PDU before publishing it: besides the checks of `validate`, empty
+    /// control block, data set or GOOSE references and a time allowed to live of 0 are
+    /// rejected, as subscribers drop such frames. Received frames may legitimately omit
+    /// some of these, so the decoders only apply `validate`.
+    ///
+    /// # Returns
+    /// `Ok(())`, or an EncodeError describing the first inconsistency found
+    pub fn validate_for_encode(&self) -> Result<(), EncodeError> {
+        self.validate()
+            .map_err(|message| EncodeError::new(&message, 0))?;
+        for (name, value) in [
+            ("gocbRef", &self.go_cb_ref),
+            ("datSet", &self.dat_set),
+            ("goID", &self.go_id),
+        ] {
+            if value.is_empty() {
+                return Err(EncodeError::new(&format!("{} is empty", name), 0));
+            }
+        }
+        if self.time_allowed_to_live == 0 {
+            return Err(EncodeError::new("timeAllowedtoLive is 0", 0));
+        }
+        Ok(())
+    }
+}
+
+/// Retransmission timing of a GOOSE publisher after a state change.
+///
+/// The first repetition follows after `min_interval_ms`, every further repetition
+/// doubles the interval until the heartbeat `max_interval_ms` is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RetransmissionSchedule {
+    /** Interval between the event frame and the first repetition */
+    pub min_interval_ms: u32,
+    /** Interval of the stable retransmission (heartbeat) */
+    pub max_interval_ms: u32,
+}
+
+impl Default for RetransmissionSchedule {
+    fn default() -> Self {
+        RetransmissionSchedule {
+            min_interval_ms: 2,
+            max_interval_ms: 1000,
+        }
+    }
+}
+
+impl RetransmissionSchedule {
+    /// Interval in ms between the frame with sequence number `sq_num` and the next one
+    pub fn interval_ms(&self, sq_num: u32) -> u32 {
+        let factor = 1u32.checked_shl(sq_num).unwrap_or(u32::MAX);
+        self.min_interval_ms
+            .saturating_mul(factor)
+            .min(self.max_interval_ms)
+    }
+}
+
+/// Retransmission state machine of a GOOSE publisher according to IEC 61850-8-1.
+///
+/// `on_event` starts a new state, `next_interval` tells how long to wait before the next
+/// repetition and advances the sequence number. Both counters roll over to 1, as 0 is
+/// reserved for the first frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GooseRetransmit {
+    schedule: RetransmissionSchedule,
+    st_num: u32,
+    sq_num: u32,
+}
+
+impl GooseRetransmit {
+    /// Creates the state machine at state number `st_num` with no repetition sent yet
+    pub fn new(schedule: RetransmissionSchedule, st_num: u32) -> Self {
+        GooseRetransmit {
+            schedule,
+            st_num,
+            sq_num: 0,
+        }
+    }
+
+    /// State number to send in the next frame
+    pub fn st_num(&self) -> u32 {
+        self.st_num
+    }
+
+    /// Sequence number to send in the next frame
+    pub fn sq_num(&self) -> u32 {
+        self.sq_num
+    }
+
+    /// Starts a new state: increments the state number and resets the sequence number
+    /// and thereby the interval to `min_interval_ms`
+    pub fn on_event(&mut self) {
+        self.st_num = next_counter(self.st_num);
+        self.sq_num = 0;
+    }
+
+    /// Returns the interval in ms until the next repetition and increments the sequence
+    /// number for it. The interval doubles up to `max_interval_ms`.
+    pub fn next_interval(&mut self) -> u32 {
+        let interval = self.schedule.interval_ms(self.sq_num);
+        self.sq_num = next_counter(self.sq_num);
+        interval
+    }
+}
+
+/// Tracks the liveness of GOOSE publishers on the subscriber side.
+///
+/// For every `go_cb_ref` the arrival time and time allowed to live of the last frame
+/// are kept. A publisher is stale once no frame arrived within that time, which
+/// IEC 61850-8-1 treats as loss of the GOOSE.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct GooseMonitor {
+    last_frames: BTreeMap<String, (Instant, u32)>,
+}
+
+#[cfg(feature = "std")]
+impl GooseMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the arrival of `pdu` at `now`
+    pub fn on_frame(&mut self, pdu: &IECGoosePdu, now: Instant) {
+        self.last_frames
+            .insert(pdu.go_cb_ref.clone(), (now, pdu.time_allowed_to_live));
+    }
+
+    /// Checks whether the last frame of `go_cb_ref` exceeded its time allowed to live
+    /// at `now`. Control blocks without any frame yet are not stale.
+    pub fn is_stale(&self, go_cb_ref: &str, now: Instant) -> bool {
+        self.last_frames.contains_key(go_cb_ref) && self.time_until_stale(go_cb_ref, now).is_none()
+    }
+
+    /// Time left at `now` until `go_cb_ref` becomes stale, `None` if it is stale
+    /// already or no frame was received
+    pub fn time_until_stale(&self, go_cb_ref: &str, now: Instant) -> Option<Duration> {
+        let (arrival, time_allowed_to_live) = self.last_frames.get(go_cb_ref)?;
+        let expires_at = *arrival + Duration::from_millis(*time_allowed_to_live as u64);
+        if now > expires_at {
+            return None;
+        }
+        Some(expires_at - now)
+    }
+
+    /// Control block references of all stale publishers at `now`
+    pub fn stale(&self, now: Instant) -> Vec<&str> {
+        self.last_frames
+            .keys()
+            .filter(|go_cb_ref| self.is_stale(go_cb_ref, now))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Static configuration of a GOOSE publisher, as given by its GSE control block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GooseConfig {
+    /** Reference to GOOSE control block in the data model of the publishing IED */
+    pub go_cb_ref: String,
+    /** Reference to the data set the GOOSE is shipping */
+    pub dat_set: String,
+    /** GOOSE ID as defined in GSEControl.appID */
+    pub go_id: String,
+    /** Configuration revision of the GOOSE control block */
+    pub conf_rev: u32,
+    /** Interval in ms between an event frame and its first repetition */
+    pub min_repetition: u32,
+    /** Interval in ms of the stable retransmission (heartbeat) */
+    pub max_repetition: u32,
+    /** Whether the GOOSE is simulated */
+    pub simulation: bool,
+    /** Whether the GOOSE needs commissioning */
+    pub nds_com: bool,
+    /** Destination (multicast) MAC-Address */
+    pub dst_addr: [u8; 6],
+    /** Source MAC-Address */
+    pub src_addr: [u8; 6],
+    /** APPID of the GOOSE */
+    pub appid: [u8; 2],
+    /** Data set entries to publish */
+    pub all_data: Vec<IECData>,
+}
+
+impl Default for GooseConfig {
+    fn default() -> Self {
+        let schedule = RetransmissionSchedule::default();
+        GooseConfig {
+            go_cb_ref: String::new(),
+            dat_set: String::new(),
+            go_id: String::new(),
+            conf_rev: 1,
+            min_repetition: schedule.min_interval_ms,
+            max_repetition: schedule.max_interval_ms,
+            simulation: false,
+            nds_com: false,
+            dst_addr: GOOSE_MCAST_BASE,
+            src_addr: [0; 6],
+            appid: [0x00, 0x00],
+            all_data: vec![],
+        }
+    }
+}
+
+impl GooseConfig {
+    /// The retransmission timing given by `min_repetition` and `max_repetition`
+    pub fn schedule(&self) -> RetransmissionSchedule {
+        RetransmissionSchedule {
+            min_interval_ms: self.min_repetition,
+            max_interval_ms: self.max_repetition,
+        }
+    }
+
+    /// The Ethernet header to publish with, without VLAN tag
+    pub fn ethernet_header(&self) -> EthernetHeader {
+        EthernetHeader::for_goose(self.dst_addr, self.src_addr, self.appid)
+    }
+
+    /// Builds the PDU of the frame described by `runtime`.
+    ///
+    /// The time allowed to live is twice the interval to the next frame, as in
+    /// `goose_event_burst`.
+    pub fn to_pdu(&self, runtime: &GooseRuntime) -> IECGoosePdu {
+        IECGoosePdu {
+            go_cb_ref: self.go_cb_ref.clone(),
+            time_allowed_to_live: self
+                .schedule()
+                .interval_ms(runtime.sq_num)
+                .saturating_mul(2),
+            dat_set: self.dat_set.clone(),
+            go_id: self.go_id.clone(),
+            t: runtime.timestamp.clone(),
+            st_num: runtime.st_num,
+            sq_num: runtime.sq_num,
+            simulation: self.simulation,
+            conf_rev: self.conf_rev,
+            nds_com: self.nds_com,
+            num_dat_set_entries: self.all_data.len() as u32,
+            all_data: self.all_data.clone(),
+        }
+    }
+}
+
+/// Changing state of a GOOSE publisher between frames
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GooseRuntime {
+    /** State number, incremented on every change of the data set */
+    pub st_num: u32,
+    /** Sequence number, incremented on every repetition of a state */
+    pub sq_num: u32,
+    /** Time of the last change of the data set */
+    pub timestamp: Timestamp,
+}
+
+#[cfg(feature = "std")]
+impl From<&IECGoosePduRasn> for IECGoosePdu {
+    fn from(pdu: &IECGoosePduRasn) -> Self {
+        IECGoosePdu {
+            go_cb_ref: pdu.go_cb_ref.to_string(),
+            time_allowed_to_live: u32::try_from(&pdu.time_allowed_to_live).unwrap_or(0),
+            dat_set: pdu.dat_set.to_string(),
+            go_id: pdu.go_id.to_string(),
+            t: Timestamp::from(&pdu.t),
             st_num: u32::try_from(&pdu.st_num).unwrap_or(0),
             sq_num: u32::try_from(&pdu.sq_num).unwrap_or(0),
             simulation: pdu.simulation,
@@ -1363,6 +2493,7 @@ impl From<&IECGoosePduRasn> for IECGoosePdu {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<&IECGoosePdu> for IECGoosePduRasn {
     fn from(pdu: &IECGoosePdu) -> Self {
         IECGoosePduRasn {
@@ -1385,36 +2516,266 @@ impl From<&IECGoosePdu> for IECGoosePduRasn {
     }
 }
 
+/// The value of a sample: an INTEGER or an IEEE-754 FLOAT32
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(untagged))]
+pub enum SampleValue {
+    /// Signed integer value (before scaling), encoded with tag 0x83
+    Int(i32),
+    /// Floating point value, encoded as FLOAT32 with tag 0x87
+    Float(f32),
+}
+
+impl SampleValue {
+    /// Returns the value as f32, converting integers
+    pub fn as_f32(&self) -> f32 {
+        match self {
+            SampleValue::Int(value) => *value as f32,
+            SampleValue::Float(value) => *value,
+        }
+    }
+}
+
+impl From<i32> for SampleValue {
+    fn from(value: i32) -> Self {
+        SampleValue::Int(value)
+    }
+}
+
+impl From<f32> for SampleValue {
+    fn from(value: f32) -> Self {
+        SampleValue::Float(value)
+    }
+}
+
+impl PartialEq<i32> for SampleValue {
+    fn eq(&self, other: &i32) -> bool {
+        matches!(self, SampleValue::Int(value) if value == other)
+    }
+}
+
+impl fmt::Display for SampleValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleValue::Int(value) => write!(f, "{}", value),
+            SampleValue::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 /// A single sampled value with its quality
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Sample {
-    /// The integer value (before scaling)
-    pub value: i32,
+    /// The value (integer before scaling, or float)
+    pub value: SampleValue,
     /// The quality flags
     pub quality: Quality,
 }
 
+/// Compact serde representation of a `Quality` as hex string of its 16-bit wire value,
+/// e.g. `"0x4000"`, for use with `#[serde(with = "iec_61850::types::quality_hex")]`.
+///
+/// `Quality` itself serializes all flags by name; this is an opt-in alternative for
+/// fields of your own types.
+#[cfg(feature = "std")]
+pub mod quality_hex {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::Quality;
+
+    pub fn serialize<S: Serializer>(quality: &Quality, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{:04x}", quality.to_u16()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Quality, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let digits = text.strip_prefix("0x").unwrap_or(&text);
+        u16::from_str_radix(digits, 16)
+            .map(Quality::from_u16)
+            .map_err(|_| de::Error::custom(format!("invalid quality \"{}\"", text)))
+    }
+}
+
+/// Serde representation of an optional 8-byte field as hex string, e.g. `"0011223344556677"`
+#[cfg(feature = "std")]
+mod hex_octets {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<[u8; 8]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_str(&hex::encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<[u8; 8]>, D::Error> {
+        let Some(text) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        hex::decode(&text)
+            .ok()
+            .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                de::Error::custom(format!("expected 8 hex encoded bytes, got \"{}\"", text))
+            })
+    }
+}
+
 impl Sample {
     /// Creates a new sample from raw value and quality bitstring (16-bit)
     pub fn new(value: i32, quality_bits: u16) -> Self {
         Sample {
-            value,
+            value: SampleValue::Int(value),
+            quality: Quality::from_u16(quality_bits),
+        }
+    }
+
+    /// Creates a new float sample from value and quality bitstring (16-bit)
+    pub fn new_float(value: f32, quality_bits: u16) -> Self {
+        Sample {
+            value: SampleValue::Float(value),
             quality: Quality::from_u16(quality_bits),
         }
     }
 
     /// Creates a new sample from value and quality
-    pub fn from_parts(value: i32, quality: Quality) -> Self {
-        Sample { value, quality }
+    pub fn from_parts(value: impl Into<SampleValue>, quality: Quality) -> Self {
+        Sample {
+            value: value.into(),
+            quality,
+        }
     }
 
-    /// Scales the integer value by a factor
+    /// Scales the value by a factor
     pub fn scaled_value(&self, scale: f32) -> f32 {
-        self.value as f32 * scale
+        self.value.as_f32() * scale
+    }
+
+    /// Returns the quality as 16-bit word, as passed to `Sample::new`
+    pub fn raw_quality(&self) -> u16 {
+        self.quality.to_u16()
+    }
+}
+
+/// Mapping between physical channel values and the integer sample values of an ASDU.
+///
+/// Each channel has a scale factor giving the physical value of one unit of the
+/// integer sample value, e.g. 0.001 for currents in mA resolution.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ChannelMap {
+    /** Scale factor per channel, in data set order */
+    pub scales: Vec<f32>,
+}
+
+impl ChannelMap {
+    pub fn new(scales: Vec<f32>) -> Self {
+        ChannelMap { scales }
+    }
+
+    /// The channels of the 9-2 LE data set: four currents with 1 mA and four
+    /// voltages with 10 mV resolution
+    pub fn le_9_2() -> Self {
+        ChannelMap::new(vec![0.001, 0.001, 0.001, 0.001, 0.01, 0.01, 0.01, 0.01])
+    }
+
+    /// Converts physical values into samples, rounding to the nearest integer value
+    ///
+    /// # Parameters
+    /// - `values`: One physical value per channel.
+    /// - `quality`: The quality of all samples.
+    ///
+    /// # Returns
+    /// The samples, or an EncodeError if the number of values does not match the channels
+    pub fn to_samples(&self, values: &[f32], quality: Quality) -> Result<Vec<Sample>, EncodeError> {
+        if values.len() != self.scales.len() {
+            return Err(EncodeError::new(
+                &format!(
+                    "Expected {} channel values, got {}",
+                    self.scales.len(),
+                    values.len()
+                ),
+                0,
+            ));
+        }
+
+        Ok(values
+            .iter()
+            .zip(&self.scales)
+            .map(|(value, scale)| {
+                Sample::from_parts(round(*value as f64 / *scale as f64) as i32, quality)
+            })
+            .collect())
+    }
+
+    /// Converts samples back into physical values
+    pub fn to_values(&self, samples: &[Sample]) -> Vec<f32> {
+        samples
+            .iter()
+            .zip(&self.scales)
+            .map(|(sample, scale)| sample.scaled_value(*scale))
+            .collect()
+    }
+}
+
+/// Rounds half away from zero like `f64::round`, which needs `std`
+fn round(value: f64) -> f64 {
+    let truncated = value as i64 as f64;
+    let fract = value - truncated;
+    if fract >= 0.5 {
+        truncated + 1.0
+    } else if fract <= -0.5 {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+/// Sample counter of a Sampled Values publisher.
+///
+/// `count` runs from 0 to `rate - 1` and then starts over, e.g. 0 to 3999 for 80 samples
+/// per cycle at 50 Hz. A `rate` of 0 lets the counter use the full range up to 65535.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SampleCounter {
+    /** Number of sample counts before the counter wraps, 0 for 65536 */
+    pub rate: u16,
+    /** smpCnt of the next sample */
+    pub count: u16,
+}
+
+impl SampleCounter {
+    /// Creates a counter starting at 0 that wraps at `rate`
+    pub fn new(rate: u16) -> Self {
+        SampleCounter { rate, count: 0 }
+    }
+
+    /// Returns the smpCnt for the next sample and advances the counter. A `count` at or
+    /// above `rate`, e.g. written directly, is taken modulo `rate`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u16 {
+        let count = match self.rate {
+            0 => self.count,
+            rate => self.count % rate,
+        };
+        self.count = match count.wrapping_add(1) {
+            next if self.rate > 0 && next >= self.rate => 0,
+            next => next,
+        };
+        count
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SavAsdu {
     /** Multicast Sampled Values ID as defined in tSampledValueControl.svId*/
     pub msv_id: String,
@@ -1424,7 +2785,8 @@ pub struct SavAsdu {
     pub smp_cnt: u16,
     /** Configuration revision of the GOOSE control block */
     pub conf_rev: u32,
-    /** Transmission time of the ASDU */
+    /** Transmission time of the ASDU, serialized as hex string */
+    #[cfg_attr(feature = "std", serde(default, with = "hex_octets"))]
     pub refr_tm: Option<[u8; 8]>,
     /** How the sample value stream is time synchronized 0 = not, 1 = locally and 2 globally */
     pub smp_synch: u8,
@@ -1432,10 +2794,87 @@ pub struct SavAsdu {
     /** All sampled data with quality */
     pub all_data: Vec<Sample>,
     pub smp_mod: Option<u16>,
+    /** Grandmaster clock identity, serialized as hex string */
+    #[cfg_attr(feature = "std", serde(default, with = "hex_octets"))]
     pub gm_identity: Option<[u8; 8]>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+/// Sampling mode of a Sampled Values stream (smpMod), defining the unit of smpRate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SmpMod {
+    /** smpRate is the number of samples per nominal period */
+    SamplesPerPeriod,
+    /** smpRate is the number of samples per second */
+    SamplesPerSecond,
+    /** smpRate is the number of seconds per sample */
+    SecondsPerSample,
+    /** Value not defined by IEC 61850-9-2 */
+    Other(u16),
+}
+
+impl From<u16> for SmpMod {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => SmpMod::SamplesPerPeriod,
+            1 => SmpMod::SamplesPerSecond,
+            2 => SmpMod::SecondsPerSample,
+            other => SmpMod::Other(other),
+        }
+    }
+}
+
+impl From<SmpMod> for u16 {
+    fn from(mode: SmpMod) -> Self {
+        match mode {
+            SmpMod::SamplesPerPeriod => 0,
+            SmpMod::SamplesPerSecond => 1,
+            SmpMod::SecondsPerSample => 2,
+            SmpMod::Other(value) => value,
+        }
+    }
+}
+
+impl SavAsdu {
+    /// Gets the refresh time, if refrTm is present in the ASDU
+    pub fn refresh_time(&self) -> Option<Timestamp> {
+        self.refr_tm.map(Timestamp::from_bytes)
+    }
+
+    /// Sets refrTm to the given time
+    pub fn set_refresh_time(&mut self, ts: &Timestamp) {
+        self.refr_tm = Some(ts.to_bytes());
+    }
+
+    /// Gets the sampling mode, if smpMod is present in the ASDU
+    pub fn sample_mode(&self) -> Option<SmpMod> {
+        self.smp_mod.map(SmpMod::from)
+    }
+
+    /// Gets the interval between two samples in microseconds from smpRate and smpMod.
+    ///
+    /// Without smpMod the rate is interpreted as samples per nominal period, which is
+    /// the default of IEC 61850-9-2. Returns None if smpRate is missing or zero, the
+    /// sampling mode is unknown or a per-period rate is combined with an invalid
+    /// nominal frequency.
+    ///
+    /// # Parameters
+    /// - `nominal_hz`: The nominal frequency of the power system, e.g. 50.0 or 60.0.
+    pub fn sample_period_us(&self, nominal_hz: f64) -> Option<f64> {
+        let rate = self.smp_rate.filter(|rate| *rate > 0)? as f64;
+
+        match self.sample_mode().unwrap_or(SmpMod::SamplesPerPeriod) {
+            SmpMod::SamplesPerPeriod if nominal_hz > 0.0 => Some(1_000_000.0 / (rate * nominal_hz)),
+            SmpMod::SamplesPerPeriod => None,
+            SmpMod::SamplesPerSecond => Some(1_000_000.0 / rate),
+            SmpMod::SecondsPerSample => Some(rate * 1_000_000.0),
+            SmpMod::Other(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SavPdu {
     /** Whether the sampled value stream is simulated */
     pub sim: bool,
@@ -1447,48 +2886,471 @@ pub struct SavPdu {
     pub sav_asdu: Vec<SavAsdu>,
 }
 
-#[derive(Debug)]
-pub enum EncodeError {
-    General {
-        message: String,
-        buffer_index: usize,
-    },
-    BufferTooSmall {
-        required: usize,
-        available: usize,
-    },
+impl SavPdu {
+    /// Interprets the security field as signature, see `SecurityInfo::parse`
+    pub fn security_info(&self) -> Option<SecurityInfo> {
+        self.security.as_deref().and_then(SecurityInfo::parse)
+    }
 }
 
-impl EncodeError {
-    pub fn new(msg: &str, buffer_index: usize) -> Self {
-        let mut chart = ['\0'; 128];
-        for (i, c) in msg.chars().take(128).enumerate() {
-            chart[i] = c;
+/// Signature carried in the security field of a SMV frame.
+///
+/// IEC 61850-9-2 leaves the content of the field open and this is not the IEC 62351-6
+/// structure: it is a best-effort convention of an algorithm identifier followed by the
+/// signature, see `SecurityInfo::parse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SecurityInfo {
+    /** Identifier of the signature algorithm */
+    pub algorithm: u8,
+    /** The signature value */
+    pub signature: Vec<u8>,
+}
+
+impl SecurityInfo {
+    /// Parses the contents of a security field of the shape `[0] algorithm` (one byte)
+    /// followed by `[1] signature` (OCTET STRING) with nothing after it.
+    ///
+    /// Returns `None` for any other layout; the raw bytes remain available in
+    /// `SavPdu::security`.
+    pub fn parse(bytes: &[u8]) -> Option<SecurityInfo> {
+        let mut tag = 0u8;
+        let mut length = 0usize;
+
+        let pos = decode_tag_length(&mut tag, &mut length, bytes, 0).ok()?;
+        if tag != 0x80 || length != 1 {
+            return None;
         }
-        EncodeError::General {
-            message: chart.iter().collect(),
-            buffer_index,
+        let algorithm = *bytes.get(pos)?;
+
+        let pos = decode_tag_length(&mut tag, &mut length, bytes, pos + 1).ok()?;
+        if tag != 0x81 || pos.checked_add(length)? != bytes.len() {
+            return None;
         }
+
+        Some(SecurityInfo {
+            algorithm,
+            signature: bytes[pos..].to_vec(),
+        })
     }
 }
 
-#[derive(Debug)]
-pub struct DecodeError {
+/// Builder for a `SavAsdu`, filling the sample block one sample at a time
+#[derive(Debug, Default, Clone)]
+pub struct SavAsduBuilder {
+    asdu: SavAsdu,
+}
+
+impl SavAsduBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the svID
+    pub fn msv_id(mut self, msv_id: &str) -> Self {
+        self.asdu.msv_id = msv_id.to_string();
+        self
+    }
+
+    /// Sets the optional data set reference
+    pub fn dat_set(mut self, dat_set: &str) -> Self {
+        self.asdu.dat_set = Some(dat_set.to_string());
+        self
+    }
+
+    /// Sets the sample count
+    pub fn smp_cnt(mut self, smp_cnt: u16) -> Self {
+        self.asdu.smp_cnt = smp_cnt;
+        self
+    }
+
+    /// Sets the configuration revision
+    pub fn conf_rev(mut self, conf_rev: u32) -> Self {
+        self.asdu.conf_rev = conf_rev;
+        self
+    }
+
+    /// Sets the optional refresh time
+    pub fn refr_tm(mut self, ts: &Timestamp) -> Self {
+        self.asdu.set_refresh_time(ts);
+        self
+    }
+
+    /// Sets the synchronisation source
+    pub fn smp_synch(mut self, smp_synch: u8) -> Self {
+        self.asdu.smp_synch = smp_synch;
+        self
+    }
+
+    /// Sets the optional sample rate
+    pub fn smp_rate(mut self, smp_rate: u16) -> Self {
+        self.asdu.smp_rate = Some(smp_rate);
+        self
+    }
+
+    /// Appends a sample to the sample block
+    pub fn sample(mut self, value: impl Into<SampleValue>, quality: Quality) -> Self {
+        self.asdu.all_data.push(Sample::from_parts(value, quality));
+        self
+    }
+
+    /// Sets the optional sampling mode
+    pub fn smp_mod(mut self, smp_mod: u16) -> Self {
+        self.asdu.smp_mod = Some(smp_mod);
+        self
+    }
+
+    /// Sets the optional grandmaster clock identity
+    pub fn gm_identity(mut self, gm_identity: [u8; 8]) -> Self {
+        self.asdu.gm_identity = Some(gm_identity);
+        self
+    }
+
+    pub fn build(self) -> SavAsdu {
+        self.asdu
+    }
+}
+
+/// Builder for a `SavPdu` that keeps noASDU in sync with the added ASDUs
+#[derive(Debug, Default)]
+pub struct SavPduBuilder {
+    sim: bool,
+    security: Option<Vec<u8>>,
+    sav_asdu: Vec<SavAsdu>,
+}
+
+impl SavPduBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the PDU as simulated
+    pub fn sim(mut self, sim: bool) -> Self {
+        self.sim = sim;
+        self
+    }
+
+    /// Sets the optional security field
+    pub fn security(mut self, security: Vec<u8>) -> Self {
+        self.security = Some(security);
+        self
+    }
+
+    /// Appends an ASDU
+    pub fn asdu(mut self, asdu: SavAsdu) -> Self {
+        self.sav_asdu.push(asdu);
+        self
+    }
+
+    /// Builds the PDU, with noASDU set to the number of added ASDUs
+    ///
+    /// # Returns
+    /// The PDU, or an EncodeError if more than 65535 ASDUs were added
+    pub fn build(self) -> Result<SavPdu, EncodeError> {
+        let no_asdu = u16::try_from(self.sav_asdu.len()).map_err(|_| {
+            EncodeError::new(
+                &format!("noASDU must be at most 65535, got {}", self.sav_asdu.len()),
+                0,
+            )
+        })?;
+        Ok(SavPdu {
+            sim: self.sim,
+            no_asdu,
+            security: self.security,
+            sav_asdu: self.sav_asdu,
+        })
+    }
+}
+
+/// Upper bounds applied while decoding frames from untrusted sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DecodeLimits {
+    /** Maximum number of samples in the sample block of a single ASDU */
+    pub max_samples: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits { max_samples: 256 }
+    }
+}
+
+/// Options for the post-processing of encoded frames by the `_with_options` encoders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EncodeOptions {
+    /** Zero-pad frames shorter than the Ethernet minimum of 60 bytes (without FCS) */
+    pub pad_to_min_frame: bool,
+    /** Append the frame check sequence, for links that do not add it themselves */
+    pub append_fcs: bool,
+}
+
+/// How tolerant the manual decoders are towards malformed frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DecodeMode {
+    /// Recover from slightly wrong length fields and unexpected ASDU tags instead of
+    /// failing, reporting a warning
+    Lenient,
+    /// Tolerate encodings that are common in the field and report inconsistencies as
+    /// warning
+    #[default]
+    Default,
+    /// Fail on inconsistencies that are otherwise reported as warning or tolerated, e.g.
+    /// a GOOSE numDatSetEntries that does not match the data set or a SMV INTEGER that is
+    /// not minimally encoded
+    Strict,
+}
+
+/// Options controlling how tolerant the manual decoders are
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DecodeOptions {
+    /** Upper bounds for the decoded frame */
+    pub limits: DecodeLimits,
+    /** Tolerance towards malformed frames */
+    pub mode: DecodeMode,
+}
+
+impl DecodeOptions {
+    /// Whether the decoders recover from malformed frames
+    pub fn is_lenient(&self) -> bool {
+        self.mode == DecodeMode::Lenient
+    }
+
+    /// Whether the decoders reject encodings they otherwise tolerate
+    pub fn is_strict(&self) -> bool {
+        self.mode == DecodeMode::Strict
+    }
+}
+
+/// A recoverable inconsistency found while decoding, e.g. in lenient mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DecodeWarning {
     pub message: String,
     pub buffer_index: usize,
 }
 
-impl DecodeError {
+impl DecodeWarning {
     pub fn new(msg: &str, buffer_index: usize) -> Self {
-        let mut chars = ['\0'; 128];
-        for (i, c) in msg.chars().take(128).enumerate() {
-            chars[i] = c;
+        DecodeWarning {
+            message: msg.to_string(),
+            buffer_index,
         }
+    }
+}
 
-        DecodeError {
-            message: chars.iter().collect(),
-            buffer_index,
+/// Tally of the tags and length forms seen by the decoder, for profiling the
+/// encoding of unknown devices
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DecodeStats {
+    /** Number of occurrences per tag byte */
+    pub tags: BTreeMap<u8, usize>,
+    /** Number of occurrences per length form, keyed by the number of length octets
+    following the initial one (0 = short form, 1..=8 = long form) */
+    pub length_forms: BTreeMap<usize, usize>,
+}
+
+impl DecodeStats {
+    /// Counts a decoded tag and the form of its length
+    pub fn record(&mut self, tag: u8, long_length_octets: usize) {
+        *self.tags.entry(tag).or_default() += 1;
+        *self.length_forms.entry(long_length_octets).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_error_display() {
+        let err = DecodeError::new("Expected ASDU tag 0x30, got 0x31", 27);
+        assert_eq!(err.message, "Expected ASDU tag 0x30, got 0x31");
+        assert_eq!(
+            err.to_string(),
+            "Expected ASDU tag 0x30, got 0x31 (at byte 27)"
+        );
+
+        // Messages are no longer cut at 128 characters
+        let long = "x".repeat(200);
+        assert_eq!(DecodeError::new(&long, 0).message, long);
+    }
+
+    #[test]
+    fn test_encode_error_display() {
+        let err = EncodeError::new("Buffer too small to write tag and length.", 4);
+        assert_eq!(
+            err.to_string(),
+            "Buffer too small to write tag and length. (at byte 4)"
+        );
+
+        let err = EncodeError::BufferTooSmall {
+            required: 66,
+            available: 60,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Buffer too small: 66 bytes required, 60 available"
+        );
+    }
+
+    #[test]
+    fn test_errors_convert_into_boxed_error() {
+        fn decode() -> Result<(), Box<dyn std::error::Error>> {
+            Err(DecodeError::new("unexpected end of ASDU", 3))?
         }
+        fn encode() -> Result<(), Box<dyn std::error::Error>> {
+            Err(EncodeError::new(
+                "noASDU is 2, but the PDU holds 3 ASDUs",
+                0,
+            ))?
+        }
+
+        assert_eq!(
+            decode().unwrap_err().to_string(),
+            "unexpected end of ASDU (at byte 3)"
+        );
+        assert!(encode().unwrap_err().to_string().starts_with("noASDU is 2"));
+    }
+}
+
+#[cfg(test)]
+mod quality_tests {
+    use super::*;
+
+    #[test]
+    fn test_convenience_constructors() {
+        assert_eq!(Quality::good(), Quality::default());
+        assert!(Quality::good().is_good());
+        assert_eq!(Quality::invalid().to_u16(), 0x4000);
+        assert_eq!(Quality::good().with_test(true).to_u16(), 0x0010);
+        assert_eq!(Quality::good().test().with_test(false), Quality::good());
+    }
+
+    #[test]
+    fn test_builder_roundtrip() {
+        let qualities = [
+            Quality::builder().build(),
+            Quality::builder()
+                .validity(Validity::Invalid)
+                .overflow(true)
+                .build(),
+            Quality::builder()
+                .validity(Validity::Questionable)
+                .out_of_range(true)
+                .bad_reference(true)
+                .oscillatory(true)
+                .failure(true)
+                .old_data(true)
+                .inconsistent(true)
+                .inaccurate(true)
+                .substituted(true)
+                .test(true)
+                .operator_blocked(true)
+                .build(),
+        ];
+        for quality in qualities {
+            assert_eq!(Quality::from_u16(quality.to_u16()), quality);
+        }
+
+        assert_eq!(
+            Quality::builder().overflow(true).overflow(false).build(),
+            Quality::good()
+        );
+        assert_eq!(
+            Quality::builder()
+                .validity(Validity::Invalid)
+                .test(true)
+                .build(),
+            Quality::invalid().test()
+        );
+    }
+
+    #[test]
+    fn test_validity_try_from_u8() {
+        assert_eq!(Validity::try_from(0).unwrap(), Validity::Good);
+        assert_eq!(Validity::try_from(1).unwrap(), Validity::Invalid);
+        assert_eq!(Validity::try_from(2).unwrap(), Validity::Reserved);
+        assert_eq!(Validity::try_from(3).unwrap(), Validity::Questionable);
+        assert!(Validity::try_from(4).is_err());
+        assert!(Validity::try_from(0xFF).is_err());
+    }
+
+    #[test]
+    fn test_try_from_u16_rejects_reserved_validity() {
+        assert_eq!(
+            Quality::try_from_u16(0x0000).unwrap().validity,
+            Validity::Good
+        );
+        assert_eq!(
+            Quality::try_from_u16(0x4000).unwrap().validity,
+            Validity::Invalid
+        );
+        assert_eq!(
+            Quality::try_from_u16(0xC010).unwrap(),
+            Quality::with_validity(Validity::Questionable).test()
+        );
+        assert!(Quality::try_from_u16(0x8000).is_err());
+        assert!(Quality::try_from_u16(0xBFF8).is_err());
+
+        // The lenient decoder keeps the reserved value so it round-trips
+        assert_eq!(Quality::from_u16(0x8000).validity, Validity::Reserved);
+        assert_eq!(Quality::from_u16(0x8000).to_u16(), 0x8000);
+    }
+
+    #[test]
+    fn test_display_all_good() {
+        let quality = Quality::default();
+        assert_eq!(quality.to_string(), "good");
+        assert!(quality.flag_names().is_empty());
+    }
+
+    #[test]
+    fn test_display_single_flag() {
+        let quality = Quality::with_validity(Validity::Questionable).substituted();
+        assert_eq!(quality.to_string(), "questionable|substituted");
+        assert_eq!(quality.flag_names(), vec!["substituted"]);
+    }
+
+    #[test]
+    fn test_display_multiple_flags() {
+        let quality = Quality::with_validity(Validity::Invalid)
+            .test()
+            .overflow()
+            .operator_blocked();
+        assert_eq!(
+            quality.to_string(),
+            "invalid|overflow|test|operator_blocked"
+        );
+        assert_eq!(
+            quality.flag_names(),
+            vec!["overflow", "test", "operator_blocked"]
+        );
+        assert_eq!(Quality::from_u16(0xFFF8).flag_names().len(), 11);
+    }
+
+    #[test]
+    fn test_with_validity_and_flags() {
+        assert_eq!(
+            Quality::with_validity(Validity::Questionable)
+                .test()
+                .to_u16(),
+            0xC010
+        );
+        assert_eq!(Quality::with_validity(Validity::Good), Quality::default());
+        assert_eq!(Quality::with_validity(Validity::Invalid).to_u16(), 0x4000);
+
+        let quality = Quality::with_validity(Validity::Invalid)
+            .overflow()
+            .old_data()
+            .substituted()
+            .operator_blocked();
+        assert_eq!(quality.to_u16(), 0x4000 | 0x2000 | 0x0100 | 0x0020 | 0x0008);
+        assert_eq!(Quality::from_u16(quality.to_u16()), quality);
     }
 }
 
@@ -1568,6 +3430,102 @@ mod timestamp_tests {
         assert!((timestamp.fraction as i32 - expected_fraction as i32).abs() < 100);
     }
 
+    #[test]
+    fn test_timestamp_from_unix_timestamp_out_of_range() {
+        let quality = TimeQuality::default();
+
+        for invalid in [-1.0, -0.5, f64::NAN, 4294967296.0] {
+            assert!(
+                Timestamp::try_from_unix_timestamp(invalid, quality).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        let ok = Timestamp::try_from_unix_timestamp(1698502245.5, quality).unwrap();
+        assert_eq!(ok, Timestamp::from_unix_timestamp(1698502245.5, quality));
+
+        // The infallible variant clamps to the UtcTime range
+        let before_epoch = Timestamp::from_unix_timestamp(-1.0, quality);
+        assert_eq!((before_epoch.seconds, before_epoch.fraction), (0, 0));
+        let after_2106 = Timestamp::from_unix_timestamp(5e9, quality);
+        assert_eq!(after_2106.seconds, u32::MAX);
+    }
+
+    #[test]
+    fn test_timestamp_from_utc_string() {
+        let timestamp = Timestamp::from_utc_string("2023-10-28T14:10:45.500000Z").unwrap();
+        assert_eq!(timestamp.seconds, 1698502245);
+        assert_eq!(timestamp.fraction, 8_388_608);
+        assert_eq!(timestamp.quality, TimeQuality::default());
+
+        for s in [
+            "1970-01-01T00:00:00.000000Z",
+            "2000-02-29T23:59:59.999999Z",
+            "2024-10-28T14:30:45.123456Z",
+            "2024-12-31T00:00:00.000001Z",
+            "2025-01-01T00:00:00.000000Z",
+            "2106-02-07T06:28:15.999999Z",
+        ] {
+            assert_eq!(Timestamp::from_utc_string(s).unwrap().to_utc_string(), s);
+        }
+
+        // Midnight starts a new day
+        let before = Timestamp::from_utc_string("2024-02-28T23:59:59.000000Z").unwrap();
+        let midnight = Timestamp::from_utc_string("2024-02-29T00:00:00.000000Z").unwrap();
+        assert_eq!(midnight.seconds, before.seconds + 1);
+
+        for invalid in [
+            "",
+            "2024-10-28 14:30:45.123456Z",
+            "2024-10-28T14:30:45.123456",
+            "2024-10-28T14:30:45Z",
+            "2024-13-01T00:00:00.000000Z",
+            "2023-02-29T00:00:00.000000Z",
+            "2024-10-28T24:00:00.000000Z",
+            "1969-12-31T23:59:59.000000Z",
+            "2106-02-07T06:28:16.000000Z",
+            "2024-1a-28T14:30:45.123456Z",
+            "+024-10-28T14:30:45.123456Z",
+        ] {
+            assert!(Timestamp::from_utc_string(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_timestamp_system_time_roundtrip() {
+        let quality = TimeQuality::default();
+
+        // Every 24-bit fraction survives the round trip through nanoseconds
+        for fraction in [0, 1, 2, 8_388_608, 16_777_214, 16_777_215] {
+            let timestamp = Timestamp {
+                seconds: 1698502245,
+                fraction,
+                quality,
+            };
+            let back = Timestamp::from_system_time(timestamp.to_system_time(), quality);
+            assert_eq!(back, timestamp);
+        }
+
+        // A SystemTime comes back within one fraction step (about 60 ns)
+        let time = UNIX_EPOCH + Duration::new(1698502245, 123_456_789);
+        let back = Timestamp::from_system_time(time, quality).to_system_time();
+        let diff = match back.duration_since(time) {
+            Ok(d) => d,
+            Err(e) => e.duration(),
+        };
+        assert!(diff.as_nanos() <= 60, "{:?}", diff);
+
+        // Nanoseconds just below a full second do not overflow the fraction
+        let time = UNIX_EPOCH + Duration::new(10, 999_999_999);
+        let timestamp = Timestamp::from_system_time(time, quality);
+        assert_eq!((timestamp.seconds, timestamp.fraction), (10, 16_777_215));
+
+        let before_epoch =
+            Timestamp::from_system_time(UNIX_EPOCH - Duration::from_secs(1), quality);
+        assert_eq!((before_epoch.seconds, before_epoch.fraction), (0, 0));
+    }
+
     #[test]
     fn test_timestamp_utc_string_format() {
         let timestamp = Timestamp {
@@ -1583,6 +3541,28 @@ mod timestamp_tests {
     }
 
     #[test]
+    fn test_to_utc_string_leap_years_and_centuries() {
+        // Reference values from Python's calendar.timegm
+        for (seconds, expected) in [
+            (951825600, "2000-02-29T12:00:00.000000Z"),
+            (978220800, "2000-12-31T00:00:00.000000Z"),
+            (946684799, "1999-12-31T23:59:59.000000Z"),
+            (94608000, "1972-12-31T00:00:00.000000Z"),
+            (4107542399, "2100-02-28T23:59:59.000000Z"),
+            (4107542400, "2100-03-01T00:00:00.000000Z"),
+            (u32::MAX, "2106-02-07T06:28:15.000000Z"),
+        ] {
+            let timestamp = Timestamp {
+                seconds,
+                fraction: 0,
+                quality: TimeQuality::default(),
+            };
+            assert_eq!(timestamp.to_utc_string(), expected, "seconds {}", seconds);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_timestamp_serialization() {
         let timestamp = Timestamp {
             seconds: 1698502245,
@@ -1673,12 +3653,59 @@ mod time_quality_tests {
 
         assert_eq!(quality.accuracy_bits(), None);
     }
+
+    #[test]
+    fn test_time_quality_accuracy_as_seconds() {
+        let with_accuracy = |time_accuracy| TimeQuality {
+            time_accuracy,
+            ..Default::default()
+        };
+
+        assert_eq!(with_accuracy(0).accuracy_as_seconds(), Some(1.0));
+        assert_eq!(
+            with_accuracy(0).accuracy_as_duration(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(with_accuracy(10).accuracy_as_seconds(), Some(1.0 / 1024.0));
+        let nanos = with_accuracy(10).accuracy_as_duration().unwrap().as_nanos();
+        assert!((976_562..=976_563).contains(&nanos), "{}", nanos);
+        assert_eq!(with_accuracy(26).accuracy_as_seconds(), None);
+        assert_eq!(with_accuracy(31).accuracy_as_seconds(), None);
+        assert_eq!(with_accuracy(31).accuracy_as_duration(), None);
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod iec_data_conversion_tests {
     use super::*;
 
+    #[test]
+    fn test_every_variant_roundtrip() {
+        let data = vec![
+            IECData::Array(vec![IECData::Int(1), IECData::Int(2)]),
+            IECData::Structure(vec![IECData::Boolean(false), IECData::UInt(7)]),
+            IECData::Boolean(true),
+            IECData::BitString("0000000000001000".to_string()),
+            IECData::Int(-42),
+            IECData::UInt(u64::MAX),
+            IECData::Float(-1.5),
+            IECData::Float32(-1.5),
+            IECData::BitString("1100000000010".to_string()),
+            IECData::OctetString("deadbeef".to_string()),
+            IECData::VisibleString("IED1/LLN0".to_string()),
+            IECData::MmsString("text".to_string()),
+            IECData::Timestamp(Timestamp {
+                seconds: 1698502245,
+                fraction: 8_388_608,
+                quality: TimeQuality::default(),
+            }),
+        ];
+
+        let wire: Vec<IECDataRasn> = data.iter().map(IECDataRasn::from).collect();
+        let back: Vec<IECData> = wire.iter().map(IECData::from).collect();
+        assert_eq!(back, data);
+    }
+
     #[test]
     fn test_boolean_conversion() {
         let rasn = IECDataRasn::Boolean(true);
@@ -1726,6 +3753,17 @@ mod iec_data_conversion_tests {
         }
     }
 
+    #[test]
+    fn test_uint_conversion_above_i64_max() {
+        for val in [i64::MAX as u64 + 1, u64::MAX] {
+            let data = IECData::UInt(val);
+
+            let rasn = IECDataRasn::from(&data);
+            assert_eq!(rasn, IECDataRasn::UInt(Integer::from(val)));
+            assert_eq!(IECData::from(&rasn), data);
+        }
+    }
+
     #[test]
     fn test_float32_conversion() {
         let value = 3.14159f32;
@@ -1733,10 +3771,7 @@ mod iec_data_conversion_tests {
         let rasn = IECDataRasn::Float(FloatingPoint(OctetString::from(bytes.to_vec())));
         let data = IECData::from(&rasn);
 
-        match data {
-            IECData::Float(f) => assert!((f - value as f64).abs() < 0.0001),
-            _ => panic!("Expected Float variant"),
-        }
+        assert_eq!(data, IECData::Float32(value));
     }
 
     #[test]
@@ -1752,13 +3787,119 @@ mod iec_data_conversion_tests {
         }
 
         let back = IECDataRasn::from(&data);
-        // Verify it encodes as 8 bytes
+        // Verify it encodes as the descriptor plus 8 bytes
         match back {
-            IECDataRasn::Float(fp) => assert_eq!(fp.0.as_ref().len(), 8),
+            IECDataRasn::Float(fp) => {
+                assert_eq!(fp.0.as_ref().len(), 9);
+                assert_eq!(fp.0.as_ref()[0], 0x0B);
+                assert_eq!(&fp.0.as_ref()[1..], &value.to_be_bytes());
+            }
             _ => panic!("Expected Float variant"),
         }
     }
 
+    #[test]
+    fn test_float_with_exponent_width_descriptor() {
+        let mut float32 = vec![0x08];
+        float32.extend_from_slice(&230.5f32.to_be_bytes());
+        let rasn = IECDataRasn::Float(FloatingPoint(OctetString::from(float32)));
+        assert_eq!(IECData::from(&rasn), IECData::Float32(230.5));
+
+        let mut float64 = vec![0x0B];
+        float64.extend_from_slice(&3.141592653589793f64.to_be_bytes());
+        let rasn = IECDataRasn::Float(FloatingPoint(OctetString::from(float64)));
+        assert_eq!(IECData::from(&rasn), IECData::Float(3.141592653589793));
+    }
+
+    #[test]
+    fn test_floating_point_helpers() {
+        // 4 byte values, with and without the exponent width
+        let float32 = FloatingPoint::from_f32(230.5);
+        assert_eq!(float32.0.as_ref()[0], 0x08);
+        assert_eq!(float32.0.len(), 5);
+        assert_eq!(float32.as_f32(), Some(230.5));
+        assert_eq!(float32.as_f64(), Some(230.5));
+        let bare32 = FloatingPoint(OctetString::from(230.5f32.to_be_bytes().to_vec()));
+        assert_eq!(bare32.as_f32(), Some(230.5));
+
+        // 8 byte values, with and without the exponent width
+        let float64 = FloatingPoint::from_f64(-1.0e300);
+        assert_eq!(float64.0.as_ref()[0], 0x0B);
+        assert_eq!(float64.0.len(), 9);
+        assert_eq!(float64.as_f64(), Some(-1.0e300));
+        assert_eq!(float64.as_f32(), None);
+        let bare64 = FloatingPoint(OctetString::from(0.1f64.to_be_bytes().to_vec()));
+        assert_eq!(bare64.as_f64(), Some(0.1));
+
+        // Malformed lengths
+        for bytes in [
+            vec![],
+            vec![0x08, 0x00, 0x00],
+            vec![0x0B, 0, 0, 0, 0],
+            vec![0; 7],
+        ] {
+            let malformed = FloatingPoint(OctetString::from(bytes));
+            assert_eq!(malformed.as_f32(), None);
+            assert_eq!(malformed.as_f64(), None);
+        }
+    }
+
+    #[test]
+    fn test_iec_data_accessors() {
+        assert_eq!(IECData::Boolean(true).as_bool(), Some(true));
+        assert_eq!(IECData::Int(-5).as_i64(), Some(-5));
+        assert_eq!(IECData::UInt(7).as_i64(), Some(7));
+        assert_eq!(IECData::UInt(u64::MAX).as_i64(), None);
+        assert_eq!(IECData::UInt(u64::MAX).as_u64(), Some(u64::MAX));
+        assert_eq!(IECData::Int(42).as_u64(), Some(42));
+        assert_eq!(IECData::Int(-1).as_u64(), None);
+        assert_eq!(IECData::Float(1.5).as_f64(), Some(1.5));
+        assert_eq!(IECData::Int(-3).as_f64(), Some(-3.0));
+        assert_eq!(IECData::UInt(3).as_f64(), Some(3.0));
+        assert_eq!(
+            IECData::VisibleString("IED1".to_string()).as_str(),
+            Some("IED1")
+        );
+        assert_eq!(IECData::MmsString("Ä".to_string()).as_str(), Some("Ä"));
+
+        // Type mismatches
+        assert_eq!(IECData::Int(1).as_bool(), None);
+        assert_eq!(IECData::Boolean(true).as_i64(), None);
+        assert_eq!(IECData::Float(1.0).as_i64(), None);
+        assert_eq!(IECData::Float(1.0).as_u64(), None);
+        assert_eq!(IECData::BitString("01".to_string()).as_f64(), None);
+        assert_eq!(IECData::OctetString("0102".to_string()).as_str(), None);
+        assert_eq!(IECData::Structure(vec![]).as_bool(), None);
+
+        assert_eq!(IECData::UInt(1).type_name(), "UInt");
+        assert_eq!(IECData::Structure(vec![]).type_name(), "Structure");
+        assert_eq!(
+            IECData::Timestamp(Timestamp::default()).type_name(),
+            "Timestamp"
+        );
+    }
+
+    #[test]
+    fn test_iec_data_get_path() {
+        // {stVal, q, t} with a nested {mag {f}} and an array member
+        let data = IECData::Structure(vec![
+            IECData::Boolean(true),
+            IECData::Structure(vec![IECData::Float(1.5), IECData::Int(3)]),
+            IECData::Array(vec![IECData::UInt(10), IECData::UInt(20)]),
+        ]);
+
+        assert_eq!(data.get_path(&[]), Some(&data));
+        assert_eq!(data.get_path(&[0]), Some(&IECData::Boolean(true)));
+        assert_eq!(data.get_path(&[1, 0]), Some(&IECData::Float(1.5)));
+        assert_eq!(data.get_path(&[1, 1]), Some(&IECData::Int(3)));
+        assert_eq!(data.get_path(&[2, 1]), Some(&IECData::UInt(20)));
+
+        assert_eq!(data.get_path(&[3]), None);
+        assert_eq!(data.get_path(&[1, 2]), None);
+        assert_eq!(data.get_path(&[0, 0]), None);
+        assert_eq!(data.get_path(&[1, 0, 0]), None);
+    }
+
     #[test]
     fn test_float_edge_cases() {
         let values = vec![0.0f64, -0.0, 1.0, -1.0, f64::MIN, f64::MAX];
@@ -1819,6 +3960,8 @@ mod iec_data_conversion_tests {
             vec![0xFF],
             vec![0x01, 0x02, 0x03, 0x04],
             vec![0xDE, 0xAD, 0xBE, 0xEF],
+            // Opaque structure passed through verbatim
+            (0..20u8).map(|i| i.wrapping_mul(37) ^ 0xA5).collect(),
         ];
 
         for bytes in test_data {
@@ -2019,7 +4162,7 @@ mod iec_data_conversion_tests {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod goose_pdu_conversion_tests {
     use super::*;
 
@@ -2068,6 +4211,20 @@ mod goose_pdu_conversion_tests {
         assert_eq!(rasn_pdu, back);
     }
 
+    #[test]
+    fn test_goose_pdu_conversion_full_u32_range() {
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.st_num = u32::MAX;
+        pdu.sq_num = 0x8000_0000;
+
+        let rasn_pdu = IECGoosePduRasn::from(&pdu);
+        assert_eq!(rasn_pdu.st_num, Integer::from(u32::MAX as i64));
+
+        let back = IECGoosePdu::from(&rasn_pdu);
+        assert_eq!(back.st_num, u32::MAX);
+        assert_eq!(back.sq_num, 0x8000_0000);
+    }
+
     #[test]
     fn test_goose_pdu_json_serialization() {
         let timestamp = Timestamp {
@@ -2098,3 +4255,734 @@ mod goose_pdu_conversion_tests {
         assert_eq!(pdu, deserialized);
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod goose_pdu_tests {
+    use super::*;
+
+    fn pdu_at(t: Timestamp, time_allowed_to_live: u32) -> IECGoosePdu {
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.t = t;
+        pdu.time_allowed_to_live = time_allowed_to_live;
+        pdu
+    }
+
+    #[test]
+    fn test_is_stale_fresh_frame() {
+        let t = Timestamp::from_unix_timestamp(1_700_000_000.25, TimeQuality::default());
+        let pdu = pdu_at(t, 2000);
+
+        let now = Timestamp::from_unix_timestamp(1_700_000_001.5, TimeQuality::default());
+        assert!(!pdu.is_stale(&now));
+
+        // A receive time before the frame time is never stale
+        let earlier = Timestamp::from_unix_timestamp(1_699_999_999.0, TimeQuality::default());
+        assert!(!pdu.is_stale(&earlier));
+    }
+
+    #[test]
+    fn test_is_stale_expired_frame() {
+        let t = Timestamp::from_unix_timestamp(1_700_000_000.25, TimeQuality::default());
+        let pdu = pdu_at(t, 2000);
+
+        let now = Timestamp::from_unix_timestamp(1_700_000_002.5, TimeQuality::default());
+        assert!(pdu.is_stale(&now));
+
+        let much_later = Timestamp::from_unix_timestamp(1_700_000_060.0, TimeQuality::default());
+        assert!(pdu.is_stale(&much_later));
+    }
+
+    #[test]
+    fn test_validate_num_dat_set_entries() {
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.all_data = vec![IECData::Boolean(true), IECData::Int(5)];
+        pdu.num_dat_set_entries = 2;
+        assert_eq!(pdu.validate(), Ok(()));
+
+        pdu.num_dat_set_entries = 3;
+        assert_eq!(
+            pdu.validate(),
+            Err("numDatSetEntries is 3, but the data set holds 2 entries".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tick_and_state_change() {
+        let mut pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.t = Timestamp::default();
+        pdu.tick();
+        pdu.tick();
+        assert_eq!((pdu.st_num, pdu.sq_num), (0, 2));
+        assert_eq!(pdu.t, Timestamp::default());
+
+        pdu.state_change(vec![IECData::Boolean(true), IECData::Int(7)]);
+        assert_eq!((pdu.st_num, pdu.sq_num), (1, 0));
+        assert_ne!(pdu.t, Timestamp::default());
+        assert_eq!(pdu.num_dat_set_entries, 2);
+        assert_eq!(pdu.all_data[1], IECData::Int(7));
+        assert_eq!(pdu.validate(), Ok(()));
+
+        pdu.tick();
+        assert_eq!((pdu.st_num, pdu.sq_num), (1, 1));
+
+        // Both counters roll over to 1
+        pdu.st_num = u32::MAX;
+        pdu.sq_num = u32::MAX;
+        pdu.tick();
+        assert_eq!(pdu.sq_num, 1);
+        pdu.state_change(vec![]);
+        assert_eq!((pdu.st_num, pdu.sq_num), (1, 0));
+    }
+
+    #[test]
+    fn test_validate_for_encode() {
+        let pdu = IECGoosePdu::new("IED1/LLN0$GO$gcb1", "IED1/LLN0$DATASET1", "GOOSE1");
+        assert!(pdu.validate_for_encode().is_ok());
+
+        let message = |pdu: &IECGoosePdu| pdu.validate_for_encode().unwrap_err().to_string();
+        let mut invalid = pdu.clone();
+        invalid.go_cb_ref.clear();
+        assert!(message(&invalid).contains("gocbRef is empty"));
+        let mut invalid = pdu.clone();
+        invalid.dat_set.clear();
+        assert!(message(&invalid).contains("datSet is empty"));
+        let mut invalid = pdu.clone();
+        invalid.go_id.clear();
+        assert!(message(&invalid).contains("goID is empty"));
+        // A received frame without goID is consistent
+        assert_eq!(invalid.validate(), Ok(()));
+        let mut invalid = pdu.clone();
+        invalid.time_allowed_to_live = 0;
+        assert!(message(&invalid).contains("timeAllowedtoLive is 0"));
+        let mut invalid = pdu;
+        invalid.num_dat_set_entries = 1;
+        assert!(message(&invalid).contains("numDatSetEntries is 1"));
+    }
+}
+
+#[cfg(test)]
+mod mac_address_tests {
+    use super::*;
+
+    #[test]
+    fn test_mac_address_display() {
+        let mac = MacAddress([0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01]);
+        assert_eq!(mac.to_string(), "01:0c:cd:04:00:01");
+    }
+
+    #[test]
+    fn test_mac_address_parse() {
+        let mac: MacAddress = "01:0C:cd:04:00:FF".parse().unwrap();
+        assert_eq!(mac, MacAddress([0x01, 0x0c, 0xcd, 0x04, 0x00, 0xff]));
+
+        for invalid in [
+            "",
+            "01:0c:cd:04:00",
+            "01:0c:cd:04:00:01:02",
+            "01:0c:cd:04:00:1",
+            "01:0c:cd:04:00:zz",
+            "01:0c:cd:04:00:+f",
+            "01-0c-cd-04-00-01",
+        ] {
+            assert!(invalid.parse::<MacAddress>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_mac_address_round_trip() {
+        let bytes = [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c];
+        let mac = MacAddress::from(bytes);
+        assert_eq!(mac.to_string().parse::<MacAddress>().unwrap(), mac);
+        assert_eq!(<[u8; 6]>::from(mac), bytes);
+
+        let header = EthernetHeader::for_goose([0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01], bytes, [0, 1]);
+        assert_eq!(header.src_mac(), mac);
+        assert_eq!(header.dst_mac().to_string(), "01:0c:cd:01:00:01");
+    }
+}
+
+#[cfg(test)]
+mod ethernet_header_tests {
+    use super::*;
+
+    const DST: [u8; 6] = [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01];
+    const SRC: [u8; 6] = [0x00, 0x1a, 0xb6, 0x03, 0x2f, 0x1c];
+
+    #[test]
+    fn test_for_smv() {
+        let header = EthernetHeader::for_smv(DST, SRC, [0x40, 0x01]);
+        assert_eq!(header.ether_type, [0x88, 0xba]);
+        assert_eq!(header.dst_addr, DST);
+        assert_eq!(header.src_addr, SRC);
+        assert_eq!(header.appid, [0x40, 0x01]);
+        assert_eq!(header.tpid, None);
+        assert_eq!(header.tci, None);
+    }
+
+    #[test]
+    fn test_for_goose() {
+        let header = EthernetHeader::for_goose(DST, SRC, [0x00, 0x01]);
+        assert_eq!(header.ether_type, [0x88, 0xb8]);
+        assert_eq!(header.dst_addr, DST);
+        assert_eq!(header.src_addr, SRC);
+        assert_eq!(header.appid, [0x00, 0x01]);
+        assert_eq!(header.tpid, None);
+        assert_eq!(header.tci, None);
+    }
+
+    #[test]
+    fn test_vlan_priority() {
+        let mut header = EthernetHeader::for_smv(DST, SRC, [0x40, 0x01]);
+        assert_eq!(header.vlan_priority(), None);
+
+        header.tpid = Some([0x81, 0x00]);
+        for priority in 0..=7u8 {
+            // Priority in the upper 3 bits, DEI set and VLAN ID 0xFFF to check masking
+            let tci = ((priority as u16) << 13) | 0x1FFF;
+            header.tci = Some(tci.to_be_bytes());
+            assert_eq!(header.vlan_priority(), Some(priority));
+
+            header.tci = Some(((priority as u16) << 13).to_be_bytes());
+            assert_eq!(header.vlan_priority(), Some(priority));
+        }
+    }
+
+    #[test]
+    fn test_set_vlan() {
+        let mut header = EthernetHeader::for_goose(DST, SRC, [0x00, 0x01]);
+        assert_eq!(header.vlan_id(), None);
+
+        header.set_vlan(3, 10).unwrap();
+        assert_eq!(header.tpid, Some([0x81, 0x00]));
+        assert_eq!(header.tci, Some([0x60, 0x0A]));
+        assert_eq!(header.vlan_priority(), Some(3));
+        assert_eq!(header.vlan_id(), Some(10));
+
+        header.set_vlan(7, 4094).unwrap();
+        assert_eq!(header.tci, Some([0xEF, 0xFE]));
+        assert_eq!(header.vlan_id(), Some(4094));
+
+        // Out of range values leave the header unchanged
+        assert!(header.set_vlan(8, 10).is_err());
+        assert!(header.set_vlan(3, 4095).is_err());
+        assert_eq!(header.tci, Some([0xEF, 0xFE]));
+    }
+
+    #[test]
+    fn test_wire_roundtrip() {
+        let mut header = EthernetHeader::for_smv(DST, SRC, [0x40, 0x01]);
+        header.length = [0x00, 0x64];
+
+        let wire = header.to_wire();
+        assert_eq!(wire.len(), 22);
+        assert_eq!(EthernetHeader::from_wire(&wire).unwrap(), (header, 22));
+    }
+
+    #[test]
+    fn test_wire_roundtrip_with_vlan() {
+        let header = EthernetHeader {
+            tpid: Some([0x81, 0x00]),
+            tci: Some([0x80, 0x05]),
+            ..EthernetHeader::for_goose(DST, SRC, [0x00, 0x01])
+        };
+
+        let wire = header.to_wire();
+        assert_eq!(wire.len(), 26);
+        assert_eq!(EthernetHeader::from_wire(&wire).unwrap(), (header, 26));
+        assert!(EthernetHeader::from_wire(&wire[..25]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod goose_config_tests {
+    use super::*;
+
+    #[test]
+    fn test_pdu_from_default_config() {
+        let config = GooseConfig::default();
+        let runtime = GooseRuntime::default();
+
+        let pdu = config.to_pdu(&runtime);
+        assert_eq!(pdu.st_num, 0);
+        assert_eq!(pdu.sq_num, 0);
+        assert_eq!(pdu.conf_rev, 1);
+        assert_eq!(pdu.time_allowed_to_live, 4);
+        assert_eq!(pdu.t, Timestamp::default());
+        assert_eq!(pdu.num_dat_set_entries, 0);
+        assert!(pdu.validate().is_ok());
+        // The default configuration has no references yet
+        assert!(pdu.validate_for_encode().is_err());
+
+        let header = config.ethernet_header();
+        assert_eq!(header.ether_type, [0x88, 0xb8]);
+        assert_eq!(header.dst_addr, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_pdu_follows_runtime() {
+        let config = GooseConfig {
+            go_cb_ref: "IED1/LLN0$GO$gcb1".to_string(),
+            dat_set: "IED1/LLN0$DATASET1".to_string(),
+            go_id: "GOOSE1".to_string(),
+            all_data: vec![IECData::Boolean(true), IECData::Int(3)],
+            ..Default::default()
+        };
+        let runtime = GooseRuntime {
+            st_num: 7,
+            sq_num: 20,
+            timestamp: Timestamp::from_unix_timestamp(1698502245.5, TimeQuality::default()),
+        };
+
+        let pdu = config.to_pdu(&runtime);
+        assert_eq!(pdu.go_cb_ref, "IED1/LLN0$GO$gcb1");
+        assert_eq!((pdu.st_num, pdu.sq_num), (7, 20));
+        assert_eq!(pdu.t, runtime.timestamp);
+        assert_eq!(pdu.time_allowed_to_live, 2000);
+        assert_eq!(pdu.num_dat_set_entries, 2);
+        assert_eq!(pdu.all_data, config.all_data);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod goose_monitor_tests {
+    use super::*;
+
+    fn pdu(go_cb_ref: &str, time_allowed_to_live: u32) -> IECGoosePdu {
+        let mut pdu = IECGoosePdu::new(go_cb_ref, "IED1/LLN0$DATASET1", "GOOSE1");
+        pdu.time_allowed_to_live = time_allowed_to_live;
+        pdu
+    }
+
+    #[test]
+    fn test_on_time_arrivals_stay_alive() {
+        let start = Instant::now();
+        let mut monitor = GooseMonitor::new();
+
+        for i in 0..5 {
+            let arrival = start + Duration::from_millis(i * 1000);
+            monitor.on_frame(&pdu("IED1/LLN0$GO$gcb1", 2000), arrival);
+            let check = arrival + Duration::from_millis(1500);
+            assert!(!monitor.is_stale("IED1/LLN0$GO$gcb1", check));
+            assert_eq!(
+                monitor.time_until_stale("IED1/LLN0$GO$gcb1", check),
+                Some(Duration::from_millis(500))
+            );
+        }
+        assert!(monitor
+            .stale(start + Duration::from_millis(6000))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_late_arrival_is_stale() {
+        let start = Instant::now();
+        let mut monitor = GooseMonitor::new();
+        monitor.on_frame(&pdu("IED1/LLN0$GO$gcb1", 2000), start);
+        monitor.on_frame(&pdu("IED2/LLN0$GO$gcb1", 20), start);
+
+        let now = start + Duration::from_millis(2000);
+        assert!(!monitor.is_stale("IED1/LLN0$GO$gcb1", now));
+        assert_eq!(
+            monitor.time_until_stale("IED1/LLN0$GO$gcb1", now),
+            Some(Duration::ZERO)
+        );
+        assert!(monitor.is_stale("IED2/LLN0$GO$gcb1", now));
+        assert_eq!(monitor.time_until_stale("IED2/LLN0$GO$gcb1", now), None);
+        assert_eq!(monitor.stale(now), vec!["IED2/LLN0$GO$gcb1"]);
+
+        let later = start + Duration::from_millis(2001);
+        assert!(monitor.is_stale("IED1/LLN0$GO$gcb1", later));
+
+        // The late frame revives the publisher
+        monitor.on_frame(&pdu("IED1/LLN0$GO$gcb1", 2000), later);
+        assert!(!monitor.is_stale("IED1/LLN0$GO$gcb1", later));
+    }
+
+    #[test]
+    fn test_unknown_publisher() {
+        let monitor = GooseMonitor::new();
+        let now = Instant::now();
+        assert!(!monitor.is_stale("IED1/LLN0$GO$gcb1", now));
+        assert_eq!(monitor.time_until_stale("IED1/LLN0$GO$gcb1", now), None);
+    }
+}
+
+#[cfg(test)]
+mod retransmission_schedule_tests {
+    use super::*;
+
+    #[test]
+    fn test_next_counter_rolls_over_to_1() {
+        assert_eq!(next_counter(0), 1);
+        assert_eq!(next_counter(41), 42);
+        assert_eq!(next_counter(u32::MAX), 1);
+    }
+
+    #[test]
+    fn test_interval_doubles_up_to_heartbeat() {
+        let schedule = RetransmissionSchedule {
+            min_interval_ms: 4,
+            max_interval_ms: 1000,
+        };
+
+        let intervals: Vec<u32> = (0..10).map(|sq_num| schedule.interval_ms(sq_num)).collect();
+        assert_eq!(intervals, vec![4, 8, 16, 32, 64, 128, 256, 512, 1000, 1000]);
+        assert_eq!(schedule.interval_ms(u32::MAX), 1000);
+    }
+
+    #[test]
+    fn test_goose_retransmit_ramp_after_event() {
+        let schedule = RetransmissionSchedule {
+            min_interval_ms: 4,
+            max_interval_ms: 1000,
+        };
+        let mut retransmit = GooseRetransmit::new(schedule, 5);
+        for _ in 0..3 {
+            retransmit.next_interval();
+        }
+
+        retransmit.on_event();
+        assert_eq!((retransmit.st_num(), retransmit.sq_num()), (6, 0));
+
+        let mut ramp = vec![];
+        for sq_num in 1..=10 {
+            ramp.push(retransmit.next_interval());
+            assert_eq!((retransmit.st_num(), retransmit.sq_num()), (6, sq_num));
+        }
+        assert_eq!(ramp, vec![4, 8, 16, 32, 64, 128, 256, 512, 1000, 1000]);
+
+        // A new event restarts the ramp
+        retransmit.on_event();
+        assert_eq!((retransmit.st_num(), retransmit.sq_num()), (7, 0));
+        assert_eq!(retransmit.next_interval(), 4);
+    }
+
+    #[test]
+    fn test_goose_retransmit_roll_over() {
+        let mut retransmit = GooseRetransmit::new(RetransmissionSchedule::default(), u32::MAX);
+        retransmit.on_event();
+        assert_eq!(retransmit.st_num(), 1);
+
+        retransmit.sq_num = u32::MAX;
+        assert_eq!(retransmit.next_interval(), 1000);
+        assert_eq!(retransmit.sq_num(), 1);
+    }
+}
+
+#[cfg(test)]
+mod sample_tests {
+    use super::*;
+
+    #[test]
+    fn test_round_matches_std() {
+        for value in [
+            0.0,
+            0.49999999999999994,
+            0.5,
+            -0.5,
+            1.5,
+            2.5,
+            -2.5,
+            1e10 + 0.5,
+            -7.3,
+            8.7,
+        ] {
+            assert_eq!(round(value), value.round(), "{}", value);
+        }
+        assert_eq!(round(f64::NAN) as i32, f64::NAN.round() as i32);
+        assert_eq!(round(f64::INFINITY) as i32, i32::MAX);
+    }
+
+    #[test]
+    fn test_raw_quality_matches_quality_word() {
+        for quality_bits in [
+            0x0000, 0x4000, 0xC000, 0x2000, 0x0100, 0x0010, 0x0008, 0xFFF8,
+        ] {
+            let sample = Sample::new(1000, quality_bits);
+            assert_eq!(sample.raw_quality(), quality_bits);
+        }
+    }
+
+    #[test]
+    fn test_raw_quality_of_structured_quality() {
+        let quality = Quality {
+            validity: Validity::Invalid,
+            overflow: true,
+            test: true,
+            ..Default::default()
+        };
+        let sample = Sample::from_parts(-5, quality);
+
+        assert_eq!(sample.raw_quality(), 0x6010);
+        assert_eq!(Sample::new(-5, sample.raw_quality()), sample);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sample_value_serde_keeps_plain_numbers() {
+        let int = Sample::new(1000, 0);
+        let float = Sample::new_float(1.5, 0);
+
+        let int_json = serde_json::to_string(&int).unwrap();
+        let float_json = serde_json::to_string(&float).unwrap();
+        assert!(int_json.contains("\"value\":1000"), "{}", int_json);
+        assert!(float_json.contains("\"value\":1.5"), "{}", float_json);
+
+        assert_eq!(serde_json::from_str::<Sample>(&int_json).unwrap(), int);
+        assert_eq!(serde_json::from_str::<Sample>(&float_json).unwrap(), float);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sav_pdu_json_roundtrip() {
+        let pdu = SavPdu {
+            sim: true,
+            no_asdu: 1,
+            security: None,
+            sav_asdu: vec![SavAsdu {
+                msv_id: "MU01".to_string(),
+                dat_set: Some("IED1/LLN0$PhsMeas1".to_string()),
+                smp_cnt: 7,
+                conf_rev: 1,
+                refr_tm: Some([0x65, 0x53, 0xf1, 0x00, 0x80, 0x00, 0x00, 0x0a]),
+                smp_synch: 2,
+                smp_rate: Some(4000),
+                all_data: vec![
+                    Sample::new(1000, 0),
+                    Sample::new_float(1.5, 0x4000 | 0x0008),
+                ],
+                smp_mod: Some(1),
+                gm_identity: Some([0x00, 0x1a, 0xb6, 0xff, 0xfe, 0x03, 0x2f, 0x1c]),
+            }],
+        };
+
+        let json = serde_json::to_string(&pdu).unwrap();
+        assert!(
+            json.contains("\"refr_tm\":\"6553f1008000000a\""),
+            "{}",
+            json
+        );
+        assert!(
+            json.contains("\"gm_identity\":\"001ab6fffe032f1c\""),
+            "{}",
+            json
+        );
+        assert!(
+            json.contains("{\"value\":1000,\"quality\":{\"validity\":"),
+            "{}",
+            json
+        );
+        assert_eq!(serde_json::from_str::<SavPdu>(&json).unwrap(), pdu);
+
+        // Absent optional fields stay absent
+        let asdu: SavAsdu = serde_json::from_str(
+            r#"{"msv_id":"MU01","dat_set":null,"smp_cnt":0,"conf_rev":1,"smp_synch":0,"smp_rate":null,"all_data":[],"smp_mod":null}"#,
+        )
+        .unwrap();
+        assert_eq!(asdu.refr_tm, None);
+        assert_eq!(asdu.gm_identity, None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_quality_hex() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct CompactSample {
+            value: SampleValue,
+            #[serde(with = "crate::types::quality_hex")]
+            quality: Quality,
+        }
+
+        let sample = CompactSample {
+            value: SampleValue::Float(1.5),
+            quality: Quality::from_u16(0x4008),
+        };
+        let json = serde_json::to_string(&sample).unwrap();
+        assert_eq!(json, "{\"value\":1.5,\"quality\":\"0x4008\"}");
+        assert_eq!(
+            serde_json::from_str::<CompactSample>(&json).unwrap(),
+            sample
+        );
+        assert!(serde_json::from_str::<CompactSample>(r#"{"value":1,"quality":"zz"}"#).is_err());
+    }
+}
+
+#[cfg(test)]
+mod sample_counter_tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_at_rate() {
+        // 80 samples per cycle at 50 Hz
+        let mut counter = SampleCounter::new(4000);
+        counter.count = 3998;
+        assert_eq!(counter.next(), 3998);
+        assert_eq!(counter.next(), 3999);
+        assert_eq!(counter.next(), 0);
+        assert_eq!(counter.next(), 1);
+    }
+
+    #[test]
+    fn test_wraps_at_65536_without_rate() {
+        let mut counter = SampleCounter::new(0);
+        assert_eq!(counter.next(), 0);
+        counter.count = u16::MAX;
+        assert_eq!(counter.next(), u16::MAX);
+        assert_eq!(counter.next(), 0);
+    }
+
+    #[test]
+    fn test_count_above_rate_wraps() {
+        let mut counter = SampleCounter {
+            rate: 10,
+            count: 23,
+        };
+        assert_eq!(counter.next(), 3);
+        assert_eq!(counter.next(), 4);
+
+        counter.count = 10;
+        assert_eq!(counter.next(), 0);
+        assert_eq!(counter.next(), 1);
+    }
+}
+
+#[cfg(test)]
+mod sav_builder_tests {
+    use super::*;
+    use crate::decode_smv::decode_smv;
+    use crate::encode_smv::encode_smv;
+
+    #[test]
+    fn test_build_and_encode_two_asdus() {
+        let good = Quality::default();
+        let asdus: Vec<SavAsdu> = (0..2u16)
+            .map(|smp_cnt| {
+                SavAsduBuilder::new()
+                    .msv_id("MU01")
+                    .smp_cnt(smp_cnt)
+                    .conf_rev(1)
+                    .smp_synch(2)
+                    .smp_rate(4000)
+                    .sample(1000, good)
+                    .sample(-1000, Quality::with_validity(Validity::Questionable))
+                    .build()
+            })
+            .collect();
+        assert_eq!(asdus[1].smp_cnt, 1);
+        assert_eq!(asdus[1].smp_rate, Some(4000));
+        assert_eq!(asdus[1].all_data[1], Sample::new(-1000, 0xC000));
+        assert_eq!(asdus[1].dat_set, None);
+
+        let pdu = SavPduBuilder::new()
+            .asdu(asdus[0].clone())
+            .asdu(asdus[1].clone())
+            .build()
+            .unwrap();
+        assert_eq!(pdu.no_asdu, 2);
+        assert!(!pdu.sim);
+
+        let header = EthernetHeader::for_smv([0x01; 6], [0x02; 6], [0x40, 0x00]);
+        let frame = encode_smv(&header, &pdu).unwrap();
+        assert_eq!(decode_smv(&frame, 22).unwrap(), pdu);
+    }
+
+    #[test]
+    fn test_sav_pdu_builder_rejects_too_many_asdus() {
+        let builder = (0..=u16::MAX as usize).fold(SavPduBuilder::new(), |builder, _| {
+            builder.asdu(SavAsdu::default())
+        });
+        assert!(builder.build().is_err());
+    }
+}
+
+#[cfg(test)]
+mod sav_asdu_tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_time() {
+        let mut asdu = SavAsdu::default();
+        assert_eq!(asdu.refresh_time(), None);
+
+        // Fraction 0x800000 is half a second, quality: clock not synchronized, 10 bit accuracy
+        asdu.refr_tm = Some([0x65, 0x4a, 0x2c, 0x80, 0x80, 0x00, 0x00, 0x2a]);
+        let refresh_time = asdu.refresh_time().unwrap();
+        assert_eq!(refresh_time.seconds, 0x654a2c80);
+        assert_eq!(refresh_time.fraction, 0x800000);
+        assert_eq!(refresh_time.fraction_as_nanos(), 500_000_000);
+        assert!(refresh_time.quality.clock_not_synchronized);
+        assert_eq!(refresh_time.quality.time_accuracy, 10);
+    }
+
+    #[test]
+    fn test_set_refresh_time_roundtrip() {
+        let ts = Timestamp {
+            seconds: 1_700_000_000,
+            fraction: 0x123456,
+            quality: TimeQuality::from_byte(0x0a),
+        };
+        let mut asdu = SavAsdu::default();
+
+        asdu.set_refresh_time(&ts);
+
+        assert_eq!(asdu.refr_tm, Some(ts.to_bytes()));
+        assert_eq!(asdu.refresh_time(), Some(ts));
+    }
+
+    #[test]
+    fn test_smp_mod_conversion() {
+        assert_eq!(SmpMod::from(0), SmpMod::SamplesPerPeriod);
+        assert_eq!(SmpMod::from(1), SmpMod::SamplesPerSecond);
+        assert_eq!(SmpMod::from(2), SmpMod::SecondsPerSample);
+        assert_eq!(SmpMod::from(3), SmpMod::Other(3));
+        assert_eq!(SmpMod::from(u16::MAX), SmpMod::Other(u16::MAX));
+
+        for value in [0u16, 1, 2, 3, 100, u16::MAX] {
+            assert_eq!(u16::from(SmpMod::from(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_sample_mode() {
+        let mut asdu = SavAsdu::default();
+        assert_eq!(asdu.sample_mode(), None);
+
+        asdu.smp_mod = Some(1);
+        assert_eq!(asdu.sample_mode(), Some(SmpMod::SamplesPerSecond));
+
+        asdu.smp_mod = Some(7);
+        assert_eq!(asdu.sample_mode(), Some(SmpMod::Other(7)));
+    }
+
+    #[test]
+    fn test_sample_period_us() {
+        let mut asdu = SavAsdu {
+            smp_rate: Some(4000),
+            smp_mod: Some(u16::from(SmpMod::SamplesPerSecond)),
+            ..Default::default()
+        };
+        assert_eq!(asdu.sample_period_us(50.0), Some(250.0));
+
+        asdu.smp_rate = Some(80);
+        asdu.smp_mod = Some(u16::from(SmpMod::SamplesPerPeriod));
+        assert_eq!(asdu.sample_period_us(50.0), Some(250.0));
+        assert_eq!(asdu.sample_period_us(0.0), None);
+
+        // smpMod absent defaults to samples per period
+        asdu.smp_mod = None;
+        assert_eq!(asdu.sample_period_us(50.0), Some(250.0));
+
+        asdu.smp_rate = Some(2);
+        asdu.smp_mod = Some(u16::from(SmpMod::SecondsPerSample));
+        assert_eq!(asdu.sample_period_us(50.0), Some(2_000_000.0));
+
+        asdu.smp_mod = Some(9);
+        assert_eq!(asdu.sample_period_us(50.0), None);
+
+        asdu.smp_mod = None;
+        asdu.smp_rate = Some(0);
+        assert_eq!(asdu.sample_period_us(50.0), None);
+        asdu.smp_rate = None;
+        assert_eq!(asdu.sample_period_us(50.0), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! Live capture on the loopback interface. Opening a datalink channel needs root or
+//! CAP_NET_RAW, without it the test passes without checking anything.
+#![cfg(feature = "pnet")]
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use iec_61850::capture::subscribe_while;
+use iec_61850::encode_goose::encode_goose;
+use iec_61850::types::{EthernetHeader, IECData, IECGoosePdu};
+use pnet::datalink::{self, Channel};
+
+const LOOPBACK: &str = "lo";
+
+#[test]
+fn test_subscribe_receives_goose_on_loopback() {
+    let Some(interface) = datalink::interfaces()
+        .into_iter()
+        .find(|interface| interface.name == LOOPBACK)
+    else {
+        eprintln!("skipped: no {} interface", LOOPBACK);
+        return;
+    };
+    let mut tx = match datalink::channel(&interface, Default::default()) {
+        Ok(Channel::Ethernet(tx, _)) => tx,
+        _ => {
+            eprintln!("skipped: cannot open a datalink channel on {}", LOOPBACK);
+            return;
+        }
+    };
+
+    let header = EthernetHeader::for_goose(
+        [0x01, 0x0c, 0xcd, 0x01, 0x00, 0x01],
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+        [0x3f, 0x17],
+    );
+    let mut pdu = IECGoosePdu::new("LOOP/LLN0$GO$gcb1", "LOOP/LLN0$DATASET1", "LOOP");
+    pdu.all_data = vec![IECData::Boolean(true)];
+    pdu.num_dat_set_entries = 1;
+    let frame = encode_goose(&header, &pdu).unwrap();
+
+    // The receiving thread blocks in the capture loop until our frame shows up
+    let (received_tx, received_rx) = mpsc::channel();
+    let expected = frame.clone();
+    thread::spawn(move || {
+        subscribe_while(LOOPBACK, |frame| {
+            if frame == expected.as_slice() {
+                let _ = received_tx.send(());
+                return false;
+            }
+            true
+        })
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        tx.send_to(&frame, None);
+        if received_rx.recv_timeout(Duration::from_millis(100)).is_ok() {
+            return;
+        }
+    }
+    panic!("GOOSE frame not received on {}", LOOPBACK);
+}
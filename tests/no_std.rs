@@ -0,0 +1,86 @@
+//! The API that remains without the `std` feature. CI runs this target with
+//! `cargo test --no-default-features --test no_std`, so it may only use what the crate
+//! exports in that configuration.
+
+use iec_61850::decode_basics::try_decode_ethernet_header;
+use iec_61850::encode_basics::{append_fcs, ethernet_fcs};
+use iec_61850::types::{EthernetHeader, Quality, Sample, SavAsdu, SavPdu, Timestamp};
+use iec_61850::{
+    decode_smv, decode_tag, decode_tag_length, encode_ber, encode_smv, encode_tag_length,
+    EncodeError,
+};
+
+#[test]
+fn test_encode_tag_length() {
+    let mut buffer = [0u8; 8];
+    assert_eq!(encode_tag_length(0x80, 5, &mut buffer, 0).unwrap(), 2);
+    assert_eq!(buffer[..2], [0x80, 0x05]);
+
+    assert_eq!(encode_tag_length(0x61, 300, &mut buffer, 0).unwrap(), 4);
+    assert_eq!(buffer[..4], [0x61, 0x82, 0x01, 0x2c]);
+
+    assert!(encode_tag_length(0x61, 300, &mut buffer, 6).is_err());
+}
+
+#[test]
+fn test_encode_ber_decode_tag_length_roundtrip() {
+    let value = [0xaa; 200];
+    let mut buffer = [0u8; 256];
+    let end = encode_ber(0x89, &value, &mut buffer, 1).unwrap();
+    assert_eq!(end, 1 + 3 + value.len());
+
+    let mut tag = 0u8;
+    let mut length = 0usize;
+    let pos = decode_tag_length(&mut tag, &mut length, &buffer, 1).unwrap();
+    assert_eq!((tag, length, pos), (0x89, 200, 4));
+    assert_eq!(buffer[pos..pos + length], value);
+
+    let err = encode_ber(0x89, &value, &mut buffer[..100], 0).unwrap_err();
+    assert!(matches!(err, EncodeError::General { .. }));
+    assert!(decode_tag_length(&mut tag, &mut length, &buffer[..2], 1).is_err());
+}
+
+#[test]
+fn test_decode_tag_high_tag_number() {
+    // [31] and [40] share the first identifier octet
+    assert_eq!(decode_tag(&[0x9f, 0x1f, 0x00], 0).unwrap(), (0x9f, 31, 2));
+    assert_eq!(decode_tag(&[0x9f, 0x28, 0x00], 0).unwrap(), (0x9f, 40, 2));
+}
+
+#[test]
+fn test_smv_roundtrip() {
+    let header = EthernetHeader::for_smv(
+        [0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01],
+        [0x02; 6],
+        [0x40, 0x00],
+    );
+    let pdu = SavPdu {
+        sim: false,
+        no_asdu: 1,
+        security: None,
+        sav_asdu: vec![SavAsdu {
+            msv_id: "MU01".to_string(),
+            smp_cnt: 7,
+            all_data: vec![Sample::from_parts(-1200, Quality::default()); 8],
+            ..Default::default()
+        }],
+    };
+
+    let mut frame = encode_smv(&header, &pdu).unwrap();
+    let mut decoded_header = EthernetHeader::default();
+    let pos = try_decode_ethernet_header(&mut decoded_header, &frame).unwrap();
+    assert_eq!(decoded_header.appid, [0x40, 0x00]);
+    assert_eq!(decode_smv(&frame, pos).unwrap(), pdu);
+
+    let length = frame.len();
+    append_fcs(&mut frame);
+    assert_eq!(ethernet_fcs(&frame), 0x2144_df1c);
+    assert_eq!(frame.len(), length + 4);
+}
+
+#[test]
+fn test_timestamp_without_clock() {
+    let t = Timestamp::from_utc_string("2024-10-28T14:30:45.123456Z").unwrap();
+    assert_eq!(Timestamp::from_bytes(t.to_bytes()), t);
+    assert_eq!(t.to_utc_string(), "2024-10-28T14:30:45.123456Z");
+}